@@ -0,0 +1,487 @@
+//! # 派生宏
+//! 只用编译器自带的 `proc_macro` API，不依赖 syn、quote：
+//! * `#[derive(Summary)]`：按 `#[summary(format = "...")]` 中的格式字符串实现 `Summary` 特征
+//! * `#[derive(FieldDisplay)]`：每个字段输出一段 `字段名: 值`，实现 `Display` 特征
+//!
+//! 只支持没有泛型参数、有命名字段的结构体（或单元结构体）。
+//! 属性写错时展开成 `compile_error!`，错误指向出错的那一段代码，而不是整个 derive
+
+use std::iter::Peekable;
+
+use proc_macro::{token_stream, Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// # 实现 `Summary` 特征
+/// `Summary` 需要在调用处的作用域中，格式字符串中的 `{name}` 替换成 `self.name`，
+/// 支持 `format!` 的格式说明，比如 `{title:>10}`、`{count:?}`，`{{`、`}}` 是花括号本身
+///
+/// ```
+/// use head_first_rust_derive::Summary;
+///
+/// trait Summary {
+///     fn summarize(&self) -> String;
+/// }
+///
+/// #[derive(Summary)]
+/// #[summary(format = "{username} 发表了微博=> {content}")]
+/// struct Weibo {
+///     username: String,
+///     content: String,
+/// }
+///
+/// let weibo = Weibo { username: "重".to_string(), content: "我发了一条微博".to_string() };
+/// assert_eq!(weibo.summarize(), "重 发表了微博=> 我发了一条微博");
+/// ```
+///
+/// 格式字符串中的字段必须存在：
+///
+/// ```compile_fail
+/// # use head_first_rust_derive::Summary;
+/// # trait Summary { fn summarize(&self) -> String; }
+/// #[derive(Summary)]
+/// #[summary(format = "{titel}")]
+/// struct Post {
+///     title: String,
+/// }
+/// ```
+///
+/// 不能用位置参数：
+///
+/// ```compile_fail
+/// # use head_first_rust_derive::Summary;
+/// # trait Summary { fn summarize(&self) -> String; }
+/// #[derive(Summary)]
+/// #[summary(format = "{} {0}")]
+/// struct Post {
+///     title: String,
+/// }
+/// ```
+///
+/// 必须有 `#[summary(format = "...")]`：
+///
+/// ```compile_fail
+/// # use head_first_rust_derive::Summary;
+/// # trait Summary { fn summarize(&self) -> String; }
+/// #[derive(Summary)]
+/// struct Post {
+///     title: String,
+/// }
+/// ```
+///
+/// 不支持枚举：
+///
+/// ```compile_fail
+/// # use head_first_rust_derive::Summary;
+/// # trait Summary { fn summarize(&self) -> String; }
+/// #[derive(Summary)]
+/// #[summary(format = "x")]
+/// enum Message {
+///     Quit,
+/// }
+/// ```
+#[proc_macro_derive(Summary, attributes(summary))]
+pub fn derive_summary(input: TokenStream) -> TokenStream {
+    parse_input(input).and_then(|input| expand_summary(&input)).unwrap_or_else(|e| e.to_compile_error())
+}
+
+/// # 实现 `Display` 特征
+/// 默认每个字段一行，格式是 `Title: 值`，字段名首字母大写、`_` 换成空格。
+/// * 结构体上的 `#[display(separator = ", ")]` 修改字段之间的分隔符
+/// * 字段上的 `#[display(rename = "标题")]` 修改显示的名字，`#[display(skip)]` 不显示这个字段
+///
+/// ```
+/// use head_first_rust_derive::FieldDisplay;
+///
+/// #[derive(FieldDisplay)]
+/// #[display(separator = ", ")]
+/// struct User {
+///     username: String,
+///     #[display(rename = "邮箱")]
+///     email: String,
+///     #[display(skip)]
+///     password: String,
+///     sign_in_count: u64,
+/// }
+///
+/// let user = User { username: "foo".into(), email: "foo@qq.com".into(), password: "123".into(), sign_in_count: 1 };
+/// assert_eq!(user.to_string(), "Username: foo, 邮箱: foo@qq.com, Sign in count: 1");
+/// ```
+///
+/// 不认识的选项：
+///
+/// ```compile_fail
+/// # use head_first_rust_derive::FieldDisplay;
+/// #[derive(FieldDisplay)]
+/// struct User {
+///     #[display(hidden)]
+///     password: String,
+/// }
+/// ```
+///
+/// 不支持元组结构体：
+///
+/// ```compile_fail
+/// # use head_first_rust_derive::FieldDisplay;
+/// #[derive(FieldDisplay)]
+/// struct Color(i32, i32, i32);
+/// ```
+#[proc_macro_derive(FieldDisplay, attributes(display))]
+pub fn derive_field_display(input: TokenStream) -> TokenStream {
+    parse_input(input).and_then(|input| expand_field_display(&input)).unwrap_or_else(|e| e.to_compile_error())
+}
+
+/// 带位置的错误，展开成 `compile_error!("...")`
+struct Error {
+    span: Span,
+    message: String,
+}
+
+impl Error {
+    fn new(span: Span, message: impl Into<String>) -> Self {
+        Error { span, message: message.into() }
+    }
+
+    /// 每个 token 都用出错的位置，编译器就会把错误标在那里
+    fn to_compile_error(&self) -> TokenStream {
+        let mut message = Literal::string(&self.message);
+        message.set_span(self.span);
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(self.span);
+        let mut body = Group::new(Delimiter::Parenthesis, TokenStream::from(TokenTree::Literal(message)));
+        body.set_span(self.span);
+        let mut semi = Punct::new(';', Spacing::Alone);
+        semi.set_span(self.span);
+        TokenStream::from_iter([
+            TokenTree::Ident(Ident::new("compile_error", self.span)),
+            TokenTree::Punct(bang),
+            TokenTree::Group(body),
+            TokenTree::Punct(semi),
+        ])
+    }
+}
+
+type Tokens = Peekable<token_stream::IntoIter>;
+
+/// `#[name(...)]` 形式的属性，`#[doc = "..."]` 这样没有括号的 args 为 None
+struct Attribute {
+    name: Ident,
+    args: Option<Group>,
+}
+
+struct Field {
+    name: Ident,
+    attrs: Vec<Attribute>,
+}
+
+struct Input {
+    name: Ident,
+    attrs: Vec<Attribute>,
+    fields: Vec<Field>,
+}
+
+fn is_punct(token: Option<&TokenTree>, ch: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(p)) if p.as_char() == ch)
+}
+
+fn parse_attributes(tokens: &mut Tokens) -> Vec<Attribute> {
+    let mut attrs = vec![];
+    while is_punct(tokens.peek(), '#') {
+        tokens.next();
+        let Some(TokenTree::Group(group)) = tokens.next() else { break };
+        let mut inner = group.stream().into_iter();
+        if let Some(TokenTree::Ident(name)) = inner.next() {
+            let args = match inner.next() {
+                Some(TokenTree::Group(args)) if args.delimiter() == Delimiter::Parenthesis => Some(args),
+                _ => None,
+            };
+            attrs.push(Attribute { name, args });
+        }
+    }
+    attrs
+}
+
+/// `pub`、`pub(crate)`、`pub(in path)`
+fn skip_visibility(tokens: &mut Tokens) {
+    if matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+        tokens.next();
+        if matches!(tokens.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis) {
+            tokens.next();
+        }
+    }
+}
+
+fn parse_input(input: TokenStream) -> Result<Input, Error> {
+    let mut tokens = input.into_iter().peekable();
+    let attrs = parse_attributes(&mut tokens);
+    skip_visibility(&mut tokens);
+    let keyword = match tokens.next() {
+        Some(TokenTree::Ident(keyword)) => keyword,
+        other => return Err(Error::new(other.map_or(Span::call_site(), |t| t.span()), "应该是结构体定义")),
+    };
+    if keyword.to_string() != "struct" {
+        return Err(Error::new(keyword.span(), format!("只支持有命名字段的结构体，不支持 {}", keyword)));
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name,
+        other => return Err(Error::new(other.map_or(Span::call_site(), |t| t.span()), "缺少结构体名")),
+    };
+    let fields = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => parse_fields(group.stream())?,
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            return Err(Error::new(name.span(), "不支持元组结构体，字段需要有名字"));
+        }
+        Some(TokenTree::Punct(p)) if p.as_char() == ';' => vec![],
+        Some(TokenTree::Punct(p)) if p.as_char() == '<' => return Err(Error::new(p.span(), "不支持泛型参数")),
+        other => return Err(Error::new(other.map_or(name.span(), |t| t.span()), "无法解析的结构体定义")),
+    };
+    Ok(Input { name, attrs, fields })
+}
+
+/// 按顶层的逗号拆分字段。类型中的 `<...>` 不是 Group，要自己数尖括号，
+/// `->` 中的 `>` 不算
+fn parse_fields(stream: TokenStream) -> Result<Vec<Field>, Error> {
+    let mut chunks: Vec<Vec<TokenTree>> = vec![vec![]];
+    let mut depth = 0usize;
+    let mut prev_dash = false;
+    for token in stream {
+        let dash = is_punct(Some(&token), '-');
+        match &token {
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' && !prev_dash => depth = depth.saturating_sub(1),
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => {
+                chunks.push(vec![]);
+                prev_dash = false;
+                continue;
+            }
+            _ => {}
+        }
+        prev_dash = dash;
+        chunks.last_mut().unwrap().push(token);
+    }
+
+    let mut fields = vec![];
+    for chunk in chunks.into_iter().filter(|chunk| !chunk.is_empty()) {
+        let mut tokens = TokenStream::from_iter(chunk).into_iter().peekable();
+        let attrs = parse_attributes(&mut tokens);
+        skip_visibility(&mut tokens);
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(name)) => name,
+            other => return Err(Error::new(other.map_or(Span::call_site(), |t| t.span()), "应该是字段名")),
+        };
+        if !is_punct(tokens.peek(), ':') {
+            return Err(Error::new(name.span(), "字段名后面应该是 `:`"));
+        }
+        fields.push(Field { name, attrs });
+    }
+    Ok(fields)
+}
+
+/// 属性参数：`skip` 或者 `key = "value"`
+enum Arg {
+    Flag(Ident),
+    Value(Ident, Literal),
+}
+
+impl Arg {
+    fn key(&self) -> &Ident {
+        match self {
+            Arg::Flag(key) | Arg::Value(key, _) => key,
+        }
+    }
+}
+
+fn parse_args(group: &Group) -> Result<Vec<Arg>, Error> {
+    let mut tokens = group.stream().into_iter().peekable();
+    let mut args = vec![];
+    while let Some(token) = tokens.next() {
+        let TokenTree::Ident(key) = token else {
+            return Err(Error::new(token.span(), "应该是参数名"));
+        };
+        if is_punct(tokens.peek(), '=') {
+            let eq = tokens.next().unwrap();
+            match tokens.next() {
+                Some(TokenTree::Literal(value)) => args.push(Arg::Value(key, value)),
+                other => return Err(Error::new(other.map_or(eq.span(), |t| t.span()), format!("{} = 后面应该是字符串", key))),
+            }
+        } else {
+            args.push(Arg::Flag(key));
+        }
+        match tokens.next() {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+            Some(other) => return Err(Error::new(other.span(), "参数之间应该用 `,` 分隔")),
+        }
+    }
+    Ok(args)
+}
+
+/// 找到名为 name 的属性的所有参数，每个参数名只能出现一次
+fn args_of(attrs: &[Attribute], name: &str) -> Result<Vec<Arg>, Error> {
+    let mut all: Vec<Arg> = vec![];
+    for attr in attrs.iter().filter(|attr| attr.name.to_string() == name) {
+        let Some(group) = &attr.args else {
+            return Err(Error::new(attr.name.span(), format!("应该写成 #[{}(...)]", name)));
+        };
+        for arg in parse_args(group)? {
+            if all.iter().any(|other| other.key().to_string() == arg.key().to_string()) {
+                return Err(Error::new(arg.key().span(), format!("重复的参数 {}", arg.key())));
+            }
+            all.push(arg);
+        }
+    }
+    Ok(all)
+}
+
+/// 字符串字面量的值，处理转义和原始字符串
+fn string_value(literal: &Literal) -> Result<String, Error> {
+    let text = literal.to_string();
+    let error = || Error::new(literal.span(), "应该是字符串字面量");
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = &raw[hashes..raw.len() - hashes];
+        return body.strip_prefix('"').and_then(|b| b.strip_suffix('"')).map(str::to_string).ok_or_else(error);
+    }
+    let body = text.strip_prefix('"').and_then(|b| b.strip_suffix('"')).ok_or_else(error)?;
+    let mut value = String::new();
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('0') => value.push('\0'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                value.push(u8::from_str_radix(&hex, 16).map_err(|_| error())? as char);
+            }
+            Some('u') => {
+                let hex: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                let code = u32::from_str_radix(&hex, 16).map_err(|_| error())?;
+                value.push(char::from_u32(code).ok_or_else(error)?);
+            }
+            // 行尾的 \ 跳过换行和下一行开头的空白
+            Some('\n') => {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            }
+            Some(other) => value.push(other),
+            None => return Err(error()),
+        }
+    }
+    Ok(value)
+}
+
+/// 格式字符串中用到的字段名，按第一次出现的顺序
+fn placeholders(format: &str, literal: &Literal, fields: &[Field]) -> Result<Vec<String>, Error> {
+    let mut names: Vec<String> = vec![];
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.next_if_eq(&'{').is_some() => {}
+            '}' if chars.next_if_eq(&'}').is_some() => {}
+            '}' => return Err(Error::new(literal.span(), "格式字符串中有多余的 `}`，要输出 `}` 请写成 `}}`")),
+            '{' => {
+                let mut content = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => content.push(c),
+                        None => return Err(Error::new(literal.span(), "格式字符串中的 `{` 没有闭合，要输出 `{` 请写成 `{{`")),
+                    }
+                }
+                let name = content.split(':').next().unwrap().trim();
+                if name.is_empty() || name.chars().all(|c| c.is_ascii_digit()) {
+                    let message = format!("格式字符串中的 `{{{}}}` 要写成字段名，比如 `{{{}}}`", content, fields.first().map_or("name".to_string(), |f| f.name.to_string()));
+                    return Err(Error::new(literal.span(), message));
+                }
+                if !fields.iter().any(|field| field.name.to_string() == name) {
+                    return Err(Error::new(literal.span(), format!("格式字符串中的字段 `{}` 不存在", name)));
+                }
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(names)
+}
+
+fn expand_summary(input: &Input) -> Result<TokenStream, Error> {
+    let mut format = None;
+    for arg in args_of(&input.attrs, "summary")? {
+        match arg {
+            Arg::Value(key, value) if key.to_string() == "format" => format = Some(value),
+            other => return Err(Error::new(other.key().span(), format!("不认识的参数 {}，只支持 format = \"...\"", other.key()))),
+        }
+    }
+    let Some(format) = format else {
+        return Err(Error::new(input.name.span(), "缺少 #[summary(format = \"...\")]"));
+    };
+    let names = placeholders(&string_value(&format)?, &format, &input.fields)?;
+
+    // 原来的字面量直接放进 format!，格式说明写错时编译器的错误也指向属性中的字符串
+    let mut args = TokenStream::from(TokenTree::Literal(format));
+    let named: String = names.iter().map(|name| format!(", {} = self.{}", name, name)).collect();
+    args.extend(named.parse::<TokenStream>().unwrap());
+    let mut body: TokenStream = "::std::format!".parse().unwrap();
+    body.extend([TokenTree::Group(Group::new(Delimiter::Parenthesis, args))]);
+
+    let mut method: TokenStream = "fn summarize(&self) -> ::std::string::String".parse().unwrap();
+    method.extend([TokenTree::Group(Group::new(Delimiter::Brace, body))]);
+    let mut output: TokenStream = format!("impl Summary for {}", input.name).parse().unwrap();
+    output.extend([TokenTree::Group(Group::new(Delimiter::Brace, method))]);
+    Ok(output)
+}
+
+/// `sign_in_count` -> `Sign in count`
+fn label(name: &str) -> String {
+    let name = name.trim_start_matches("r#").replace('_', " ");
+    let mut chars = name.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn expand_field_display(input: &Input) -> Result<TokenStream, Error> {
+    let mut separator = "\n".to_string();
+    for arg in args_of(&input.attrs, "display")? {
+        match arg {
+            Arg::Value(key, value) if key.to_string() == "separator" => separator = string_value(&value)?,
+            other => return Err(Error::new(other.key().span(), format!("不认识的参数 {}，结构体上只支持 separator = \"...\"", other.key()))),
+        }
+    }
+
+    let mut writes = vec![];
+    for field in &input.fields {
+        let mut name = label(&field.name.to_string());
+        let mut skip = false;
+        for arg in args_of(&field.attrs, "display")? {
+            match arg {
+                Arg::Flag(key) if key.to_string() == "skip" => skip = true,
+                Arg::Value(key, value) if key.to_string() == "rename" => name = string_value(&value)?,
+                other => return Err(Error::new(other.key().span(), format!("不认识的参数 {}，字段上只支持 skip 和 rename = \"...\"", other.key()))),
+            }
+        }
+        if !skip {
+            writes.push(format!("::std::write!(f, \"{{}}: {{}}\", {}, self.{})?;", Literal::string(&name), field.name));
+        }
+    }
+    let separator = format!("f.write_str({})?;", Literal::string(&separator));
+    let body = writes.join(&separator);
+    // 没有字段要显示时不用 f，避免未使用变量的警告
+    let f = if writes.is_empty() { "_f" } else { "f" };
+    let output = format!(
+        "impl ::std::fmt::Display for {} {{
+            fn fmt(&self, {}: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {{
+                {}
+                ::std::result::Result::Ok(())
+            }}
+        }}",
+        input.name, f, body
+    );
+    Ok(output.parse().unwrap())
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use head_first_rust_derive::{FieldDisplay, Summary};
+
+trait Summary {
+    fn summarize(&self) -> String;
+}
+
+#[derive(Summary, FieldDisplay)]
+#[summary(format = "文章：{title}，作者：{author}")]
+struct Post {
+    title: String,
+    author: String,
+    content: String,
+}
+
+#[derive(Summary)]
+#[summary(format = "{username} 发表了微博=> {content}")]
+pub struct Weibo {
+    pub username: String,
+    pub(crate) content: String,
+}
+
+fn post() -> Post {
+    Post { title: "Head first Rust".to_string(), author: "Zhong".to_string(), content: "一本介绍Rust的书籍".to_string() }
+}
+
+#[test]
+fn summary_replaces_fields() {
+    assert_eq!(post().summarize(), "文章：Head first Rust，作者：Zhong");
+    let weibo = Weibo { username: "重".to_string(), content: "我发了一条微博".to_string() };
+    assert_eq!(weibo.summarize(), "重 发表了微博=> 我发了一条微博");
+}
+
+#[test]
+fn field_display_defaults_to_one_line_per_field() {
+    assert_eq!(post().to_string(), "Title: Head first Rust\nAuthor: Zhong\nContent: 一本介绍Rust的书籍");
+}
+
+/// 字段类型中有逗号、尖括号、函数指针的 `->`，字段上有文档注释
+#[derive(Summary, FieldDisplay)]
+#[summary(format = "{{{name:>6}}} {count:03} {name} {tags:?}")]
+#[display(separator = " | ")]
+struct Complex {
+    /// 名字
+    name: &'static str,
+    #[display(skip)]
+    #[allow(dead_code)]
+    map: HashMap<String, Vec<(u8, u16)>>,
+    #[display(skip)]
+    #[allow(dead_code)]
+    callback: fn(i32) -> Option<i32>,
+    #[display(rename = "数量")]
+    count: u32,
+    #[display(skip)]
+    tags: Vec<&'static str>,
+}
+
+fn complex() -> Complex {
+    Complex { name: "rust", map: HashMap::new(), callback: |x| Some(x), count: 7, tags: vec!["a", "b"] }
+}
+
+#[test]
+fn summary_keeps_format_specs_and_escapes() {
+    assert_eq!(complex().summarize(), "{  rust} 007 rust [\"a\", \"b\"]");
+}
+
+#[test]
+fn field_display_options() {
+    assert_eq!(complex().to_string(), "Name: rust | 数量: 7");
+}
+
+#[derive(Summary)]
+#[summary(format = r#"raw "{id}" \n"#)]
+struct Raw {
+    id: u8,
+}
+
+#[derive(Summary)]
+#[summary(format = "\u{4e2d}\t{id}\x21")]
+struct Escaped {
+    id: u8,
+}
+
+#[test]
+fn summary_string_literal_forms() {
+    assert_eq!(Raw { id: 1 }.summarize(), "raw \"1\" \\n");
+    assert_eq!(Escaped { id: 2 }.summarize(), "中\t2!");
+}
+
+#[derive(Summary, FieldDisplay)]
+#[summary(format = "单元结构体")]
+struct Unit;
+
+#[derive(FieldDisplay)]
+struct Empty {}
+
+#[test]
+fn structs_without_fields() {
+    assert_eq!(Unit.summarize(), "单元结构体");
+    assert_eq!(Unit.to_string(), "");
+    assert_eq!(Empty {}.to_string(), "");
+}
+
+#[derive(FieldDisplay)]
+struct Keywords {
+    r#type: &'static str,
+    sign_in_count: u64,
+}
+
+#[test]
+fn field_display_labels() {
+    let value = Keywords { r#type: "admin", sign_in_count: 3 };
+    assert_eq!(value.to_string(), "Type: admin\nSign in count: 3");
+}
@@ -0,0 +1,16 @@
+use head_first_rust_derive::{FieldDisplay, Summary};
+
+trait Summary {
+    fn summarize(&self) -> String;
+}
+
+#[derive(Summary, FieldDisplay)]
+#[summary(format = "文章：{title}，作者：{author:?}")]
+#[display(separator = " | ")]
+struct Post {
+    title: String,
+    #[display(rename = "作者")]
+    author: String,
+    #[display(skip)]
+    content: String,
+}
@@ -0,0 +1,25 @@
+// 与 derived.rs 中派生宏应该生成的代码逐个 token 相同
+trait Summary {
+    fn summarize(&self) -> String;
+}
+
+struct Post {
+    title: String,
+    author: String,
+    content: String,
+}
+
+impl Summary for Post {
+    fn summarize(&self) -> ::std::string::String {
+        ::std::format!("文章：{title}，作者：{author:?}", title = self.title, author = self.author)
+    }
+}
+
+impl ::std::fmt::Display for Post {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::std::write!(f, "{}: {}", "Title", self.title)?;
+        f.write_str(" | ")?;
+        ::std::write!(f, "{}: {}", "作者", self.author)?;
+        ::std::result::Result::Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+//! 用 rustc 编译 `tests/ui`、`tests/expand` 下的文件：
+//! * `ui`：每个文件都应该编译失败，检查派生宏给出的错误信息和它指向的行
+//! * `expand`：比较派生宏展开后的代码和手写的代码
+//!
+//! `compile_fail` 文档测试只要有编译错误就算通过，检查不到错误的内容和位置。
+//! 列号、rustc 自己的错误信息会随编译器版本变化，所以只比较行号和派生宏的信息
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn rustc() -> Command {
+    Command::new(std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+}
+
+/// 编译产物放在 Cargo 给集成测试准备的临时目录中，随 `target` 一起清理
+fn out_dir() -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join("ui")
+}
+
+/// 每个测试进程单独编译一次派生宏，不依赖 target 目录中哪个版本的产物
+fn derive_lib() -> &'static Path {
+    static LIB: OnceLock<PathBuf> = OnceLock::new();
+    LIB.get_or_init(|| {
+        let dir = out_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let output = rustc()
+            .args(["--edition", "2021", "--crate-type", "proc-macro", "--crate-name", "head_first_rust_derive"])
+            .args(["--extern", "proc_macro", "--out-dir"])
+            .arg(&dir)
+            .arg(manifest_dir().join("src/lib.rs"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "编译派生宏失败：\n{}", String::from_utf8_lossy(&output.stderr));
+        let name = format!("{}head_first_rust_derive{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX);
+        dir.join(name)
+    })
+}
+
+/// 编译 `tests/<path>`，返回 (是否成功, stdout, stderr)
+fn compile(path: &str, args: &[&str], envs: &[(&str, &str)]) -> (bool, String, String) {
+    let mut extern_arg = std::ffi::OsString::from("head_first_rust_derive=");
+    extern_arg.push(derive_lib());
+    let output = rustc()
+        .args(["--edition", "2021", "--crate-type", "lib", "--error-format", "short"])
+        .arg("--extern")
+        .arg(extern_arg)
+        .args(args)
+        .envs(envs.iter().copied())
+        .arg(manifest_dir().join("tests").join(path))
+        .output()
+        .unwrap();
+    let text = |bytes: Vec<u8>| String::from_utf8(bytes).unwrap();
+    (output.status.success(), text(output.stdout), text(output.stderr))
+}
+
+/// short 格式的错误是 `路径:行:列: error: 信息`，返回 (行, 信息)
+fn errors(path: &str) -> Vec<(usize, String)> {
+    let out_dir = out_dir();
+    let (success, _, stderr) = compile(path, &["--emit", "metadata", "--out-dir", out_dir.to_str().unwrap()], &[]);
+    assert!(!success, "{} 应该编译失败", path);
+    let prefix = format!("{}:", manifest_dir().join("tests").join(path).display());
+    stderr
+        .lines()
+        .filter_map(|line| line.strip_prefix(&prefix))
+        .filter_map(|rest| {
+            let mut parts = rest.splitn(3, ':');
+            let line = parts.next()?.parse().ok()?;
+            parts.next()?;
+            let message = parts.next()?.trim().strip_prefix("error: ")?;
+            Some((line, message.to_string()))
+        })
+        .collect()
+}
+
+#[test]
+fn errors_point_at_the_offending_tokens() {
+    let cases = [
+        // 格式字符串的问题指向属性中的字符串
+        ("summary_unknown_field.rs", 8, "格式字符串中的字段 `titel` 不存在"),
+        ("summary_positional.rs", 8, "格式字符串中的 `{}` 要写成字段名，比如 `{title}`"),
+        // 缺少属性时指向结构体名
+        ("summary_missing_format.rs", 8, "缺少 #[summary(format = \"...\")]"),
+        ("summary_unknown_arg.rs", 8, "不认识的参数 prefix，只支持 format = \"...\""),
+        ("summary_enum.rs", 9, "只支持有命名字段的结构体，不支持 enum"),
+        ("display_unknown_option.rs", 6, "不认识的参数 hidden，字段上只支持 skip 和 rename = \"...\""),
+        ("display_duplicate_arg.rs", 5, "重复的参数 skip"),
+        ("display_struct_option.rs", 4, "不认识的参数 rename，结构体上只支持 separator = \"...\""),
+        ("display_tuple_struct.rs", 4, "不支持元组结构体，字段需要有名字"),
+        ("display_generic.rs", 4, "不支持泛型参数"),
+    ];
+    for (file, line, message) in cases {
+        let found = errors(&format!("ui/{}", file));
+        assert_eq!(found, vec![(line, message.to_string())], "{}", file);
+    }
+    // 格式说明的错误由 format! 报告，信息是 rustc 的，只检查它指向属性所在的行
+    let found = errors("ui/summary_bad_spec.rs");
+    assert_eq!(found.iter().map(|(line, _)| *line).collect::<Vec<_>>(), vec![9]);
+}
+
+/// 取出 `impl ... for Post { ... }` 这一段，按花括号配对
+fn impl_block<'a>(source: &'a str, header: &str) -> &'a str {
+    let start = source.find(header).unwrap_or_else(|| panic!("没有找到 {}：\n{}", header, source));
+    let mut depth = 0;
+    for (i, c) in source[start..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &source[start..start + i + 1];
+                }
+            }
+            _ => {}
+        }
+    }
+    panic!("{} 的花括号没有配对", header)
+}
+
+/// `-Zunpretty=expanded` 只有 nightly 可以用，RUSTC_BOOTSTRAP 让 stable 也能打印展开结果；
+/// 这是编译器的内部功能，输出格式随版本变化，所以用到它的测试默认不运行
+fn expanded(path: &str) -> String {
+    let (success, stdout, stderr) = compile(path, &["-Zunpretty=expanded"], &[("RUSTC_BOOTSTRAP", "1")]);
+    assert!(success, "展开 {} 失败：\n{}", path, stderr);
+    stdout
+}
+
+#[test]
+#[ignore = "依赖不稳定的 -Zunpretty=expanded，用 cargo test -- --ignored 运行"]
+fn expansion_matches_hand_written_impls() {
+    let derived = expanded("expand/derived.rs");
+    let hand_written = expanded("expand/hand_written.rs");
+    // 空白只和 pretty printer 的排版有关，比较前合并成一个空格
+    let normalize = |block: &str| block.split_whitespace().collect::<Vec<_>>().join(" ");
+    for header in ["impl Summary for Post", "impl ::std::fmt::Display for Post"] {
+        assert_eq!(normalize(impl_block(&derived, header)), normalize(impl_block(&hand_written, header)));
+    }
+    // 跳过的字段不会出现在 Display 中
+    assert!(!impl_block(&derived, "impl ::std::fmt::Display for Post").contains("content"));
+}
@@ -0,0 +1,7 @@
+use head_first_rust_derive::FieldDisplay;
+
+#[derive(FieldDisplay)]
+struct User {
+    #[display(skip, rename = "密码", skip)]
+    password: String,
+}
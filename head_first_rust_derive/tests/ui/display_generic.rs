@@ -0,0 +1,6 @@
+use head_first_rust_derive::FieldDisplay;
+
+#[derive(FieldDisplay)]
+struct Wrapper<T> {
+    value: T,
+}
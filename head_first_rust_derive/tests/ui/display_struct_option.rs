@@ -0,0 +1,7 @@
+use head_first_rust_derive::FieldDisplay;
+
+#[derive(FieldDisplay)]
+#[display(separator = ", ", rename)]
+struct User {
+    name: String,
+}
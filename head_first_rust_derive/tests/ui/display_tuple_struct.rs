@@ -0,0 +1,4 @@
+use head_first_rust_derive::FieldDisplay;
+
+#[derive(FieldDisplay)]
+struct Color(i32, i32, i32);
@@ -0,0 +1,8 @@
+use head_first_rust_derive::FieldDisplay;
+
+#[derive(FieldDisplay)]
+struct User {
+    name: String,
+    #[display(hidden)]
+    password: String,
+}
@@ -0,0 +1,12 @@
+use head_first_rust_derive::Summary;
+
+trait Summary {
+    fn summarize(&self) -> String;
+}
+
+// 格式说明由 format! 检查，错误同样指向属性中的字符串
+#[derive(Summary)]
+#[summary(format = "{title:y}")]
+struct Post {
+    title: String,
+}
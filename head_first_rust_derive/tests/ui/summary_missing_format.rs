@@ -0,0 +1,10 @@
+use head_first_rust_derive::Summary;
+
+trait Summary {
+    fn summarize(&self) -> String;
+}
+
+#[derive(Summary)]
+struct Post {
+    title: String,
+}
@@ -0,0 +1,11 @@
+use head_first_rust_derive::Summary;
+
+trait Summary {
+    fn summarize(&self) -> String;
+}
+
+#[derive(Summary)]
+#[summary(format = "{} {0}")]
+struct Post {
+    title: String,
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::fft;
+
+fn main() {
+    fft::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::float_bits;
+
+fn main() {
+    float_bits::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::char_index;
+
+fn main() {
+    char_index::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::ident_case;
+
+fn main() {
+    ident_case::main();
+}
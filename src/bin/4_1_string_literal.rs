@@ -0,0 +1,5 @@
+use head_first_rust::ch2::string_literal;
+
+fn main() {
+    string_literal::main();
+}
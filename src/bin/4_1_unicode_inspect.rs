@@ -0,0 +1,5 @@
+use head_first_rust::ch2::unicode_inspect;
+
+fn main() {
+    unicode_inspect::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::utf8_codec;
+
+fn main() {
+    utf8_codec::main();
+}
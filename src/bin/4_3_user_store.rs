@@ -0,0 +1,5 @@
+use head_first_rust::ch2::user_store;
+
+fn main() {
+    user_store::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::message_script;
+
+fn main() {
+    message_script::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::poker;
+
+fn main() {
+    poker::main();
+}
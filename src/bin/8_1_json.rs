@@ -0,0 +1,5 @@
+use head_first_rust::ch2::json;
+
+fn main() {
+    json::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::mod_int;
+
+fn main() {
+    mod_int::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::json_convert;
+
+fn main() {
+    json_convert::main();
+}
@@ -0,0 +1,5 @@
+use head_first_rust::ch2::text_buffer;
+
+fn main() {
+    text_buffer::main();
+}
@@ -0,0 +1,506 @@
+use std::fmt::{Binary, Display, Formatter};
+use std::ops::{BitAnd, BitOr, Sub};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("定长位集合", Box::new(|| fixed_bitset())),
+        ("集合运算", Box::new(|| set_operations())),
+        ("可增长位集合", Box::new(|| growable_bitset())),
+        ("位域打包", Box::new(|| bit_field())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// 位置 -> (第几个字, 字内掩码)
+fn locate(index: usize) -> (usize, u64) {
+    (index / WORD_BITS, 1u64 << (index % WORD_BITS))
+}
+
+/// # 遍历所有为 1 的位
+/// 每次用 `trailing_zeros` 找到最低位的 1，再用 `w & (w - 1)` 清掉它
+pub struct Ones<'a> {
+    words: &'a [u64],
+    index: usize,
+    current: u64,
+}
+
+impl<'a> Ones<'a> {
+    fn new(words: &'a [u64]) -> Self {
+        let current = words.first().copied().unwrap_or(0);
+        Self { words, index: 0, current }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = *self.words.get(self.index)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.index * WORD_BITS + bit)
+    }
+}
+
+/// # 定长位集合
+/// * const 泛型 N 是 u64 字的个数，容量为 `N * 64` 位
+/// * 数据放在栈上的数组里，不需要堆分配
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitSet<const N: usize> {
+    words: [u64; N],
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> BitSet<N> {
+    pub const CAPACITY: usize = N * WORD_BITS;
+
+    pub fn new() -> Self {
+        Self { words: [0; N] }
+    }
+
+    /// 越界时 panic，与数组索引一致
+    fn word_mut(&mut self, index: usize) -> (&mut u64, u64) {
+        assert!(index < Self::CAPACITY, "bit index {} out of range for BitSet of {} bits", index, Self::CAPACITY);
+        let (word, mask) = locate(index);
+        (&mut self.words[word], mask)
+    }
+
+    pub fn set(&mut self, index: usize) {
+        let (word, mask) = self.word_mut(index);
+        *word |= mask;
+    }
+
+    pub fn clear(&mut self, index: usize) {
+        let (word, mask) = self.word_mut(index);
+        *word &= !mask;
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        let (word, mask) = self.word_mut(index);
+        *word ^= mask;
+    }
+
+    pub fn test(&self, index: usize) -> bool {
+        let (word, mask) = locate(index);
+        self.words.get(word).is_some_and(|w| w & mask != 0)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    fn zip_with(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        let mut words = [0; N];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = op(self.words[i], other.words[i]);
+        }
+        Self { words }
+    }
+
+    pub fn iter(&self) -> Ones<'_> {
+        Ones::new(&self.words)
+    }
+
+    pub fn count_ones(&self) -> u32 {
+        self.words.iter().map(|w| w.count_ones()).sum()
+    }
+
+    /// 从最高位（CAPACITY - 1）往下数连续的 0
+    pub fn leading_zeros(&self) -> u32 {
+        leading_zeros(&self.words)
+    }
+
+    /// 从最低位（0）往上数连续的 0
+    pub fn trailing_zeros(&self) -> u32 {
+        trailing_zeros(&self.words)
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for index in iter {
+            set.set(index);
+        }
+        set
+    }
+}
+
+impl<const N: usize> BitOr for BitSet<N> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl<const N: usize> BitAnd for BitSet<N> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl<const N: usize> Sub for BitSet<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+impl<const N: usize> Display for BitSet<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_set(f, self.iter())
+    }
+}
+
+/// 按 `{:b}` 打印时从高位到低位输出所有位
+impl<const N: usize> Binary for BitSet<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for word in self.words.iter().rev() {
+            write!(f, "{:064b}", word)?;
+        }
+        Ok(())
+    }
+}
+
+/// # 可增长位集合
+/// * 数据放在 Vec 中，`set` 超出长度时自动扩容
+/// * `len` 是逻辑上的位数，`leading_zeros` 以它为上界
+/// * 相等只看有哪些位为 1，与 `len` 无关
+#[derive(Debug, Clone, Default)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_len(len: usize) -> Self {
+        Self { words: vec![0; len.div_ceil(WORD_BITS)], len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn grow(&mut self, index: usize) {
+        if index >= self.len {
+            self.len = index + 1;
+            self.words.resize(self.len.div_ceil(WORD_BITS), 0);
+        }
+    }
+
+    pub fn set(&mut self, index: usize) {
+        self.grow(index);
+        let (word, mask) = locate(index);
+        self.words[word] |= mask;
+    }
+
+    /// 超出长度的位本来就是 0，不需要扩容
+    pub fn clear(&mut self, index: usize) {
+        let (word, mask) = locate(index);
+        if let Some(w) = self.words.get_mut(word) {
+            *w &= !mask;
+        }
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        self.grow(index);
+        let (word, mask) = locate(index);
+        self.words[word] ^= mask;
+    }
+
+    pub fn test(&self, index: usize) -> bool {
+        let (word, mask) = locate(index);
+        self.words.get(word).is_some_and(|w| w & mask != 0)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    /// 较短的一方按 0 补齐
+    fn zip_with(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        let count = self.words.len().max(other.words.len());
+        let words = (0..count)
+            .map(|i| op(self.words.get(i).copied().unwrap_or(0), other.words.get(i).copied().unwrap_or(0)))
+            .collect();
+        Self { words, len: self.len.max(other.len) }
+    }
+
+    pub fn iter(&self) -> Ones<'_> {
+        Ones::new(&self.words)
+    }
+
+    pub fn count_ones(&self) -> u32 {
+        self.words.iter().map(|w| w.count_ones()).sum()
+    }
+
+    pub fn leading_zeros(&self) -> u32 {
+        let padding = (self.words.len() * WORD_BITS - self.len) as u32;
+        leading_zeros(&self.words) - padding
+    }
+
+    /// 全 0 时返回 `len`
+    pub fn trailing_zeros(&self) -> u32 {
+        trailing_zeros(&self.words).min(self.len as u32)
+    }
+}
+
+/// 较短的一方按 0 补齐后逐字比较
+impl PartialEq for BitVec {
+    fn eq(&self, other: &Self) -> bool {
+        let count = self.words.len().max(other.words.len());
+        (0..count).all(|i| self.words.get(i).copied().unwrap_or(0) == other.words.get(i).copied().unwrap_or(0))
+    }
+}
+
+impl Eq for BitVec {}
+
+impl FromIterator<usize> for BitVec {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for index in iter {
+            set.set(index);
+        }
+        set
+    }
+}
+
+impl Display for BitVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_set(f, self.iter())
+    }
+}
+
+fn leading_zeros(words: &[u64]) -> u32 {
+    let mut count = 0;
+    for word in words.iter().rev() {
+        count += word.leading_zeros();
+        if *word != 0 {
+            break;
+        }
+    }
+    count
+}
+
+fn trailing_zeros(words: &[u64]) -> u32 {
+    let mut count = 0;
+    for word in words.iter() {
+        count += word.trailing_zeros();
+        if *word != 0 {
+            break;
+        }
+    }
+    count
+}
+
+fn write_set(f: &mut Formatter<'_>, ones: Ones<'_>) -> std::fmt::Result {
+    let items: Vec<String> = ones.map(|i| i.to_string()).collect();
+    write!(f, "{{{}}}", items.join(", "))
+}
+
+/// # 位域
+/// 描述一个整数中从 `offset` 开始、宽 `width` 位的字段，
+/// 多个位域可以把几个小字段打包进一个 u32/u64
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitField {
+    offset: u32,
+    width: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BitFieldError {
+    /// 值超出了字段能表示的范围
+    Overflow { value: u64, width: u32 },
+}
+
+impl Display for BitFieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BitFieldError::Overflow { value, width } => write!(f, "{} 无法放进 {} 位的字段", value, width),
+        }
+    }
+}
+
+impl BitField {
+    /// 字段必须完整地落在 u64 内
+    pub const fn new(offset: u32, width: u32) -> Self {
+        assert!(width > 0 && offset + width <= u64::BITS, "bit field out of range");
+        Self { offset, width }
+    }
+
+    /// 紧跟在当前字段后面的下一个字段
+    pub const fn next(&self, width: u32) -> Self {
+        Self::new(self.offset + self.width, width)
+    }
+
+    pub fn max_value(&self) -> u64 {
+        u64::MAX >> (u64::BITS - self.width)
+    }
+
+    fn mask(&self) -> u64 {
+        self.max_value() << self.offset
+    }
+
+    pub fn get(&self, packed: u64) -> u64 {
+        (packed & self.mask()) >> self.offset
+    }
+
+    /// 返回写入字段后的新值，其他位保持不变
+    pub fn set(&self, packed: u64, value: u64) -> Result<u64, BitFieldError> {
+        if value > self.max_value() {
+            return Err(BitFieldError::Overflow { value, width: self.width });
+        }
+        Ok(packed & !self.mask() | value << self.offset)
+    }
+}
+
+/// RGB 颜色打包进 u32：`0x00RRGGBB`
+const BLUE: BitField = BitField::new(0, 8);
+const GREEN: BitField = BLUE.next(8);
+const RED: BitField = GREEN.next(8);
+
+fn pack_rgb(r: u8, g: u8, b: u8) -> u32 {
+    let packed = [(RED, r), (GREEN, g), (BLUE, b)]
+        .iter()
+        .try_fold(0, |acc, (field, v)| field.set(acc, *v as u64))
+        .unwrap(); // u8 一定放得进 8 位
+    packed as u32
+}
+
+fn unpack_rgb(packed: u32) -> (u8, u8, u8) {
+    let packed = packed as u64;
+    (RED.get(packed) as u8, GREEN.get(packed) as u8, BLUE.get(packed) as u8)
+}
+
+/// 扑克牌：低 4 位是点数（1~13），接着 2 位是花色
+const CARD_VALUE: BitField = BitField::new(0, 4);
+const CARD_SUIT: BitField = CARD_VALUE.next(2);
+
+fn fixed_bitset() {
+    let mut set: BitSet<2> = BitSet::new();
+    println!("容量：{} 位", BitSet::<2>::CAPACITY);
+    set.set(1);
+    set.set(3);
+    set.set(64);
+    set.set(127);
+    set.toggle(3);
+    set.toggle(5);
+    set.clear(1);
+    println!("{}", set);
+    println!("{:b}", set);
+
+    assert!(set.test(5) && set.test(64) && !set.test(3) && !set.test(1));
+    assert_eq!(set.count_ones(), 3);
+    assert_eq!(set.leading_zeros(), 0);
+    assert_eq!(set.trailing_zeros(), 5);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![5, 64, 127]);
+
+    let empty: BitSet<1> = BitSet::new();
+    assert_eq!((empty.leading_zeros(), empty.trailing_zeros()), (64, 64));
+}
+
+fn set_operations() {
+    let evens: BitSet<1> = (0..20).step_by(2).collect();
+    let threes: BitSet<1> = (0..20).step_by(3).collect();
+    println!("偶数：{}", evens);
+    println!("3 的倍数：{}", threes);
+    println!("并集：{}", evens | threes);
+    println!("交集：{}", evens & threes);
+    println!("差集：{}", evens - threes);
+
+    assert_eq!((evens & threes).iter().collect::<Vec<_>>(), vec![0, 6, 12, 18]);
+    assert_eq!((evens - threes).count_ones(), 6);
+    assert_eq!(evens.union(&threes), evens | threes);
+}
+
+fn growable_bitset() {
+    let mut bits = BitVec::new();
+    bits.set(3);
+    bits.set(200);
+    println!("len = {}，{}", bits.len(), bits);
+    assert_eq!(bits.len(), 201);
+    assert_eq!(bits.leading_zeros(), 0);
+    assert_eq!(bits.trailing_zeros(), 3);
+
+    bits.clear(200);
+    bits.clear(1000); // 超出范围，什么都不做
+    assert_eq!(bits.leading_zeros(), 197);
+
+    let primes: BitVec = [2, 3, 5, 7, 11, 13].into_iter().collect();
+    let odds: BitVec = (1..14).step_by(2).collect();
+    println!("质数 ∩ 奇数：{}", primes.intersection(&odds));
+    println!("质数 - 奇数：{}", primes.difference(&odds));
+    assert_eq!(primes.difference(&odds).iter().collect::<Vec<_>>(), vec![2]);
+    assert_eq!(primes.union(&odds).count_ones(), 8);
+
+    let sized = BitVec::with_len(10);
+    assert_eq!((sized.leading_zeros(), sized.trailing_zeros()), (10, 10));
+
+    // 成员相同就相等，不管长度
+    let mut wide = BitVec::with_len(500);
+    wide.set(2);
+    assert_eq!(wide, [2].into_iter().collect::<BitVec>());
+    assert_eq!(sized, BitVec::new());
+    assert_ne!(wide, sized);
+}
+
+fn bit_field() {
+    let orange = pack_rgb(255, 165, 0);
+    println!("橙色：{:#08x}", orange);
+    assert_eq!(orange, 0xffa500);
+    assert_eq!(unpack_rgb(orange), (255, 165, 0));
+
+    // 黑桃（花色 1）Q（12）
+    let card = CARD_VALUE.set(0, 12).and_then(|c| CARD_SUIT.set(c, 1)).unwrap();
+    println!("扑克牌：{:#08b} => 花色 {}，点数 {}", card, CARD_SUIT.get(card), CARD_VALUE.get(card));
+    assert_eq!(card, 0b01_1100);
+
+    match CARD_VALUE.set(card, 16) {
+        Ok(_) => unreachable!(),
+        Err(e) => println!("错误：{}", e),
+    }
+}
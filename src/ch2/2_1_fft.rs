@@ -0,0 +1,255 @@
+use std::f64::consts::PI;
+use num::complex::Complex;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("与朴素 DFT 对比", Box::new(|| compare_with_dft())),
+        ("逆变换", Box::new(|| inverse())),
+        ("实数信号频谱", Box::new(|| spectrum())),
+        ("整数卷积", Box::new(|| convolution())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+type C64 = Complex<f64>;
+
+/// `e^(iθ)`
+fn unit(theta: f64) -> C64 {
+    C64::new(theta.cos(), theta.sin())
+}
+
+/// # 朴素 DFT
+/// `X[k] = Σ x[j] · e^(-2πi·jk/n)`，复杂度 O(n²)，用来验证 FFT
+pub fn dft(input: &[C64]) -> Vec<C64> {
+    let n = input.len();
+    (0..n)
+        .map(|k| {
+            input
+                .iter()
+                .enumerate()
+                .map(|(j, x)| x * unit(-2.0 * PI * ((j * k) % n) as f64 / n as f64))
+                .sum()
+        })
+        .collect()
+}
+
+/// # 快速傅里叶变换
+/// * 长度是 2 的幂：迭代版 radix-2 Cooley–Tukey
+/// * 其他长度：Bluestein 算法，转换成 2 的幂长度的卷积
+pub fn fft(input: &[C64]) -> Vec<C64> {
+    transform(input, false)
+}
+
+/// 逆变换，结果已经除以 n
+pub fn ifft(input: &[C64]) -> Vec<C64> {
+    let n = input.len() as f64;
+    transform(input, true).into_iter().map(|x| x / n).collect()
+}
+
+fn transform(input: &[C64], inverse: bool) -> Vec<C64> {
+    let mut data = input.to_vec();
+    if data.len() <= 1 {
+        return data;
+    }
+    if data.len().is_power_of_two() {
+        radix2(&mut data, inverse);
+        data
+    } else {
+        bluestein(&data, inverse)
+    }
+}
+
+/// 原地 radix-2 FFT，不做 1/n 缩放
+fn radix2(data: &mut [C64], inverse: bool) {
+    let n = data.len();
+    debug_assert!(n.is_power_of_two());
+
+    // 位逆序重排
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let w_len = unit(sign * 2.0 * PI / len as f64);
+        for chunk in data.chunks_mut(len) {
+            let (even, odd) = chunk.split_at_mut(len / 2);
+            let mut w = C64::new(1.0, 0.0);
+            for (e, o) in even.iter_mut().zip(odd.iter_mut()) {
+                let t = w * *o;
+                *o = *e - t;
+                *e += t;
+                w *= w_len;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// # Bluestein 算法
+/// 利用 `jk = (j² + k² - (k-j)²) / 2`，把任意长度的 DFT 写成卷积，
+/// 卷积再用补零到 2 的幂长度的 FFT 计算
+fn bluestein(input: &[C64], inverse: bool) -> Vec<C64> {
+    let n = input.len();
+    let m = (2 * n - 1).next_power_of_two();
+    let sign = if inverse { 1.0 } else { -1.0 };
+
+    // chirp[k] = e^(±πi·k²/n)，k² 对 2n 取模避免大数损失精度
+    let chirp: Vec<C64> = (0..n)
+        .map(|k| unit(sign * PI * ((k * k) % (2 * n)) as f64 / n as f64))
+        .collect();
+
+    let mut a = vec![C64::new(0.0, 0.0); m];
+    for k in 0..n {
+        a[k] = input[k] * chirp[k];
+    }
+    let mut b = vec![C64::new(0.0, 0.0); m];
+    b[0] = chirp[0].conj();
+    for k in 1..n {
+        b[k] = chirp[k].conj();
+        b[m - k] = chirp[k].conj();
+    }
+
+    radix2(&mut a, false);
+    radix2(&mut b, false);
+    let mut conv: Vec<C64> = a.iter().zip(&b).map(|(x, y)| x * y).collect();
+    radix2(&mut conv, true);
+
+    (0..n).map(|k| conv[k] / m as f64 * chirp[k]).collect()
+}
+
+/// 实数输入的 FFT，只返回前 `n / 2 + 1` 个频率分量（其余是共轭对称的）
+pub fn rfft(input: &[f64]) -> Vec<C64> {
+    let complex: Vec<C64> = input.iter().map(|&x| C64::new(x, 0.0)).collect();
+    let mut out = fft(&complex);
+    out.truncate(input.len() / 2 + 1);
+    out
+}
+
+/// 幅度谱：每个频率分量的模，按 n 归一化
+pub fn magnitude_spectrum(input: &[f64]) -> Vec<f64> {
+    let n = input.len() as f64;
+    rfft(input).iter().map(|x| x.norm() / n).collect()
+}
+
+/// # 整数序列卷积
+/// 相当于多项式乘法，FFT 把 O(n·m) 降到 O((n+m)·log(n+m))
+pub fn convolve(a: &[i64], b: &[i64]) -> Vec<i64> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+    let pad = |v: &[i64]| {
+        let mut out: Vec<C64> = v.iter().map(|&x| C64::new(x as f64, 0.0)).collect();
+        out.resize(size, C64::new(0.0, 0.0));
+        out
+    };
+    let (fa, fb) = (fft(&pad(a)), fft(&pad(b)));
+    let product: Vec<C64> = fa.iter().zip(&fb).map(|(x, y)| x * y).collect();
+    ifft(&product).iter().take(len).map(|x| x.re.round() as i64).collect()
+}
+
+fn max_error(a: &[C64], b: &[C64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).norm()).fold(0.0, f64::max)
+}
+
+/// 固定公式生成的测试信号，结果可复现
+fn sample_signal(n: usize) -> Vec<C64> {
+    (0..n)
+        .map(|i| {
+            let t = i as f64;
+            C64::new((t * 0.7).sin() + (t * t * 0.01).cos(), (t * 1.3).cos() - 0.5)
+        })
+        .collect()
+}
+
+fn compare_with_dft() {
+    for n in [1, 2, 8, 64, 256, 3, 5, 12, 100, 257] {
+        let x = sample_signal(n);
+        let error = max_error(&fft(&x), &dft(&x));
+        let algorithm = if n.is_power_of_two() { "radix-2" } else { "Bluestein" };
+        println!("n = {:>3}（{:<9}）最大误差：{:.2e}", n, algorithm, error);
+        assert!(error < 1e-9 * n as f64);
+    }
+}
+
+fn inverse() {
+    for n in [16, 30] {
+        let x = sample_signal(n);
+        let back = ifft(&fft(&x));
+        let error = max_error(&x, &back);
+        println!("n = {}，ifft(fft(x)) 与 x 的最大误差：{:.2e}", n, error);
+        assert!(error < 1e-12);
+    }
+}
+
+fn spectrum() {
+    // 50Hz 与 120Hz 叠加的信号，采样率 1000Hz，采样 1000 个点（非 2 的幂）
+    let rate = 1000.0;
+    let signal: Vec<f64> = (0..1000)
+        .map(|i| {
+            let t = i as f64 / rate;
+            (2.0 * PI * 50.0 * t).sin() + 0.5 * (2.0 * PI * 120.0 * t).sin()
+        })
+        .collect();
+    let spectrum = magnitude_spectrum(&signal);
+    let mut peaks: Vec<(usize, f64)> = spectrum.iter().copied().enumerate().filter(|(_, m)| *m > 0.1).collect();
+    peaks.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (freq, magnitude) in &peaks {
+        println!("{:>4} Hz：{:.3}", freq, magnitude);
+    }
+    // 正弦波的能量平分到正负频率上，幅度各占一半
+    assert_eq!(peaks.len(), 2);
+    assert_eq!(peaks[0].0, 50);
+    assert!((peaks[0].1 - 0.5).abs() < 1e-9);
+    assert_eq!(peaks[1].0, 120);
+    assert!((peaks[1].1 - 0.25).abs() < 1e-9);
+}
+
+fn convolution() {
+    // (1 + 2x + 3x²)(4 + 5x) = 4 + 13x + 22x² + 15x³
+    let product = convolve(&[1, 2, 3], &[4, 5]);
+    println!("{:?}", product);
+    assert_eq!(product, vec![4, 13, 22, 15]);
+
+    // 与 O(n·m) 的直接计算对比
+    let a: Vec<i64> = (0..300).map(|i| (i * 37 % 101) - 50).collect();
+    let b: Vec<i64> = (0..200).map(|i| (i * 53 % 97) - 48).collect();
+    let mut naive = vec![0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            naive[i + j] += x * y;
+        }
+    }
+    assert_eq!(convolve(&a, &b), naive);
+    println!("300 × 200 项卷积与直接计算一致");
+
+    // 大整数乘法：把数字看成十进制多项式
+    let digits = |s: &str| s.bytes().rev().map(|b| (b - b'0') as i64).collect::<Vec<_>>();
+    let mut coefficients = convolve(&digits("12345678901234567890"), &digits("98765432109876543210"));
+    let mut carry = 0;
+    for c in coefficients.iter_mut() {
+        *c += carry;
+        carry = *c / 10;
+        *c %= 10;
+    }
+    while carry > 0 {
+        coefficients.push(carry % 10);
+        carry /= 10;
+    }
+    let product: String = coefficients.iter().rev().map(|d| d.to_string()).collect();
+    println!("12345678901234567890 × 98765432109876543210 = {}", product);
+    assert_eq!(product, "1219326311370217952237463801111263526900");
+}
@@ -0,0 +1,393 @@
+use std::fmt::{Display, Formatter};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("f64 位分解", Box::new(|| decompose_f64())),
+        ("f32 位分解", Box::new(|| decompose_f32())),
+        ("特殊值分类", Box::new(|| special_values())),
+        ("相邻可表示值", Box::new(|| neighbours())),
+        ("按位对比", Box::new(|| bit_diff())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # IEEE 754 浮点数
+/// * 符号位（sign）：1 位，0 为正，1 为负
+/// * 指数位（exponent）：f32 8 位，f64 11 位，存储时加上偏移量（bias）
+/// * 尾数位（mantissa）：f32 23 位，f64 52 位，规格化数隐含最高位 1
+pub trait IeeeFloat: Copy {
+    const NAME: &'static str;
+    const EXP_BITS: u32;
+    const MANT_BITS: u32;
+
+    /// 原始位模式，统一放到 u64 中
+    fn to_raw(self) -> u64;
+    fn from_raw(raw: u64) -> Self;
+
+    const TOTAL_BITS: u32 = 1 + Self::EXP_BITS + Self::MANT_BITS;
+    const BIAS: i32 = (1 << (Self::EXP_BITS - 1)) - 1;
+    const EXP_MAX: u32 = (1 << Self::EXP_BITS) - 1;
+}
+
+impl IeeeFloat for f32 {
+    const NAME: &'static str = "f32";
+    const EXP_BITS: u32 = 8;
+    const MANT_BITS: u32 = 23;
+
+    fn to_raw(self) -> u64 {
+        self.to_bits() as u64
+    }
+
+    fn from_raw(raw: u64) -> Self {
+        f32::from_bits(raw as u32)
+    }
+}
+
+impl IeeeFloat for f64 {
+    const NAME: &'static str = "f64";
+    const EXP_BITS: u32 = 11;
+    const MANT_BITS: u32 = 52;
+
+    fn to_raw(self) -> u64 {
+        self.to_bits()
+    }
+
+    fn from_raw(raw: u64) -> Self {
+        f64::from_bits(raw)
+    }
+}
+
+/// # 浮点数分类
+/// * Zero：指数位、尾数位全 0
+/// * Subnormal：指数位全 0，尾数位非 0，没有隐含的 1
+/// * Normal：指数位既不全 0 也不全 1
+/// * Infinite：指数位全 1，尾数位全 0
+/// * Nan：指数位全 1，尾数位非 0；尾数最高位为 1 是 quiet NaN，其余位是 payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatClass {
+    Zero,
+    Subnormal,
+    Normal,
+    Infinite,
+    Nan { quiet: bool, payload: u64 },
+}
+
+impl Display for FloatClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            FloatClass::Zero => write!(f, "零"),
+            FloatClass::Subnormal => write!(f, "非规格化数"),
+            FloatClass::Normal => write!(f, "规格化数"),
+            FloatClass::Infinite => write!(f, "无穷大"),
+            FloatClass::Nan { quiet, payload } => {
+                let kind = if quiet { "quiet" } else { "signaling" };
+                write!(f, "NaN（{}，payload = {:#x}）", kind, payload)
+            }
+        }
+    }
+}
+
+/// # 浮点数的位分解
+#[derive(Debug, Clone, Copy)]
+pub struct FloatBits<F: IeeeFloat> {
+    value: F,
+    raw: u64,
+}
+
+impl<F: IeeeFloat> FloatBits<F> {
+    pub fn new(value: F) -> Self {
+        Self { value, raw: value.to_raw() }
+    }
+
+    pub fn value(&self) -> F {
+        self.value
+    }
+
+    pub fn raw(&self) -> u64 {
+        self.raw
+    }
+
+    /// 符号位为 1 时返回 true
+    pub fn is_negative(&self) -> bool {
+        self.raw >> (F::TOTAL_BITS - 1) & 1 == 1
+    }
+
+    /// 存储的（带偏移量的）指数
+    pub fn biased_exponent(&self) -> u32 {
+        ((self.raw >> F::MANT_BITS) & F::EXP_MAX as u64) as u32
+    }
+
+    /// 真实指数：规格化数为 `biased - bias`，零和非规格化数固定为 `1 - bias`
+    pub fn unbiased_exponent(&self) -> i32 {
+        match self.biased_exponent() {
+            0 => 1 - F::BIAS,
+            e => e as i32 - F::BIAS,
+        }
+    }
+
+    /// 尾数位（不含隐含的 1）
+    pub fn mantissa(&self) -> u64 {
+        self.raw & ((1u64 << F::MANT_BITS) - 1)
+    }
+
+    pub fn class(&self) -> FloatClass {
+        let (exp, mant) = (self.biased_exponent(), self.mantissa());
+        match (exp, mant) {
+            (0, 0) => FloatClass::Zero,
+            (0, _) => FloatClass::Subnormal,
+            (e, 0) if e == F::EXP_MAX => FloatClass::Infinite,
+            (e, m) if e == F::EXP_MAX => {
+                let quiet_bit = 1u64 << (F::MANT_BITS - 1);
+                FloatClass::Nan { quiet: m & quiet_bit != 0, payload: m & (quiet_bit - 1) }
+            }
+            _ => FloatClass::Normal,
+        }
+    }
+
+    /// 按 `符号 指数 尾数` 分组的二进制字符串
+    pub fn bit_string(&self) -> String {
+        let bits = format!("{:0width$b}", self.raw, width = F::TOTAL_BITS as usize);
+        let exp_end = 1 + F::EXP_BITS as usize;
+        format!("{} {} {}", &bits[..1], &bits[1..exp_end], &bits[exp_end..])
+    }
+
+    /// 精确的十进制值：有限浮点数都是 `m * 2^e`，一定能写成有限位小数
+    pub fn exact_decimal(&self) -> String {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let (significand, exp) = match self.class() {
+            FloatClass::Nan { .. } => return "NaN".to_string(),
+            FloatClass::Infinite => return format!("{}inf", sign),
+            FloatClass::Zero => return format!("{}0", sign),
+            FloatClass::Subnormal => (self.mantissa(), self.unbiased_exponent() - F::MANT_BITS as i32),
+            FloatClass::Normal => (
+                self.mantissa() | 1u64 << F::MANT_BITS,
+                self.unbiased_exponent() - F::MANT_BITS as i32,
+            ),
+        };
+
+        let mut digits = Decimal::from_u64(significand);
+        if exp >= 0 {
+            for _ in 0..exp {
+                digits.mul_small(2);
+            }
+            return format!("{}{}", sign, digits);
+        }
+
+        // m * 2^-k = m * 5^k / 10^k
+        let k = (-exp) as usize;
+        for _ in 0..k {
+            digits.mul_small(5);
+        }
+        let text = format!("{:0>width$}", digits.to_string(), width = k + 1);
+        let (int_part, frac_part) = text.split_at(text.len() - k);
+        let frac_part = frac_part.trim_end_matches('0');
+        if frac_part.is_empty() {
+            format!("{}{}", sign, int_part)
+        } else {
+            format!("{}{}.{}", sign, int_part, frac_part)
+        }
+    }
+
+    /// 比当前值大的下一个可表示值（IEEE 754 nextUp）
+    pub fn next_up(&self) -> F {
+        let sign_bit = 1u64 << (F::TOTAL_BITS - 1);
+        match self.class() {
+            FloatClass::Nan { .. } => self.value,
+            FloatClass::Infinite if !self.is_negative() => self.value,
+            FloatClass::Zero => F::from_raw(1),
+            _ if self.is_negative() => {
+                let raw = self.raw - 1;
+                // -最小非规格化数的下一个值是 -0
+                F::from_raw(if raw == sign_bit { sign_bit } else { raw })
+            }
+            _ => F::from_raw(self.raw + 1),
+        }
+    }
+
+    /// 比当前值小的下一个可表示值（IEEE 754 nextDown）
+    pub fn next_down(&self) -> F {
+        let sign_bit = 1u64 << (F::TOTAL_BITS - 1);
+        let negated = FloatBits::new(F::from_raw(self.raw ^ sign_bit));
+        F::from_raw(negated.next_up().to_raw() ^ sign_bit)
+    }
+}
+
+impl<F: IeeeFloat + Display> Display for FloatBits<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} {}", F::NAME, self.value)?;
+        writeln!(f, "  位模式：{}", self.bit_string())?;
+        writeln!(f, "  符号：{}", if self.is_negative() { "-" } else { "+" })?;
+        writeln!(
+            f,
+            "  指数：biased = {}，unbiased = {}（bias = {}）",
+            self.biased_exponent(),
+            self.unbiased_exponent(),
+            F::BIAS
+        )?;
+        writeln!(f, "  尾数：{:#x}", self.mantissa())?;
+        writeln!(f, "  分类：{}", self.class())?;
+        writeln!(f, "  精确值：{}", self.exact_decimal())?;
+        writeln!(f, "  前一个：{}", FloatBits::new(self.next_down()).exact_decimal())?;
+        write!(f, "  后一个：{}", FloatBits::new(self.next_up()).exact_decimal())
+    }
+}
+
+/// # 两个浮点数按位对比
+pub struct FloatDiff<F: IeeeFloat> {
+    left: FloatBits<F>,
+    right: FloatBits<F>,
+}
+
+pub fn diff<F: IeeeFloat>(left: F, right: F) -> FloatDiff<F> {
+    FloatDiff { left: FloatBits::new(left), right: FloatBits::new(right) }
+}
+
+impl<F: IeeeFloat> FloatDiff<F> {
+    /// 不同的位，按从高到低的位置返回（位置 0 为最低位）
+    pub fn differing_bits(&self) -> Vec<u32> {
+        let xor = self.left.raw ^ self.right.raw;
+        (0..F::TOTAL_BITS).rev().filter(|i| xor >> i & 1 == 1).collect()
+    }
+
+    /// 位所在的字段
+    fn field_of(bit: u32) -> &'static str {
+        if bit == F::TOTAL_BITS - 1 {
+            "符号"
+        } else if bit >= F::MANT_BITS {
+            "指数"
+        } else {
+            "尾数"
+        }
+    }
+}
+
+impl<F: IeeeFloat + Display> Display for FloatDiff<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let marks: String = self
+            .left
+            .bit_string()
+            .chars()
+            .zip(self.right.bit_string().chars())
+            .map(|(l, r)| if l == ' ' { ' ' } else if l == r { '.' } else { '^' })
+            .collect();
+        writeln!(f, "  {:<24} {}", self.left.value, self.left.bit_string())?;
+        writeln!(f, "  {:<24} {}", self.right.value, self.right.bit_string())?;
+        writeln!(f, "  {:<24} {}", "", marks)?;
+        let bits = self.differing_bits();
+        if bits.is_empty() {
+            return write!(f, "  位模式完全相同");
+        }
+        let detail: Vec<String> = bits.iter().map(|&b| format!("{}({})", b, Self::field_of(b))).collect();
+        write!(f, "  共 {} 位不同：{}", bits.len(), detail.join(", "))
+    }
+}
+
+/// 只支持乘以小整数的十进制大数，小端存储，每个元素 9 位
+struct Decimal {
+    limbs: Vec<u32>,
+}
+
+impl Decimal {
+    const BASE: u64 = 1_000_000_000;
+
+    fn from_u64(mut n: u64) -> Self {
+        let mut limbs = vec![];
+        while n > 0 {
+            limbs.push((n % Self::BASE) as u32);
+            n /= Self::BASE;
+        }
+        Self { limbs }
+    }
+
+    fn mul_small(&mut self, factor: u32) {
+        let mut carry = 0u64;
+        for limb in self.limbs.iter_mut() {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = (product % Self::BASE) as u32;
+            carry = product / Self::BASE;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut iter = self.limbs.iter().rev();
+        match iter.next() {
+            None => return write!(f, "0"),
+            Some(top) => write!(f, "{}", top)?,
+        }
+        for limb in iter {
+            write!(f, "{:09}", limb)?;
+        }
+        Ok(())
+    }
+}
+
+fn decompose_f64() {
+    println!("{}", FloatBits::new(0.1f64));
+    println!("{}", FloatBits::new(0.1f64 + 0.2));
+    println!("{}", FloatBits::new(-2.5f64));
+
+    assert_eq!(FloatBits::new(1.0f64).biased_exponent(), 1023);
+    assert_eq!(FloatBits::new(1.0f64).unbiased_exponent(), 0);
+    assert_eq!(FloatBits::new(0.1f64).exact_decimal(), "0.1000000000000000055511151231257827021181583404541015625");
+}
+
+fn decompose_f32() {
+    println!("{}", FloatBits::new(0.1f32));
+    println!("{}", FloatBits::new(16777216f32)); // 2^24，f32 能精确表示的连续整数上限
+
+    assert_eq!(FloatBits::new(0.1f32).exact_decimal(), "0.100000001490116119384765625");
+    assert_eq!(FloatBits::new(16777216f32).next_up(), 16777218f32);
+}
+
+fn special_values() {
+    let values = [0.0, -0.0, f64::MIN_POSITIVE, f64::MIN_POSITIVE / 4.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN];
+    for v in values {
+        let bits = FloatBits::new(v);
+        println!("{:>24e} {} {}", v, bits.bit_string(), bits.class());
+    }
+
+    // 自定义 payload 的 NaN
+    let nan = FloatBits::new(f32::from_bits(0x7fc0_0042));
+    println!("{}", nan);
+    assert_eq!(nan.class(), FloatClass::Nan { quiet: true, payload: 0x42 });
+    assert_eq!(FloatBits::new(f64::MIN_POSITIVE / 4.0).class(), FloatClass::Subnormal);
+}
+
+fn neighbours() {
+    let one = FloatBits::new(1.0f64);
+    println!("1.0 的前一个：{}", FloatBits::new(one.next_down()).exact_decimal());
+    println!("1.0 的后一个：{}", FloatBits::new(one.next_up()).exact_decimal());
+    assert_eq!(one.next_up() - 1.0, f64::EPSILON);
+
+    // 0 的两侧是最小的非规格化数
+    let zero = FloatBits::new(0.0f64);
+    println!("0 的后一个：{:e}", zero.next_up());
+    assert_eq!(zero.next_up(), f64::from_bits(1));
+    assert_eq!(FloatBits::new(-f64::from_bits(1)).next_up().to_bits(), (-0.0f64).to_bits());
+    assert_eq!(FloatBits::new(f64::MAX).next_up(), f64::INFINITY);
+    assert_eq!(FloatBits::new(f64::INFINITY).next_down(), f64::MAX);
+}
+
+fn bit_diff() {
+    println!("f64: 0.1 + 0.2 vs 0.3");
+    println!("{}", diff(0.1f64 + 0.2, 0.3));
+    assert_eq!(diff(0.1f64 + 0.2, 0.3).differing_bits(), vec![2, 1, 0]);
+
+    println!("f32: 0.1 + 0.2 vs 0.3");
+    println!("{}", diff(0.1f32 + 0.2, 0.3));
+    assert!(diff(0.1f32 + 0.2, 0.3).differing_bits().is_empty());
+
+    println!("1.0 vs -1.0");
+    println!("{}", diff(1.0f64, -1.0));
+}
@@ -0,0 +1,322 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::{Duration, Instant};
+use num::complex::Complex;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("Mandelbrot 集", Box::new(|| mandelbrot())),
+        ("Julia 集", Box::new(|| julia())),
+        ("放大", Box::new(|| zoom())),
+        ("输出图片", Box::new(|| write_images())),
+        ("统计", Box::new(|| render_stats())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 分形的种类
+/// 都是迭代 `z = z² + c`，区别在于谁是变量：
+/// * Mandelbrot：z 从 0 开始，c 是画面上的点
+/// * Julia：c 固定，z 从画面上的点开始
+#[derive(Debug, Clone, Copy)]
+pub enum FractalKind {
+    Mandelbrot,
+    Julia(Complex<f64>),
+}
+
+/// 逃逸时间：|z| 超过 2 后一定发散，返回此时的迭代次数；没有逃逸返回 None
+pub fn escape_time(kind: FractalKind, point: Complex<f64>, max_iter: u32) -> Option<u32> {
+    let (mut z, c) = match kind {
+        FractalKind::Mandelbrot => (Complex::new(0.0, 0.0), point),
+        FractalKind::Julia(c) => (point, c),
+    };
+    for i in 0..max_iter {
+        // norm_sqr 避免开方
+        if z.norm_sqr() > 4.0 {
+            return Some(i);
+        }
+        z = z * z + c;
+    }
+    None
+}
+
+/// # 渲染参数
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    kind: FractalKind,
+    center: Complex<f64>,
+    /// 实轴方向上可见的宽度
+    span: f64,
+    max_iter: u32,
+    width: usize,
+    height: usize,
+    /// 单个像素的高宽比，终端字符大约是 2:1
+    pixel_aspect: f64,
+}
+
+impl Renderer {
+    pub fn new(kind: FractalKind) -> Self {
+        let center = match kind {
+            FractalKind::Mandelbrot => Complex::new(-0.5, 0.0),
+            FractalKind::Julia(_) => Complex::new(0.0, 0.0),
+        };
+        Self { kind, center, span: 3.5, max_iter: 100, width: 80, height: 40, pixel_aspect: 1.0 }
+    }
+
+    pub fn size(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn max_iter(mut self, max_iter: u32) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    pub fn pixel_aspect(mut self, aspect: f64) -> Self {
+        self.pixel_aspect = aspect;
+        self
+    }
+
+    /// 以 center 为中心放大 factor 倍
+    pub fn zoom(mut self, center: Complex<f64>, factor: f64) -> Self {
+        self.center = center;
+        self.span /= factor;
+        self
+    }
+
+    /// 像素 (col, row) 中心对应的复平面坐标，row 向下增长，虚轴向上增长
+    fn point_at(&self, col: usize, row: usize) -> Complex<f64> {
+        let pixel = self.span / self.width as f64;
+        let re = self.center.re + (col as f64 + 0.5 - self.width as f64 / 2.0) * pixel;
+        let im = self.center.im - (row as f64 + 0.5 - self.height as f64 / 2.0) * pixel * self.pixel_aspect;
+        Complex::new(re, im)
+    }
+
+    pub fn render(&self) -> Frame {
+        let mut data = Vec::with_capacity(self.width * self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                data.push(escape_time(self.kind, self.point_at(col, row), self.max_iter));
+            }
+        }
+        Frame { width: self.width, height: self.height, max_iter: self.max_iter, data }
+    }
+}
+
+/// # 渲染结果
+/// 每个像素保存逃逸时间，之后再决定用字符还是颜色显示
+pub struct Frame {
+    width: usize,
+    height: usize,
+    max_iter: u32,
+    data: Vec<Option<u32>>,
+}
+
+impl Frame {
+    /// 集合内部（没有逃逸）的像素个数
+    pub fn inside_count(&self) -> usize {
+        self.data.iter().filter(|p| p.is_none()).count()
+    }
+
+    /// 0.0（立刻逃逸）~ 1.0（集合内部）
+    fn intensity(&self, pixel: Option<u32>) -> f64 {
+        match pixel {
+            None => 1.0,
+            Some(i) => (i as f64 / self.max_iter as f64).sqrt(),
+        }
+    }
+
+    /// 宽度为 0 时没有像素，返回空字符串
+    pub fn to_ascii(&self) -> String {
+        const RAMP: &[u8] = b" .:-=+*#%@";
+        if self.width == 0 {
+            return String::new();
+        }
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.data.chunks(self.width) {
+            for &pixel in row {
+                let index = (self.intensity(pixel) * (RAMP.len() - 1) as f64).round() as usize;
+                out.push(RAMP[index] as char);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// 灰度图：P5 二进制格式
+    pub fn write_pgm(&self, out: &mut impl Write) -> std::io::Result<()> {
+        write!(out, "P5\n{} {}\n255\n", self.width, self.height)?;
+        let bytes: Vec<u8> = self.data.iter().map(|&p| (self.intensity(p) * 255.0) as u8).collect();
+        out.write_all(&bytes)?;
+        // BufWriter 在 drop 时写出剩余数据会忽略错误，这里主动 flush 把错误返回
+        out.flush()
+    }
+
+    /// 彩色图：P6 二进制格式
+    pub fn write_ppm(&self, out: &mut impl Write, palette: &Palette) -> std::io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for &pixel in &self.data {
+            let color = match pixel {
+                None => [0, 0, 0],
+                Some(_) => palette.color(self.intensity(pixel)),
+            };
+            out.write_all(&color)?;
+        }
+        out.flush()
+    }
+
+    pub fn stats(&self) -> FrameStats {
+        let escaped: Vec<u32> = self.data.iter().flatten().copied().collect();
+        let inside = self.data.len() - escaped.len();
+        let escaped_iterations: u64 = escaped.iter().map(|&i| i as u64).sum();
+        FrameStats {
+            pixels: self.data.len(),
+            inside,
+            iterations: escaped_iterations + inside as u64 * self.max_iter as u64,
+            mean_escape: if escaped.is_empty() { 0.0 } else { escaped_iterations as f64 / escaped.len() as f64 },
+            max_escape: escaped.iter().max().copied(),
+        }
+    }
+}
+
+/// # 一帧的统计
+/// * `iterations` 是实际迭代的总次数，集合内部的像素每个都跑满 `max_iter` 次
+/// * `mean_escape`、`max_escape` 只统计逃逸了的像素
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameStats {
+    pub pixels: usize,
+    pub inside: usize,
+    pub iterations: u64,
+    pub mean_escape: f64,
+    pub max_escape: Option<u32>,
+}
+
+/// 连续渲染 runs 次，返回每次的耗时
+pub fn time_renders(renderer: &Renderer, runs: usize) -> (Frame, Vec<Duration>) {
+    assert!(runs > 0, "runs must be positive");
+    let mut times = Vec::with_capacity(runs);
+    let mut frame = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        frame = Some(renderer.render());
+        times.push(start.elapsed());
+    }
+    (frame.unwrap(), times)
+}
+
+/// # 调色板
+/// 在几个颜色节点之间线性插值
+pub struct Palette {
+    stops: Vec<[u8; 3]>,
+}
+
+impl Palette {
+    pub fn new(stops: Vec<[u8; 3]>) -> Self {
+        assert!(stops.len() >= 2, "palette needs at least two colors");
+        Self { stops }
+    }
+
+    /// 深蓝 -> 白 -> 橙
+    pub fn sunset() -> Self {
+        Self::new(vec![[0, 7, 100], [32, 107, 203], [237, 255, 255], [255, 170, 0], [0, 2, 0]])
+    }
+
+    pub fn color(&self, t: f64) -> [u8; 3] {
+        let scaled = t.clamp(0.0, 1.0) * (self.stops.len() - 1) as f64;
+        let index = (scaled.floor() as usize).min(self.stops.len() - 2);
+        let frac = scaled - index as f64;
+        let (from, to) = (self.stops[index], self.stops[index + 1]);
+        let mut color = [0; 3];
+        for i in 0..3 {
+            color[i] = (from[i] as f64 + (to[i] as f64 - from[i] as f64) * frac).round() as u8;
+        }
+        color
+    }
+}
+
+fn mandelbrot() {
+    let start = Instant::now();
+    let frame = Renderer::new(FractalKind::Mandelbrot).size(78, 32).pixel_aspect(2.0).render();
+    print!("{}", frame.to_ascii());
+    println!("耗时：{:?}", start.elapsed());
+
+    // c = 0 和 c = -1 在集合内，c = 1 很快逃逸
+    assert_eq!(escape_time(FractalKind::Mandelbrot, Complex::new(0.0, 0.0), 1000), None);
+    assert_eq!(escape_time(FractalKind::Mandelbrot, Complex::new(-1.0, 0.0), 1000), None);
+    assert_eq!(escape_time(FractalKind::Mandelbrot, Complex::new(1.0, 0.0), 1000), Some(3));
+}
+
+fn julia() {
+    let c = Complex::new(-0.8, 0.156);
+    let frame = Renderer::new(FractalKind::Julia(c)).size(78, 32).pixel_aspect(2.0).max_iter(200).render();
+    print!("{}", frame.to_ascii());
+}
+
+fn zoom() {
+    // 海马谷附近
+    let center = Complex::new(-0.743_643_887, 0.131_825_904);
+    for factor in [10.0, 100.0] {
+        let renderer = Renderer::new(FractalKind::Mandelbrot)
+            .size(78, 24)
+            .pixel_aspect(2.0)
+            .max_iter(500)
+            .zoom(center, factor);
+        let start = Instant::now();
+        let frame = renderer.render();
+        println!("放大 {} 倍，集合内像素 {} 个，耗时：{:?}", factor, frame.inside_count(), start.elapsed());
+        print!("{}", frame.to_ascii());
+    }
+}
+
+fn write_images() {
+    let dir = std::env::temp_dir();
+    let frame = Renderer::new(FractalKind::Mandelbrot).size(600, 400).max_iter(200).zoom(Complex::new(-0.6, 0.0), 1.2).render();
+
+    let pgm = dir.join("mandelbrot.pgm");
+    let ppm = dir.join("mandelbrot.ppm");
+    let result = File::create(&pgm)
+        .and_then(|f| frame.write_pgm(&mut BufWriter::new(f)))
+        .and_then(|_| File::create(&ppm))
+        .and_then(|f| frame.write_ppm(&mut BufWriter::new(f), &Palette::sunset()));
+    match result {
+        Ok(_) => println!("已写入：{} 和 {}", pgm.display(), ppm.display()),
+        Err(e) => println!("写入图片失败：{}", e),
+    }
+
+    let mut buf = vec![];
+    frame.write_pgm(&mut buf).unwrap();
+    assert!(buf.starts_with(b"P5\n600 400\n255\n"));
+    assert_eq!(buf.len(), "P5\n600 400\n255\n".len() + 600 * 400);
+
+    let palette = Palette::sunset();
+    assert_eq!(palette.color(0.0), [0, 7, 100]);
+    assert_eq!(palette.color(1.0), [0, 2, 0]);
+
+    assert_eq!(Renderer::new(FractalKind::Mandelbrot).size(0, 10).render().to_ascii(), "");
+}
+
+/// # 统计
+/// 同一帧渲染多次，取最短、中位数、最长耗时；纯 CPU 计算，适合用来比较 debug 和 release
+fn render_stats() {
+    let renderer = Renderer::new(FractalKind::Mandelbrot).size(200, 100).max_iter(300);
+    let (frame, mut times) = time_renders(&renderer, 5);
+    times.sort();
+    let stats = frame.stats();
+    let median = times[times.len() / 2];
+    println!("像素 {}，集合内 {}，迭代总次数 {}", stats.pixels, stats.inside, stats.iterations);
+    println!("逃逸像素平均迭代 {:.2} 次，最多 {} 次", stats.mean_escape, stats.max_escape.unwrap_or(0));
+    println!("耗时：最短 {:?}，中位数 {:?}，最长 {:?}", times[0], median, times[times.len() - 1]);
+    println!("速度：{:.1} 百万次迭代/秒", stats.iterations as f64 / median.as_secs_f64() / 1e6);
+
+    assert_eq!(stats.pixels, 200 * 100);
+    assert_eq!(stats.inside, frame.inside_count());
+    assert!(stats.max_escape.is_some_and(|i| i < 300));
+}
@@ -1,4 +1,5 @@
 use num::complex::Complex;
+use crate::ch2::{fft, float_bits};
 
 pub fn main() {
     let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
@@ -9,6 +10,7 @@ pub fn main() {
         ("位运算", Box::new(|| bit_operation())),
         ("序列（Range）", Box::new(|| range())),
         ("有理数和复数", Box::new(|| rational_and_complex_numbers())),
+        ("复数应用：FFT", Box::new(|| complex_fft())),
     ];
 
     for (name, function) in functions.into_iter() {
@@ -66,6 +68,8 @@ fn float() {
     println!("xyz (f64)");
     println!("  0.1 + 0.2: {:x}", (xyz.0 + xyz.1).to_bits());
     println!("        0.3: {:x}", xyz.2.to_bits());
+    // 逐位对比：只有尾数的最后几位不同
+    println!("{}", float_bits::diff(xyz.0 + xyz.1, xyz.2));
 
     assert_eq!(abc.0 + abc.1, abc.2);
     // assert_eq!(xyz.0 + xyz.1, xyz.2); assertion `left == right` failed
@@ -175,3 +179,9 @@ fn rational_and_complex_numbers() {
     let result = a + b;
     println!("{} + {}i", result.re, result.im);
 }
+
+//复数应用：用 FFT 计算多项式乘法，详见 fft 模块
+fn complex_fft() {
+    let product = fft::convolve(&[1, 2, 3], &[4, 5]);
+    println!("(1 + 2x + 3x²)(4 + 5x) 的系数：{:?}", product);
+}
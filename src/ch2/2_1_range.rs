@@ -0,0 +1,371 @@
+use std::marker::PhantomData;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("整数步长序列", Box::new(|| int_step())),
+        ("反向序列", Box::new(|| reverse())),
+        ("浮点数序列", Box::new(|| floats())),
+        ("字符序列", Box::new(|| chars())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 可以按步长遍历的整数
+/// 内部统一转成 i128 计算，第 i 个元素直接算出 `start + i * step`，
+/// 所以两端都可以取值，也能精确知道剩余个数；
+/// i128、u128 两端的差超出 i128 的范围，所以不支持
+pub trait StepInt: Copy {
+    fn to_i128(self) -> i128;
+    fn from_i128(v: i128) -> Self;
+}
+
+macro_rules! impl_step_int {
+    ($($t:ty),*) => {
+        $(
+            impl StepInt for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(v: i128) -> Self {
+                    v as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_step_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// # 个数一定放得下 usize 的类型
+/// 与标准库的 `Range<T>` 一样，只有这些类型的序列实现 `ExactSizeIterator`；
+/// 其他类型（如 `0..=u64::MAX`）的个数可能超过 `usize::MAX`
+pub trait ExactStepInt: StepInt {}
+
+macro_rules! impl_exact_step_int {
+    ($($t:ty),*) => {
+        $(impl ExactStepInt for $t {})*
+    };
+}
+
+impl_exact_step_int!(i8, i16, u8, u16);
+#[cfg(target_pointer_width = "64")]
+impl_exact_step_int!(i32, u32);
+
+/// # 步长序列
+/// * 还没取出的元素下标是 `front..back`
+/// * 步长可以为负数，表示从大到小
+#[derive(Debug, Clone)]
+pub struct StepRange<T: StepInt> {
+    start: i128,
+    step: i128,
+    front: u128,
+    back: u128,
+    _marker: PhantomData<T>,
+}
+
+impl<T: StepInt> StepRange<T> {
+    fn with_count(start: T, step: i128, count: u128) -> Self {
+        Self { start: start.to_i128(), step, front: 0, back: count, _marker: PhantomData }
+    }
+
+    fn nth_value(&self, i: u128) -> T {
+        T::from_i128(self.start + i as i128 * self.step)
+    }
+}
+
+/// `[start, end)` 按 `step` 取值，与 `(start..end).step_by(step)` 一致，但允许负步长
+///
+/// # Panics
+/// step 为 0 时 panic，与 `step_by(0)` 一致
+pub fn range_step<T: StepInt>(start: T, end: T, step: i128) -> StepRange<T> {
+    assert!(step != 0, "step must not be zero");
+    let distance = end.to_i128() - start.to_i128();
+    let count = if distance != 0 && distance.signum() == step.signum() {
+        (distance.unsigned_abs() - 1) / step.unsigned_abs() + 1
+    } else {
+        0
+    };
+    StepRange::with_count(start, step, count)
+}
+
+/// `[start, end]` 按 `step` 取值
+pub fn range_step_inclusive<T: StepInt>(start: T, end: T, step: i128) -> StepRange<T> {
+    assert!(step != 0, "step must not be zero");
+    let distance = end.to_i128() - start.to_i128();
+    let count = if distance == 0 || distance.signum() == step.signum() {
+        distance.unsigned_abs() / step.unsigned_abs() + 1
+    } else {
+        0
+    };
+    StepRange::with_count(start, step, count)
+}
+
+/// 从 start 递减到 end（包含两端）
+pub fn countdown<T: StepInt>(start: T, end: T) -> StepRange<T> {
+    range_step_inclusive(start, end, -1)
+}
+
+impl<T: StepInt> Iterator for StepRange<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        let value = self.nth_value(self.front);
+        self.front += 1;
+        Some(value)
+    }
+
+    /// 个数超过 usize 时返回 `(usize::MAX, None)`
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.back - self.front) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T: StepInt> DoubleEndedIterator for StepRange<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.nth_value(self.back))
+    }
+}
+
+impl<T: ExactStepInt> ExactSizeIterator for StepRange<T> {}
+
+/// # 浮点数序列
+/// 不用 `x += step` 累加（误差会越积越大），而是每次用下标直接算出第 i 个值
+#[derive(Debug, Clone)]
+pub struct FloatRange {
+    start: f64,
+    end: f64,
+    count: usize,
+    front: usize,
+    back: usize,
+    kind: FloatRangeKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FloatRangeKind {
+    /// 在 `[start, end]` 上均匀取 count 个点
+    Linspace,
+    /// `start + i * step`，不包含 end
+    Arange { step: f64 },
+}
+
+impl FloatRange {
+    fn new(start: f64, end: f64, count: usize, kind: FloatRangeKind) -> Self {
+        Self { start, end, count, front: 0, back: count, kind }
+    }
+
+    fn nth_value(&self, i: usize) -> f64 {
+        match self.kind {
+            // 只有一个点时取 start，与 numpy 一致
+            FloatRangeKind::Linspace if self.count == 1 => self.start,
+            FloatRangeKind::Linspace if i + 1 == self.count => self.end,
+            FloatRangeKind::Linspace => {
+                let t = i as f64 / (self.count - 1) as f64;
+                self.start + (self.end - self.start) * t
+            }
+            FloatRangeKind::Arange { step } => self.start + i as f64 * step,
+        }
+    }
+}
+
+/// 在 `[start, end]` 上均匀取 `count` 个点，两个端点都精确命中
+pub fn linspace(start: f64, end: f64, count: usize) -> FloatRange {
+    FloatRange::new(start, end, count, FloatRangeKind::Linspace)
+}
+
+/// `[start, end)` 按 `step` 取值
+/// 个数按 `(end - start) / step` 向上取整，
+/// 商非常接近整数时（如 `1.0 / 0.1`）视为整数，避免多出一个几乎等于 end 的点
+///
+/// # Panics
+/// step 为 0 或不是有限数时 panic
+pub fn arange(start: f64, end: f64, step: f64) -> FloatRange {
+    assert!(step != 0.0 && step.is_finite(), "step must be finite and non-zero");
+    let quotient = (end - start) / step;
+    let nearest = quotient.round();
+    let count = if (quotient - nearest).abs() <= 1e-9 * nearest.abs().max(1.0) {
+        nearest
+    } else {
+        quotient.ceil()
+    };
+    FloatRange::new(start, end, count.max(0.0) as usize, FloatRangeKind::Arange { step })
+}
+
+impl Iterator for FloatRange {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        let value = self.nth_value(self.front);
+        self.front += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for FloatRange {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.nth_value(self.back))
+    }
+}
+
+impl ExactSizeIterator for FloatRange {}
+
+/// # 字符序列
+/// 代理码点 `U+D800..=U+DFFF` 不是合法的 char，遍历时跳过
+#[derive(Debug, Clone)]
+pub struct CharRange {
+    front: u32,
+    back: u32,
+}
+
+const SURROGATES: std::ops::RangeInclusive<u32> = 0xD800..=0xDFFF;
+
+/// `[start, end]` 之间所有合法的 char
+pub fn char_range(start: char, end: char) -> CharRange {
+    CharRange { front: start as u32, back: end as u32 + 1 }
+}
+
+impl Iterator for CharRange {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if SURROGATES.contains(&self.front) {
+            self.front = self.front.max(*SURROGATES.end() + 1);
+        }
+        if self.front >= self.back {
+            return None;
+        }
+        let c = char::from_u32(self.front);
+        self.front += 1;
+        c
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let total = self.back.saturating_sub(self.front);
+        // 与代理区的交集
+        let lo = self.front.max(*SURROGATES.start());
+        let hi = self.back.min(*SURROGATES.end() + 1);
+        let len = (total - hi.saturating_sub(lo)) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for CharRange {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if SURROGATES.contains(&(self.back.wrapping_sub(1))) {
+            self.back = self.back.min(*SURROGATES.start());
+        }
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        char::from_u32(self.back)
+    }
+}
+
+impl ExactSizeIterator for CharRange {}
+
+fn int_step() {
+    let evens: Vec<i32> = range_step(0, 10, 2).collect();
+    println!("{:?}", evens);
+    assert_eq!(evens, (0..10).step_by(2).collect::<Vec<_>>());
+
+    let down: Vec<i32> = range_step(10, 0, -3).collect();
+    println!("{:?}", down);
+    assert_eq!(down, vec![10, 7, 4, 1]);
+
+    // 无符号整数也可以使用负步长
+    let bytes: Vec<u8> = range_step_inclusive(255u8, 0, -85).collect();
+    println!("{:?}", bytes);
+    assert_eq!(bytes, vec![255, 170, 85, 0]);
+
+    // 方向不一致时为空序列
+    assert_eq!(range_step(0, 10, -1).len(), 0);
+    assert_eq!(range_step(i64::MIN, i64::MAX, i64::MAX as i128).count(), 3);
+
+    // i64 两端相减不会溢出；个数超过 usize 时 size_hint 没有上界
+    assert_eq!(range_step_inclusive(i64::MIN, i64::MAX, 1).size_hint(), (usize::MAX, None));
+    assert_eq!(range_step_inclusive(u64::MAX, 0, -1).size_hint(), (usize::MAX, None));
+    assert_eq!(range_step_inclusive(i32::MIN, i32::MAX, 1).len(), 1 << 32);
+}
+
+fn reverse() {
+    let r: Vec<u32> = countdown(5u32, 1).collect();
+    println!("{:?}", r);
+    assert_eq!(r, (1..=5).rev().collect::<Vec<_>>());
+
+    // 从两端同时取值
+    let mut it = range_step_inclusive(1, 10, 3);
+    assert_eq!(it.len(), 4);
+    assert_eq!((it.next(), it.next_back()), (Some(1), Some(10)));
+    assert_eq!(it.rev().collect::<Vec<_>>(), vec![7, 4]);
+}
+
+fn floats() {
+    // 累加 0.1 十次并不等于 1.0
+    let mut acc = 0.0;
+    for _ in 0..10 {
+        acc += 0.1;
+    }
+    println!("累加：{}", acc);
+
+    let xs: Vec<f64> = arange(0.0, 1.0, 0.1).collect();
+    println!("arange(0, 1, 0.1)：{:?}", xs);
+    assert_eq!(xs.len(), 10);
+    assert_eq!(xs[3], 0.30000000000000004); // 3 * 0.1，只有一次舍入
+
+    let ys: Vec<f64> = linspace(0.0, 1.0, 11).collect();
+    println!("linspace(0, 1, 11)：{:?}", ys);
+    assert_eq!(ys.first(), Some(&0.0));
+    assert_eq!(ys.last(), Some(&1.0));
+
+    let back: Vec<f64> = linspace(-1.0, 1.0, 5).rev().collect();
+    assert_eq!(back, vec![1.0, 0.5, 0.0, -0.5, -1.0]);
+    assert_eq!(linspace(2.0, 3.0, 1).collect::<Vec<_>>(), vec![2.0]);
+    assert_eq!(arange(1.0, 0.0, -0.25).len(), 4);
+    assert_eq!(arange(0.0, 1.0, -0.25).len(), 0);
+}
+
+fn chars() {
+    let letters: String = char_range('a', 'z').collect();
+    println!("{}", letters);
+    assert_eq!(letters, ('a'..='z').collect::<String>());
+
+    // 跨越代理区：U+D7FF 后面紧接着 U+E000
+    let around = char_range('\u{D7FE}', '\u{E001}');
+    assert_eq!(around.len(), 4);
+    let codes: Vec<String> = around.map(|c| format!("U+{:04X}", c as u32)).collect();
+    println!("{:?}", codes);
+    assert_eq!(codes, vec!["U+D7FE", "U+D7FF", "U+E000", "U+E001"]);
+
+    let back: String = char_range('\u{D7FF}', '\u{E000}').rev().collect();
+    assert_eq!(back, "\u{E000}\u{D7FF}");
+    assert_eq!(char_range(char::MIN, char::MAX).len(), 0x110000 - 0x800);
+}
@@ -0,0 +1,204 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Bound, RangeBounds};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("按字符切片", Box::new(|| slicing())),
+        ("按字符编辑", Box::new(|| editing())),
+        ("错误处理", Box::new(|| errors())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 按字符下标操作字符串时的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharIndexError {
+    /// 字符下标超出字符个数
+    OutOfBounds { index: usize, len: usize },
+    /// 起点大于终点
+    InvalidRange { start: usize, end: usize },
+}
+
+impl Display for CharIndexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CharIndexError::OutOfBounds { index, len } => {
+                write!(f, "字符下标 {} 越界，字符串只有 {} 个字符", index, len)
+            }
+            CharIndexError::InvalidRange { start, end } => write!(f, "范围 {}..{} 的起点大于终点", start, end),
+        }
+    }
+}
+
+impl std::error::Error for CharIndexError {}
+
+/// # 按字符下标读取
+/// `&s[a..b]`、`split_at` 用的是字节下标，落在多字节字符中间会 panic；
+/// 这里的下标都是字符下标，越界返回 Err
+pub trait CharIndexExt {
+    /// 字符下标 -> 字节下标，`index == 字符个数` 时返回字符串长度
+    fn char_to_byte(&self, index: usize) -> Result<usize, CharIndexError>;
+
+    fn char_slice(&self, range: impl RangeBounds<usize>) -> Result<&str, CharIndexError>;
+
+    fn split_at_char(&self, index: usize) -> Result<(&str, &str), CharIndexError>;
+}
+
+impl CharIndexExt for str {
+    fn char_to_byte(&self, index: usize) -> Result<usize, CharIndexError> {
+        let mut count = 0;
+        for (i, (byte, _)) in self.char_indices().enumerate() {
+            if i == index {
+                return Ok(byte);
+            }
+            count += 1;
+        }
+        if index == count {
+            Ok(self.len())
+        } else {
+            Err(CharIndexError::OutOfBounds { index, len: count })
+        }
+    }
+
+    fn char_slice(&self, range: impl RangeBounds<usize>) -> Result<&str, CharIndexError> {
+        let (start, end) = byte_range(self, range)?;
+        Ok(&self[start..end])
+    }
+
+    fn split_at_char(&self, index: usize) -> Result<(&str, &str), CharIndexError> {
+        Ok(self.split_at(self.char_to_byte(index)?))
+    }
+}
+
+/// 把字符范围转换成字节范围
+fn byte_range(s: &str, range: impl RangeBounds<usize>) -> Result<(usize, usize), CharIndexError> {
+    // `i + 1` 在 i 为 usize::MAX 时会溢出，这样的下标一定越界
+    let after = |i: usize| i.checked_add(1).ok_or_else(|| out_of_bounds(s, i));
+    let start = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => after(i)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&i) => Some(after(i)?),
+        Bound::Excluded(&i) => Some(i),
+        Bound::Unbounded => None,
+    };
+    if let Some(end) = end {
+        if start > end {
+            return Err(CharIndexError::InvalidRange { start, end });
+        }
+    }
+    let start_byte = s.char_to_byte(start)?;
+    let end_byte = match end {
+        Some(end) => start_byte + s[start_byte..].char_to_byte(end - start).map_err(|_| out_of_bounds(s, end))?,
+        None => s.len(),
+    };
+    Ok((start_byte, end_byte))
+}
+
+fn out_of_bounds(s: &str, index: usize) -> CharIndexError {
+    CharIndexError::OutOfBounds { index, len: s.chars().count() }
+}
+
+/// # 按字符下标修改
+/// 对应 `insert_str`、`replace_range`、`drain`、`truncate`，但不会因为字符边界 panic
+pub trait CharEditExt {
+    fn insert_at_char(&mut self, index: usize, s: &str) -> Result<(), CharIndexError>;
+
+    fn replace_char_range(&mut self, range: impl RangeBounds<usize>, with: &str) -> Result<(), CharIndexError>;
+
+    /// 删除并返回被删除的部分
+    fn remove_char_range(&mut self, range: impl RangeBounds<usize>) -> Result<String, CharIndexError>;
+
+    /// 只保留前 n 个字符，n 超过字符个数时返回 Err
+    fn truncate_chars(&mut self, n: usize) -> Result<(), CharIndexError>;
+}
+
+impl CharEditExt for String {
+    fn insert_at_char(&mut self, index: usize, s: &str) -> Result<(), CharIndexError> {
+        let byte = self.char_to_byte(index)?;
+        self.insert_str(byte, s);
+        Ok(())
+    }
+
+    fn replace_char_range(&mut self, range: impl RangeBounds<usize>, with: &str) -> Result<(), CharIndexError> {
+        let (start, end) = byte_range(self, range)?;
+        self.replace_range(start..end, with);
+        Ok(())
+    }
+
+    fn remove_char_range(&mut self, range: impl RangeBounds<usize>) -> Result<String, CharIndexError> {
+        let (start, end) = byte_range(self, range)?;
+        Ok(self.drain(start..end).collect())
+    }
+
+    fn truncate_chars(&mut self, n: usize) -> Result<(), CharIndexError> {
+        let byte = self.char_to_byte(n)?;
+        self.truncate(byte);
+        Ok(())
+    }
+}
+
+fn slicing() {
+    let s = "你好，rust";
+    println!("{:?}", s.char_slice(..2));
+    println!("{:?}", s.char_slice(3..));
+    println!("{:?}", s.split_at_char(3));
+
+    assert_eq!(s.char_slice(0..2), Ok("你好"));
+    assert_eq!(s.char_slice(2..=2), Ok("，"));
+    assert_eq!(s.char_slice(..), Ok(s));
+    assert_eq!(s.split_at_char(7), Ok((s, "")));
+    assert_eq!(s.char_to_byte(3), Ok(9));
+}
+
+fn editing() {
+    let mut s = String::from("hello你,rust");
+    s.insert_at_char(6, "好啊").unwrap();
+    println!("{}", s);
+    assert_eq!(s, "hello你好啊,rust");
+
+    s.replace_char_range(9..13, "RUST").unwrap();
+    println!("{}", s);
+
+    let removed = s.remove_char_range(5..8).unwrap();
+    println!("删除了 {:?}，剩下 {}", removed, s);
+    assert_eq!((removed.as_str(), s.as_str()), ("你好啊", "hello,RUST"));
+
+    let mut t = String::from("测试truncate");
+    t.truncate_chars(2).unwrap();
+    assert_eq!(t, "测试");
+}
+
+fn errors() {
+    let mut s = String::from("中国人");
+    let (start, end) = (2, 1);
+    let results = [
+        s.char_slice(1..5).map(|_| ()),
+        s.char_slice(start..end).map(|_| ()),
+        s.split_at_char(4).map(|_| ()),
+        s.insert_at_char(10, "!"),
+        s.truncate_chars(4),
+    ];
+    for result in results {
+        match result {
+            Ok(_) => println!("成功"),
+            Err(e) => println!("错误：{}", e),
+        }
+    }
+    assert_eq!(s.char_slice(1..5), Err(CharIndexError::OutOfBounds { index: 5, len: 3 }));
+    assert_eq!(s.remove_char_range(start..end), Err(CharIndexError::InvalidRange { start: 2, end: 1 }));
+    // 范围的端点是 usize::MAX 时也返回错误，不会因为加 1 溢出而 panic
+    let overflow = CharIndexError::OutOfBounds { index: usize::MAX, len: 3 };
+    assert_eq!(s.char_slice(..=usize::MAX), Err(overflow.clone()));
+    assert_eq!(s.char_slice((Bound::Excluded(usize::MAX), Bound::Unbounded)), Err(overflow.clone()));
+    assert_eq!(s.remove_char_range(1..=usize::MAX), Err(overflow));
+    assert_eq!(s, "中国人"); // 出错时不修改原字符串
+}
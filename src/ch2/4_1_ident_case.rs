@@ -0,0 +1,416 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+use crate::ch2::similarity::did_you_mean;
+use crate::ch2::unicode_inspect::pad_to_width;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("命名风格转换", Box::new(|| conversion())),
+        ("命名风格识别", Box::new(|| detection())),
+        ("标识符校验", Box::new(|| validation())),
+        ("转换成标识符", Box::new(|| escaping())),
+        ("拼写建议", Box::new(|| spelling())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 命名风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    /// `snake_case`：函数、变量、模块
+    Snake,
+    /// `camelCase`
+    Camel,
+    /// `PascalCase`：类型、特征、枚举成员
+    Pascal,
+    /// `kebab-case`：crate 名、命令行参数
+    Kebab,
+    /// `SCREAMING_SNAKE_CASE`：常量、静态变量
+    ScreamingSnake,
+    /// `Title Case`
+    Title,
+}
+
+impl Case {
+    pub const ALL: [Case; 6] = [Case::Snake, Case::Camel, Case::Pascal, Case::Kebab, Case::ScreamingSnake, Case::Title];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Case::Snake => "snake_case",
+            Case::Camel => "camelCase",
+            Case::Pascal => "PascalCase",
+            Case::Kebab => "kebab-case",
+            Case::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+            Case::Title => "Title Case",
+        }
+    }
+
+    /// 按这种风格把单词拼起来
+    /// * 缩写词只保留首字母大写：`HTTP` + `server` -> `HttpServer`
+    /// * camelCase、PascalCase 没有分隔符，纯数字单词会并入前一个单词：`version_2` -> `version2`
+    pub fn join<S: AsRef<str>>(self, words: &[S]) -> String {
+        let words = words.iter().map(AsRef::as_ref);
+        match self {
+            Case::Snake => words.map(str::to_lowercase).collect::<Vec<_>>().join("_"),
+            Case::Kebab => words.map(str::to_lowercase).collect::<Vec<_>>().join("-"),
+            Case::ScreamingSnake => words.map(str::to_uppercase).collect::<Vec<_>>().join("_"),
+            Case::Title => words.map(capitalize).collect::<Vec<_>>().join(" "),
+            Case::Pascal => words.map(capitalize).collect(),
+            Case::Camel => words
+                .enumerate()
+                .map(|(i, word)| if i == 0 { word.to_lowercase() } else { capitalize(word) })
+                .collect(),
+        }
+    }
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 首字母大写，其余小写
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+/// # 拆分单词
+/// 字母、数字以外的字符都是分隔符，此外在这些位置断开：
+/// * 小写字母后面跟大写字母：`fooBar` -> `foo` `Bar`
+/// * 数字后面跟大写字母：`utf8Decoder` -> `utf8` `Decoder`
+/// * 连续大写字母的最后一个后面跟小写字母：`HTTPServer` -> `HTTP` `Server`
+///
+/// 数字跟在前一个单词后面：`v2`、`utf8`、`3d` 都是一个单词
+pub fn split_words(s: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    let mut words = vec![];
+    let mut start = None;
+    for (i, &(byte, c)) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if let Some(begin) = start.take() {
+                words.push(&s[begin..byte]);
+            }
+            continue;
+        }
+        let Some(begin) = start else {
+            start = Some(byte);
+            continue;
+        };
+        let prev = chars[i - 1].1;
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let boundary = c.is_uppercase()
+            && ((prev.is_alphabetic() && !prev.is_uppercase())
+                || prev.is_numeric()
+                || (prev.is_uppercase() && next.is_some_and(char::is_lowercase)));
+        if boundary {
+            words.push(&s[begin..byte]);
+            start = Some(byte);
+        }
+    }
+    if let Some(begin) = start {
+        words.push(&s[begin..]);
+    }
+    words
+}
+
+/// # 命名风格转换
+pub trait CaseExt {
+    fn to_case(&self, case: Case) -> String;
+
+    /// 按 [`Case::ALL`] 的顺序找第一个相符的风格，
+    /// 只有一个小写单词时 snake_case、camelCase、kebab-case 都相符，返回 snake_case
+    fn detect_case(&self) -> Option<Case>;
+}
+
+impl CaseExt for str {
+    fn to_case(&self, case: Case) -> String {
+        case.join(&split_words(self))
+    }
+
+    fn detect_case(&self) -> Option<Case> {
+        let words = split_words(self);
+        if words.is_empty() {
+            return None;
+        }
+        Case::ALL.into_iter().find(|case| case.join(&words) == self)
+    }
+}
+
+/// 任何情况下都不能作为普通标识符
+pub const STRICT_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+/// 保留给将来使用，同样不能作为普通标识符（2024 版本还保留了 `gen`）
+pub const RESERVED_KEYWORDS: &[&str] = &[
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof", "unsized", "virtual",
+    "yield",
+];
+
+/// 只在特定语境下有特殊含义，可以作为普通标识符
+pub const WEAK_KEYWORDS: &[&str] = &["macro_rules", "raw", "safe", "union"];
+
+/// 连 `r#` 也不能用的关键字
+const NOT_RAW: &[&str] = &["crate", "self", "Self", "super"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordKind {
+    Strict,
+    Reserved,
+    Weak,
+}
+
+pub fn keyword_kind(s: &str) -> Option<KeywordKind> {
+    if STRICT_KEYWORDS.contains(&s) {
+        Some(KeywordKind::Strict)
+    } else if RESERVED_KEYWORDS.contains(&s) {
+        Some(KeywordKind::Reserved)
+    } else if WEAK_KEYWORDS.contains(&s) {
+        Some(KeywordKind::Weak)
+    } else {
+        None
+    }
+}
+
+/// # 标识符
+/// `raw` 表示写成了 `r#name` 的原始标识符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ident<'a> {
+    pub name: &'a str,
+    pub raw: bool,
+}
+
+impl Display for Ident<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.raw {
+            write!(f, "r#")?;
+        }
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentError {
+    Empty,
+    /// 单独的 `_` 是通配符，不是标识符
+    Underscore,
+    /// 关键字要写成 `r#name`
+    Keyword(String),
+    /// `crate`、`self`、`Self`、`super` 不能作为原始标识符
+    RawNotAllowed(String),
+    InvalidStart(char),
+    /// index 是字符下标
+    InvalidChar { index: usize, ch: char },
+}
+
+impl Display for IdentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdentError::Empty => write!(f, "标识符不能为空"),
+            IdentError::Underscore => write!(f, "`_` 不是标识符"),
+            IdentError::Keyword(name) => write!(f, "`{}` 是关键字，可以写成 `r#{}`", name, name),
+            IdentError::RawNotAllowed(name) => write!(f, "`{}` 不能作为原始标识符", name),
+            IdentError::InvalidStart(ch) => write!(f, "标识符不能以 {:?} 开头", ch),
+            IdentError::InvalidChar { index, ch } => write!(f, "第 {} 个字符 {:?} 不能出现在标识符中", index, ch),
+        }
+    }
+}
+
+impl std::error::Error for IdentError {}
+
+/// # 校验标识符
+/// 首字符是字母或 `_`，其余是字母、数字或 `_`。
+/// 编译器用的是 Unicode 的 XID_Start / XID_Continue，这里用 `is_alphabetic`、`is_alphanumeric` 近似
+pub fn parse_ident(s: &str) -> Result<Ident<'_>, IdentError> {
+    let (name, raw) = match s.strip_prefix("r#") {
+        Some(name) => (name, true),
+        None => (s, false),
+    };
+    let mut chars = name.chars();
+    match chars.next() {
+        None => return Err(IdentError::Empty),
+        Some(c) if !c.is_alphabetic() && c != '_' => return Err(IdentError::InvalidStart(c)),
+        _ => {}
+    }
+    let offset = if raw { 2 } else { 0 };
+    if let Some((index, ch)) = chars.enumerate().find(|&(_, c)| !c.is_alphanumeric() && c != '_') {
+        return Err(IdentError::InvalidChar { index: index + 1 + offset, ch });
+    }
+    if name == "_" {
+        return Err(IdentError::Underscore);
+    }
+    match keyword_kind(name) {
+        _ if raw && NOT_RAW.contains(&name) => Err(IdentError::RawNotAllowed(name.to_string())),
+        Some(KeywordKind::Strict | KeywordKind::Reserved) if !raw => Err(IdentError::Keyword(name.to_string())),
+        _ => Ok(Ident { name, raw }),
+    }
+}
+
+/// # 关键字转义
+/// 关键字加上 `r#`，不能作为原始标识符的加上 `_` 后缀
+pub fn escape_keyword(name: &str) -> Cow<'_, str> {
+    match keyword_kind(name) {
+        _ if NOT_RAW.contains(&name) => Cow::Owned(format!("{}_", name)),
+        Some(KeywordKind::Strict | KeywordKind::Reserved) => Cow::Owned(format!("r#{}", name)),
+        _ => Cow::Borrowed(name),
+    }
+}
+
+/// # 把任意文本转换成指定风格的合法标识符
+/// 数字开头时加上 `_` 前缀；没有单词时返回 None，
+/// `Kebab`、`Title` 含有 `-` 和空格，不能作为标识符，也返回 None
+pub fn to_ident(text: &str, case: Case) -> Option<String> {
+    if matches!(case, Case::Kebab | Case::Title) {
+        return None;
+    }
+    let name = text.to_case(case);
+    let name = match name.chars().next()? {
+        c if c.is_numeric() => format!("_{}", name),
+        _ => name,
+    };
+    Some(escape_keyword(&name).into_owned())
+}
+
+/// # 拼写建议
+/// 返回 [`did_you_mean`] 找到的最接近的候选词，跳过与 `word` 完全相同的词
+pub fn suggest<'a>(word: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    did_you_mean(word, candidates.iter().copied()).into_iter().find(|c| *c != word)
+}
+
+/// # 检查标识符中每个单词的拼写
+/// 不在词表中的单词换成建议的拼写，保持原来的命名风格；没有需要修改的单词时返回 None
+pub fn fix_spelling(ident: &str, vocabulary: &[&str]) -> Option<String> {
+    let case = ident.detect_case().unwrap_or(Case::Snake);
+    let mut changed = false;
+    let words: Vec<String> = split_words(ident)
+        .into_iter()
+        .map(|word| {
+            let lower = word.to_lowercase();
+            if vocabulary.contains(&lower.as_str()) {
+                return lower;
+            }
+            match suggest(&lower, vocabulary) {
+                Some(fixed) => {
+                    changed = true;
+                    fixed.to_string()
+                }
+                None => lower,
+            }
+        })
+        .collect();
+    changed.then(|| case.join(&words))
+}
+
+fn conversion() {
+    let inputs = ["same_assoicated_function", "HTTPServer", "utf8Decoder", "parse-json-v2", "XMLHttpRequest", "中文 name"];
+    for input in inputs {
+        println!("{}", input);
+        for case in Case::ALL {
+            println!("    {} {}", pad_to_width(case.name(), 22), input.to_case(case));
+        }
+    }
+
+    assert_eq!(split_words("XMLHttpRequest"), ["XML", "Http", "Request"]);
+    assert_eq!(split_words("utf8Decoder_3d"), ["utf8", "Decoder", "3d"]);
+    assert_eq!(split_words("__IOError--x"), ["IO", "Error", "x"]);
+    assert_eq!("HTTPServer".to_case(Case::Snake), "http_server");
+    assert_eq!("HTTPServer".to_case(Case::Camel), "httpServer");
+    assert_eq!("make some noise".to_case(Case::ScreamingSnake), "MAKE_SOME_NOISE");
+    assert_eq!("object_safety".to_case(Case::Title), "Object Safety");
+    // 纯数字单词在 camelCase 中并入前一个单词，转换回来时丢失了分隔
+    assert_eq!("version_2_api".to_case(Case::Camel), "version2Api");
+    assert_eq!("version2Api".to_case(Case::Snake), "version2_api");
+}
+
+fn detection() {
+    for s in ["where_bound", "makeSomeNoise", "PokerSuit", "head-first-rust", "MAX_VALUE", "Hello World", "foo", "Mixed_Case", ""] {
+        let case = s.detect_case();
+        println!("{:<18} {}", format!("{:?}", s), case.map_or("无法识别".to_string(), |case| case.to_string()));
+    }
+    assert_eq!("PokerSuit".detect_case(), Some(Case::Pascal));
+    assert_eq!("MAX_VALUE".detect_case(), Some(Case::ScreamingSnake));
+    assert_eq!("foo".detect_case(), Some(Case::Snake));
+    assert_eq!("Mixed_Case".detect_case(), None);
+
+    // 所有风格之间互相转换，单词不变
+    let words = ["read", "http", "header", "utf8"];
+    for from in Case::ALL {
+        let s = from.join(&words);
+        assert_eq!(s.detect_case().map(|case| case.join(&words)), Some(s.clone()));
+        for to in Case::ALL {
+            assert_eq!(s.to_case(to), to.join(&words));
+        }
+    }
+}
+
+fn validation() {
+    let inputs = ["object_safetiy", "r#type", "type", "r#self", "_", "_private", "2d", "my-name", "变量", "r#", "union", "r#foo"];
+    for input in inputs {
+        match parse_ident(input) {
+            Ok(ident) => println!("{} 合法{}", pad_to_width(input, 16), if ident.raw { "，原始标识符" } else { "" }),
+            Err(e) => println!("{} {}", pad_to_width(input, 16), e),
+        }
+    }
+    assert_eq!(parse_ident("r#type"), Ok(Ident { name: "type", raw: true }));
+    assert_eq!(parse_ident("type"), Err(IdentError::Keyword("type".to_string())));
+    assert_eq!(parse_ident("r#self"), Err(IdentError::RawNotAllowed("self".to_string())));
+    assert_eq!(parse_ident("yield"), Err(IdentError::Keyword("yield".to_string())));
+    assert_eq!(parse_ident("my-name"), Err(IdentError::InvalidChar { index: 2, ch: '-' }));
+    assert_eq!(parse_ident("2d"), Err(IdentError::InvalidStart('2')));
+    assert_eq!(parse_ident("_"), Err(IdentError::Underscore));
+    assert_eq!(parse_ident("r#"), Err(IdentError::Empty));
+    assert!(parse_ident("union").is_ok());
+    assert!(parse_ident("变量").is_ok());
+    assert_eq!(parse_ident("r#foo").map(|ident| ident.to_string()), Ok("r#foo".to_string()));
+}
+
+fn escaping() {
+    let inputs = [("type", Case::Snake), ("Self", Case::Pascal), ("2nd place", Case::Snake), ("move to", Case::Camel), ("super", Case::Snake), ("!!!", Case::Snake), ("foo bar", Case::Kebab), ("foo bar", Case::Title)];
+    for (text, case) in inputs {
+        println!("{:<12} {:<12} {:?}", format!("{:?}", text), case.name(), to_ident(text, case));
+    }
+    assert_eq!(to_ident("type", Case::Snake).as_deref(), Some("r#type"));
+    assert_eq!(to_ident("Self", Case::Pascal).as_deref(), Some("Self_"));
+    assert_eq!(to_ident("2nd place", Case::Snake).as_deref(), Some("_2nd_place"));
+    assert_eq!(to_ident("!!!", Case::Snake), None);
+    assert_eq!(to_ident("foo bar", Case::Kebab), None);
+    assert_eq!(to_ident("foo bar", Case::Title), None);
+    for (text, case) in inputs {
+        if let Some(ident) = to_ident(text, case) {
+            assert!(parse_ident(&ident).is_ok(), "{}", ident);
+        }
+    }
+}
+
+fn spelling() {
+    const VOCABULARY: &[&str] = &[
+        "same", "associated", "function", "object", "safety", "make", "some", "noise", "method", "trait", "bound",
+        "where", "string", "slice", "first", "word", "loop", "while", "poker", "suit",
+    ];
+    for ident in ["same_assoicated_function", "object_safetiy", "make_some_nosie", "PokerSiut", "where_bound", "qwerty"] {
+        match fix_spelling(ident, VOCABULARY) {
+            Some(fixed) => println!("{} -> 你是不是想写 {}？", ident, fixed),
+            None => println!("{} 没有发现拼写错误", ident),
+        }
+    }
+    assert_eq!(fix_spelling("same_assoicated_function", VOCABULARY).as_deref(), Some("same_associated_function"));
+    assert_eq!(fix_spelling("object_safetiy", VOCABULARY).as_deref(), Some("object_safety"));
+    assert_eq!(fix_spelling("make_some_nosie", VOCABULARY).as_deref(), Some("make_some_noise"));
+    assert_eq!(fix_spelling("PokerSiut", VOCABULARY).as_deref(), Some("PokerSuit"));
+    assert_eq!(fix_spelling("where_bound", VOCABULARY), None);
+    assert_eq!(suggest("qwerty", VOCABULARY), None);
+}
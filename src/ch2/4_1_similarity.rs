@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+
+use crate::ch2::unicode_inspect::pad_to_width;
+use crate::rng::XorShift64;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("编辑距离", Box::new(|| distances())),
+        ("最长公共子序列", Box::new(|| common_subsequence())),
+        ("按行比较", Box::new(|| line_diff())),
+        ("快照对比", Box::new(|| snapshot())),
+        ("你是不是想找", Box::new(|| did_you_mean_demo())),
+        ("随机对比", Box::new(|| random_check())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # Levenshtein 距离
+/// 插入、删除、替换一个字符各算一次编辑，按字符而不是字节计算，只保留两行 O(min(m, n)) 的空间
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, &x) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &y) in b.iter().enumerate() {
+            let cost = usize::from(x != y);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// # 受限的 Damerau 距离（optimal string alignment）
+/// 在 Levenshtein 的基础上，相邻两个字符交换也算一次编辑，但交换过的字符不能再被编辑：
+/// `"CA"` -> `"ABC"` 是 3 而不是 2
+pub fn osa_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// # Damerau–Levenshtein 距离
+/// 允许交换之后的字符继续编辑，满足三角不等式。
+/// `last_row` 记录每个字符上一次在 a 中出现的行，用来找可以交换的位置
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let infinity = a.len() + b.len();
+    // 比普通的表多一行一列，第 0 行、第 0 列是哨兵
+    let mut d = vec![vec![0; b.len() + 2]; a.len() + 2];
+    d[0][0] = infinity;
+    for i in 0..=a.len() {
+        d[i + 1][0] = infinity;
+        d[i + 1][1] = i;
+    }
+    for j in 0..=b.len() {
+        d[0][j + 1] = infinity;
+        d[1][j + 1] = j;
+    }
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+    for i in 1..=a.len() {
+        let mut last_col = 0;
+        for j in 1..=b.len() {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_col = j;
+                0
+            } else {
+                1
+            };
+            d[i + 1][j + 1] = (d[i][j] + cost)
+                .min(d[i + 1][j] + 1)
+                .min(d[i][j + 1] + 1)
+                .min(d[k][l] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        last_row.insert(a[i - 1], i);
+    }
+    d[a.len() + 1][b.len() + 1]
+}
+
+/// # 最长公共子序列
+/// 经典的 O(mn) 动态规划，多个答案时取其中一个
+pub fn lcs<T: PartialEq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    // len[i][j]：a[i..] 和 b[j..] 的最长公共子序列长度
+    let mut len = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            len[i][j] = if a[i] == b[j] { len[i + 1][j + 1] + 1 } else { len[i + 1][j].max(len[i][j + 1]) };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::with_capacity(len[0][0]);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            result.push(a[i].clone());
+            i += 1;
+            j += 1;
+        } else if len[i + 1][j] >= len[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+pub fn lcs_str(a: &str, b: &str) -> String {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    lcs(&a, &b).into_iter().collect()
+}
+
+/// # 编辑脚本中的一步
+/// 下标指向原序列 `old` 和新序列 `new`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal { old: usize, new: usize },
+    Delete { old: usize },
+    Insert { new: usize },
+}
+
+/// # Myers 差分算法
+/// 在编辑图上按编辑次数 d 逐层搜索，每条对角线 k = x - y 只记录走得最远的 x，
+/// 找到的是最短的编辑脚本，相等的部分就是一个最长公共子序列。
+/// 时间 O((m + n)·d)，保存了每一层的记录用来回溯，空间也是 O((m + n)·d)
+pub fn myers_diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = n + m;
+    let index = |k: isize| (k + max) as usize;
+    let mut v = vec![0isize; 2 * max as usize + 2];
+    let mut trace = vec![];
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            // 从 k + 1 向下走是插入，从 k - 1 向右走是删除
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut ops = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) { k + 1 } else { k - 1 };
+        let prev_x = v[index(prev_k)];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            ops.push(DiffOp::Equal { old: x as usize, new: y as usize });
+        }
+        if d > 0 {
+            if x == prev_x {
+                ops.push(DiffOp::Insert { new: prev_y as usize });
+            } else {
+                ops.push(DiffOp::Delete { old: prev_x as usize });
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    ops.reverse();
+    ops
+}
+
+/// # 统一格式（unified diff）
+/// 和 `diff -u` 的输出格式相同，context 是每处修改前后保留的相同行数，没有差异时返回空字符串
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str, context: usize) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = myers_diff(&old_lines, &new_lines);
+
+    // 每一步之前两边已经走过的行数
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut o, mut n) = (0, 0);
+    for op in &ops {
+        positions.push((o, n));
+        match op {
+            DiffOp::Equal { .. } => (o, n) = (o + 1, n + 1),
+            DiffOp::Delete { .. } => o += 1,
+            DiffOp::Insert { .. } => n += 1,
+        }
+    }
+
+    // 修改前后各扩展 context 行，重叠或相邻的合并成一块
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for (i, op) in ops.iter().enumerate() {
+        if matches!(op, DiffOp::Equal { .. }) {
+            continue;
+        }
+        let (start, end) = (i.saturating_sub(context), (i + context + 1).min(ops.len()));
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (start, end) in hunks {
+        let ops = &ops[start..end];
+        let old_count = ops.iter().filter(|op| !matches!(op, DiffOp::Insert { .. })).count();
+        let new_count = ops.iter().filter(|op| !matches!(op, DiffOp::Delete { .. })).count();
+        // 没有行时起始行号是前一行
+        let (o, n) = positions[start];
+        let old_start = if old_count == 0 { o } else { o + 1 };
+        let new_start = if new_count == 0 { n } else { n + 1 };
+        out += &format!("@@ -{},{} +{},{} @@\n", old_start, old_count, new_start, new_count);
+        for op in ops {
+            let line = match *op {
+                DiffOp::Equal { old, .. } => format!(" {}", old_lines[old]),
+                DiffOp::Delete { old } => format!("-{}", old_lines[old]),
+                DiffOp::Insert { new } => format!("+{}", new_lines[new]),
+            };
+            out += &line;
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// # 比较多行文本
+/// 不相等时 panic，并打印统一格式的差异，比 `assert_eq!` 打印两个长字符串容易看
+#[track_caller]
+pub fn assert_text_eq(actual: &str, expected: &str) {
+    if actual != expected {
+        let diff = unified_diff(expected, actual, "expected", "actual", 3);
+        if diff.is_empty() {
+            // 只有行尾不同，按行比较看不出来
+            panic!("text differs only in line endings:\n expected: {:?}\n   actual: {:?}", expected, actual);
+        }
+        panic!("text differs:\n{}", diff);
+    }
+}
+
+/// # 你是不是想找
+/// 忽略大小写比较，返回距离不超过输入长度三分之一（至少允许 1）的候选，按距离从近到远排序
+pub fn did_you_mean<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let input = input.to_lowercase();
+    let limit = (input.chars().count() / 3).max(1);
+    let mut found: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| (damerau_levenshtein(&input, &candidate.to_lowercase()), candidate))
+        .filter(|&(distance, _)| distance <= limit)
+        .collect();
+    found.sort();
+    found.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+fn distances() {
+    let pairs = [("kitten", "sitting"), ("nosie", "noise"), ("CA", "ABC"), ("字符串", "字串"), ("🦀rust", "rust🦀"), ("", "abc")];
+    println!("{} {:>4} {:>4} {:>4}", pad_to_width("", 18), "Lev", "OSA", "DL");
+    for (a, b) in pairs {
+        let label = format!("{:?}/{:?}", a, b);
+        println!("{} {:>4} {:>4} {:>4}", pad_to_width(&label, 18), levenshtein(a, b), osa_distance(a, b), damerau_levenshtein(a, b));
+    }
+    assert_eq!(levenshtein("kitten", "sitting"), 3);
+    assert_eq!((levenshtein("nosie", "noise"), damerau_levenshtein("nosie", "noise")), (2, 1));
+    assert_eq!((osa_distance("CA", "ABC"), damerau_levenshtein("CA", "ABC")), (3, 2));
+    // 按字符计算，多字节字符也只算一次编辑
+    assert_eq!(levenshtein("字符串", "字串"), 1);
+    assert_eq!(levenshtein("🦀rust", "rust🦀"), 2);
+}
+
+fn common_subsequence() {
+    for (a, b) in [("ABCBDAB", "BDCABA"), ("associated", "assoicated"), ("生命周期", "生命的周期")] {
+        println!("{:?} 和 {:?}：{:?}", a, b, lcs_str(a, b));
+    }
+    assert_eq!(lcs_str("ABCBDAB", "BDCABA").len(), 4);
+    assert_eq!(lcs_str("生命周期", "生命的周期"), "生命周期");
+    assert_eq!(lcs(&[1, 2, 3, 4, 1], &[3, 4, 1, 2, 1]), [3, 4, 1]);
+}
+
+fn line_diff() {
+    let old = "fn main() {\n    let x = 5;\n    println!(\"{}\", x);\n}\n";
+    let new = "fn main() {\n    let mut x = 5;\n    x += 1;\n    println!(\"{}\", x);\n}\n";
+    let diff = unified_diff(old, new, "a/main.rs", "b/main.rs", 3);
+    print!("{}", diff);
+    assert_eq!(
+        diff,
+        "--- a/main.rs\n+++ b/main.rs\n@@ -1,4 +1,5 @@\n fn main() {\n-    let x = 5;\n+    let mut x = 5;\n+    x += 1;\n     println!(\"{}\", x);\n }\n"
+    );
+
+    // 相隔较远的修改分成两块，只保留前后一行上下文
+    let old: String = (1..=12).map(|i| format!("line {}\n", i)).collect();
+    let new = old.replace("line 2\n", "line two\n").replace("line 11\n", "").replace("line 12\n", "line 12\nline 13\n");
+    let diff = unified_diff(&old, &new, "old", "new", 1);
+    print!("{}", diff);
+    assert_eq!(diff.matches("@@ -").count(), 2);
+    assert!(diff.contains("@@ -1,3 +1,3 @@\n line 1\n-line 2\n+line two\n line 3\n"));
+    assert!(diff.contains("@@ -10,3 +10,3 @@\n line 10\n-line 11\n line 12\n+line 13\n"));
+    assert_eq!(unified_diff(&old, &old, "old", "new", 3), "");
+    // 从空文本开始
+    assert_eq!(unified_diff("", "a\nb\n", "old", "new", 3), "--- old\n+++ new\n@@ -0,0 +1,2 @@\n+a\n+b\n");
+}
+
+fn snapshot() {
+    // 课程输出的快照和实际输出
+    let expected = "第 1 轮\n猜一个数字\n太小了\n太大了\n猜对了！\n";
+    let actual = "第 1 轮\n猜一个数字\n太小了\n太小了\n太大了\n猜对了！\n";
+    // 暂时去掉默认的 panic 信息，只打印捕获到的内容
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = std::panic::catch_unwind(|| assert_text_eq(actual, expected));
+    std::panic::set_hook(hook);
+    let message = result.unwrap_err();
+    let message = message.downcast_ref::<String>().unwrap();
+    println!("{}", message);
+    assert!(message.contains("+太小了\n"));
+    assert_text_eq(expected, expected);
+}
+
+fn did_you_mean_demo() {
+    // 8_2_trait 中各个小节对应的函数名
+    let sections = ["trait_bound", "where_bound", "returns_summarizable", "trait_as_params", "json_traits", "notify"];
+    for input in ["wehre_bound", "Trait_Bound", "trait_params", "summary"] {
+        match did_you_mean(input, sections).as_slice() {
+            [] => println!("没有找到小节 `{}`", input),
+            [only] => println!("没有找到小节 `{}`，你是不是想找 `{}`？", input, only),
+            found => println!("没有找到小节 `{}`，你是不是想找：{:?}", input, found),
+        }
+    }
+    assert_eq!(did_you_mean("wehre_bound", sections), ["where_bound"]);
+    assert_eq!(did_you_mean("notfy", sections), ["notify"]);
+    assert_eq!(did_you_mean("Trait_Bound", sections)[0], "trait_bound");
+    assert!(did_you_mean("summary", sections).is_empty());
+}
+
+/// 和朴素实现对比：Myers 的相等部分是最长公共子序列，编辑脚本可以还原出新序列
+fn random_check() {
+    let mut rng = XorShift64::new(44);
+    let alphabet = ['a', 'b', 'c', '字', '🦀'];
+    for _ in 0..2000 {
+        let a: String = (0..rng.below(12)).map(|_| *rng.choose(&alphabet).unwrap()).collect();
+        let b: String = (0..rng.below(12)).map(|_| *rng.choose(&alphabet).unwrap()).collect();
+        let (ac, bc): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+
+        let ops = myers_diff(&ac, &bc);
+        let equal = ops.iter().filter(|op| matches!(op, DiffOp::Equal { .. })).count();
+        assert_eq!(equal, lcs(&ac, &bc).len(), "{:?} {:?}", a, b);
+        let rebuilt: String = ops
+            .iter()
+            .filter_map(|op| match *op {
+                DiffOp::Equal { new, .. } | DiffOp::Insert { new } => Some(bc[new]),
+                DiffOp::Delete { .. } => None,
+            })
+            .collect();
+        assert_eq!(rebuilt, b);
+
+        let (lev, osa, dl) = (levenshtein(&a, &b), osa_distance(&a, &b), damerau_levenshtein(&a, &b));
+        assert!(dl <= osa && osa <= lev, "{:?} {:?}", a, b);
+        assert_eq!(lev, levenshtein(&b, &a));
+        assert_eq!(dl, damerau_levenshtein(&b, &a));
+        // 只有插入和删除时，编辑次数 = m + n - 2·LCS
+        assert_eq!(ops.len() - equal, ac.len() + bc.len() - 2 * equal);
+    }
+    println!("2000 组随机字符串，各种距离和差分结果一致");
+}
@@ -0,0 +1,559 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use crate::ch2::unicode_inspect::{category, display_width, pad_to_width, CharCategory};
+use crate::rng::XorShift64;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("解析字面量", Box::new(|| parse_demo())),
+        ("错误位置", Box::new(|| error_demo())),
+        ("生成最短字面量", Box::new(|| print_demo())),
+        ("往返随机测试", Box::new(|| round_trip())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 字面量的值
+/// `"..."`、`r#"..."#` 得到字符串，`b"..."`、`br"..."` 得到字节串
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Literal {
+    Str(String),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteralErrorKind {
+    /// 缺少开头的 `"`，或者前缀不是 `b` `r` `br`
+    ExpectedQuote,
+    /// 没有找到结尾的 `"`（原始字符串还要带上同样多的 `#`）
+    Unterminated,
+    /// 字面量结束后还有其他字符
+    TrailingCharacters,
+    /// 原始字符串最多 255 个 `#`
+    TooManyHashes(usize),
+    UnknownEscape(char),
+    /// `\x` 后面不足两位十六进制数
+    HexEscapeTooShort,
+    InvalidHexDigit(char),
+    /// 字符串中的 `\x` 只能表示 ASCII，即不超过 `\x7F`
+    HexEscapeOutOfRange(u8),
+    /// `\u` 后面必须是 `{`
+    UnicodeEscapeMissingBrace,
+    UnicodeEscapeEmpty,
+    /// `\u{...}` 最多 6 位十六进制数
+    UnicodeEscapeTooLong,
+    UnicodeEscapeUnterminated,
+    /// 代理码点或超过 U+10FFFF
+    InvalidCodePoint(u32),
+    UnicodeEscapeInByteString,
+    NonAsciiInByteString(char),
+    /// 单独的 `\r`，`\r\n` 会当作换行
+    BareCarriageReturn,
+    /// 期望字符串，实际是字节串
+    UnexpectedByteString,
+}
+
+/// # 解析错误
+/// `span` 是出错部分在源码中的字节范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiteralError {
+    pub span: Range<usize>,
+    pub kind: LiteralErrorKind,
+}
+
+impl Display for LiteralError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "字节 {}..{} 处：", self.span.start, self.span.end)?;
+        match self.kind {
+            LiteralErrorKind::ExpectedQuote => write!(f, "期望 `\"`"),
+            LiteralErrorKind::Unterminated => write!(f, "字面量没有结束"),
+            LiteralErrorKind::TrailingCharacters => write!(f, "字面量之后还有多余的字符"),
+            LiteralErrorKind::TooManyHashes(n) => write!(f, "原始字符串最多 255 个 #，这里有 {} 个", n),
+            LiteralErrorKind::UnknownEscape(c) => write!(f, "未知的转义 \\{}", c.escape_default()),
+            LiteralErrorKind::HexEscapeTooShort => write!(f, "\\x 后面需要两位十六进制数"),
+            LiteralErrorKind::InvalidHexDigit(c) => write!(f, "{:?} 不是十六进制数字", c),
+            LiteralErrorKind::HexEscapeOutOfRange(b) => write!(f, "字符串中的 \\x 不能超过 7F，这里是 {:02X}", b),
+            LiteralErrorKind::UnicodeEscapeMissingBrace => write!(f, "\\u 后面需要 {{"),
+            LiteralErrorKind::UnicodeEscapeEmpty => write!(f, "\\u{{}} 中没有数字"),
+            LiteralErrorKind::UnicodeEscapeTooLong => write!(f, "\\u{{...}} 最多 6 位十六进制数"),
+            LiteralErrorKind::UnicodeEscapeUnterminated => write!(f, "\\u{{...}} 缺少 }}"),
+            LiteralErrorKind::InvalidCodePoint(code) => write!(f, "{:X} 不是合法的 Unicode 码点", code),
+            LiteralErrorKind::UnicodeEscapeInByteString => write!(f, "字节串中不能使用 \\u"),
+            LiteralErrorKind::NonAsciiInByteString(c) => write!(f, "字节串中不能出现非 ASCII 字符 {:?}", c),
+            LiteralErrorKind::BareCarriageReturn => write!(f, "不能单独出现 \\r"),
+            LiteralErrorKind::UnexpectedByteString => write!(f, "期望字符串，实际是字节串"),
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+fn error(span: Range<usize>, kind: LiteralErrorKind) -> LiteralError {
+    LiteralError { span, kind }
+}
+
+struct Cursor<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 当前字符的字节范围，已到结尾时为空范围
+    fn current_span(&self) -> Range<usize> {
+        self.pos..self.pos + self.peek().map_or(0, char::len_utf8)
+    }
+}
+
+/// # 解析 Rust 字符串字面量的源码
+/// 支持 `"..."`、`b"..."`、`r#"..."#`、`br#"..."#`，规则与编译器一致：
+/// * 转义：`\n` `\r` `\t` `\\` `\0` `\'` `\"` `\x7F` `\u{1F980}`
+/// * 行尾的 `\` 会吃掉换行和下一行开头的空白
+/// * 原始字符串不处理转义，`#` 的个数决定结尾
+pub fn parse_literal(src: &str) -> Result<Literal, LiteralError> {
+    let mut cursor = Cursor { src, pos: 0 };
+    let is_byte = cursor.eat('b');
+    let bytes = if cursor.eat('r') {
+        raw_body(&mut cursor, is_byte)?
+    } else {
+        escaped_body(&mut cursor, is_byte)?
+    };
+    if cursor.pos < src.len() {
+        return Err(error(cursor.pos..src.len(), LiteralErrorKind::TrailingCharacters));
+    }
+    if is_byte {
+        Ok(Literal::Bytes(bytes))
+    } else {
+        // 只写入过完整的字符和不超过 7F 的 \x，一定是合法的 UTF-8
+        Ok(Literal::Str(String::from_utf8(bytes).expect("string literal is valid UTF-8")))
+    }
+}
+
+pub fn parse_str_literal(src: &str) -> Result<String, LiteralError> {
+    match parse_literal(src)? {
+        Literal::Str(s) => Ok(s),
+        Literal::Bytes(_) => Err(error(0..1, LiteralErrorKind::UnexpectedByteString)),
+    }
+}
+
+/// 字节串的前缀 `b` 可以省略，`"..."` 也按字节串解析
+pub fn parse_byte_literal(src: &str) -> Result<Vec<u8>, LiteralError> {
+    if src.starts_with('b') {
+        match parse_literal(src)? {
+            Literal::Bytes(bytes) => Ok(bytes),
+            Literal::Str(s) => Ok(s.into_bytes()),
+        }
+    } else {
+        parse_str_literal(src).map(String::into_bytes)
+    }
+}
+
+fn push_char(c: char, start: usize, is_byte: bool, out: &mut Vec<u8>) -> Result<(), LiteralError> {
+    if is_byte && !c.is_ascii() {
+        return Err(error(start..start + c.len_utf8(), LiteralErrorKind::NonAsciiInByteString(c)));
+    }
+    let mut buf = [0; 4];
+    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    Ok(())
+}
+
+fn escaped_body(cursor: &mut Cursor, is_byte: bool) -> Result<Vec<u8>, LiteralError> {
+    if !cursor.eat('"') {
+        return Err(error(cursor.current_span(), LiteralErrorKind::ExpectedQuote));
+    }
+    let mut out = Vec::new();
+    loop {
+        let start = cursor.pos;
+        match cursor.bump() {
+            None => return Err(error(0..cursor.src.len(), LiteralErrorKind::Unterminated)),
+            Some('"') => return Ok(out),
+            Some('\\') => escape(cursor, start, is_byte, &mut out)?,
+            Some('\r') => {
+                if !cursor.eat('\n') {
+                    return Err(error(start..cursor.pos, LiteralErrorKind::BareCarriageReturn));
+                }
+                out.push(b'\n');
+            }
+            Some(c) => push_char(c, start, is_byte, &mut out)?,
+        }
+    }
+}
+
+/// 处理 `\` 之后的部分，`start` 是 `\` 的位置
+fn escape(cursor: &mut Cursor, start: usize, is_byte: bool, out: &mut Vec<u8>) -> Result<(), LiteralError> {
+    let c = match cursor.bump() {
+        Some(c) => c,
+        None => return Err(error(0..cursor.src.len(), LiteralErrorKind::Unterminated)),
+    };
+    match c {
+        'n' => out.push(b'\n'),
+        'r' => out.push(b'\r'),
+        't' => out.push(b'\t'),
+        '0' => out.push(0),
+        '\\' | '\'' | '"' => out.push(c as u8),
+        'x' => {
+            let mut value = 0;
+            for _ in 0..2 {
+                match cursor.peek() {
+                    Some(d) if d.is_ascii_hexdigit() => {
+                        cursor.bump();
+                        value = value * 16 + d.to_digit(16).unwrap();
+                    }
+                    Some(d) if d != '"' => {
+                        return Err(error(cursor.current_span(), LiteralErrorKind::InvalidHexDigit(d)));
+                    }
+                    _ => return Err(error(start..cursor.pos, LiteralErrorKind::HexEscapeTooShort)),
+                }
+            }
+            let value = value as u8;
+            if !is_byte && value > 0x7F {
+                return Err(error(start..cursor.pos, LiteralErrorKind::HexEscapeOutOfRange(value)));
+            }
+            out.push(value);
+        }
+        'u' => {
+            if is_byte {
+                return Err(error(start..cursor.pos, LiteralErrorKind::UnicodeEscapeInByteString));
+            }
+            let c = unicode_escape(cursor, start)?;
+            push_char(c, start, is_byte, out)?;
+        }
+        // 续行：跳过换行和下一行开头的空白
+        '\n' | '\r' => {
+            if c == '\r' && !cursor.eat('\n') {
+                return Err(error(start + 1..cursor.pos, LiteralErrorKind::BareCarriageReturn));
+            }
+            while matches!(cursor.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+                cursor.bump();
+            }
+        }
+        c => return Err(error(start..cursor.pos, LiteralErrorKind::UnknownEscape(c))),
+    }
+    Ok(())
+}
+
+/// 解析 `\u` 之后的 `{...}`，数字之间可以有下划线，例如 `\u{1F_980}`
+fn unicode_escape(cursor: &mut Cursor, start: usize) -> Result<char, LiteralError> {
+    if !cursor.eat('{') {
+        return Err(error(start..cursor.pos, LiteralErrorKind::UnicodeEscapeMissingBrace));
+    }
+    let mut value: u32 = 0;
+    let mut digits = 0;
+    loop {
+        match cursor.peek() {
+            Some('}') => {
+                cursor.bump();
+                break;
+            }
+            Some('_') if digits > 0 => {}
+            Some(d) if d.is_ascii_hexdigit() => {
+                digits += 1;
+                if digits > 6 {
+                    return Err(error(start..cursor.pos + 1, LiteralErrorKind::UnicodeEscapeTooLong));
+                }
+                value = value * 16 + d.to_digit(16).unwrap();
+            }
+            Some(d) if d != '"' => return Err(error(cursor.current_span(), LiteralErrorKind::InvalidHexDigit(d))),
+            _ => return Err(error(start..cursor.pos, LiteralErrorKind::UnicodeEscapeUnterminated)),
+        }
+        cursor.bump();
+    }
+    if digits == 0 {
+        return Err(error(start..cursor.pos, LiteralErrorKind::UnicodeEscapeEmpty));
+    }
+    char::from_u32(value).ok_or(error(start..cursor.pos, LiteralErrorKind::InvalidCodePoint(value)))
+}
+
+fn raw_body(cursor: &mut Cursor, is_byte: bool) -> Result<Vec<u8>, LiteralError> {
+    let hash_start = cursor.pos;
+    let mut hashes = 0;
+    while cursor.eat('#') {
+        hashes += 1;
+    }
+    if hashes > 255 {
+        return Err(error(hash_start..cursor.pos, LiteralErrorKind::TooManyHashes(hashes)));
+    }
+    if !cursor.eat('"') {
+        return Err(error(cursor.current_span(), LiteralErrorKind::ExpectedQuote));
+    }
+
+    let body_start = cursor.pos;
+    let closing = format!("\"{}", "#".repeat(hashes));
+    let end = match cursor.src[body_start..].find(&closing) {
+        Some(i) => body_start + i,
+        None => return Err(error(0..cursor.src.len(), LiteralErrorKind::Unterminated)),
+    };
+    let body = &cursor.src[body_start..end];
+
+    let mut out = Vec::with_capacity(body.len());
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let start = body_start + i;
+        if c == '\r' {
+            if chars.next_if(|&(_, next)| next == '\n').is_none() {
+                return Err(error(start..start + 1, LiteralErrorKind::BareCarriageReturn));
+            }
+            out.push(b'\n');
+        } else {
+            push_char(c, start, is_byte, &mut out)?;
+        }
+    }
+    cursor.pos = end + closing.len();
+    Ok(out)
+}
+
+/// 普通字面量中需要转义的字符：控制字符与零宽字符这类看不见的字符
+fn is_invisible(c: char) -> bool {
+    matches!(category(c), CharCategory::Control | CharCategory::Other)
+}
+
+/// # 生成普通字面量
+/// 只转义必要的字符，其余字符原样输出
+pub fn escaped_literal(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            c if c.is_ascii_control() => out.push_str(&format!("\\x{:02x}", c as u8)),
+            c if is_invisible(c) => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// 原始字符串需要的 `#` 个数：内容中 `"` 之后最长的连续 `#` 再多一个
+fn hashes_needed(s: &str) -> usize {
+    s.match_indices('"')
+        .map(|(i, _)| s[i + 1..].chars().take_while(|&c| c == '#').count() + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// # 生成原始字面量
+/// 内容中有看不见的字符时返回 None，原始字符串无法转义它们
+pub fn raw_literal(s: &str) -> Option<String> {
+    if s.chars().any(is_invisible) {
+        return None;
+    }
+    let hashes = "#".repeat(hashes_needed(s));
+    Some(format!("r{}\"{}\"{}", hashes, s, hashes))
+}
+
+/// # 最短的字符串字面量
+/// 原始字符串更短时（通常是包含很多 `\` 的路径、正则表达式）用原始字符串，一样长时用普通字面量
+pub fn to_literal(s: &str) -> String {
+    let escaped = escaped_literal(s);
+    match raw_literal(s) {
+        Some(raw) if raw.len() < escaped.len() => raw,
+        _ => escaped,
+    }
+}
+
+/// # 最短的字节串字面量
+/// 非打印字节写成 `\xNN`，全是可打印 ASCII 时才考虑 `br"..."`
+pub fn to_byte_literal(bytes: &[u8]) -> String {
+    let mut escaped = String::from("b\"");
+    for &b in bytes {
+        match b {
+            b'\\' => escaped.push_str("\\\\"),
+            b'"' => escaped.push_str("\\\""),
+            b'\n' => escaped.push_str("\\n"),
+            b'\r' => escaped.push_str("\\r"),
+            b'\t' => escaped.push_str("\\t"),
+            0 => escaped.push_str("\\0"),
+            0x20..=0x7E => escaped.push(b as char),
+            _ => escaped.push_str(&format!("\\x{:02x}", b)),
+        }
+    }
+    escaped.push('"');
+
+    if bytes.iter().all(|b| (0x20..=0x7E).contains(b)) {
+        // 全是可打印 ASCII，一定是合法的 UTF-8
+        let raw = format!("b{}", raw_literal(std::str::from_utf8(bytes).unwrap()).unwrap());
+        if raw.len() < escaped.len() {
+            return raw;
+        }
+    }
+    escaped
+}
+
+/// 单独的 `\r` 会让终端回到行首，换成 `␍` 显示
+fn shown(src: &str) -> String {
+    src.replace('\r', "\u{240D}")
+}
+
+/// 在源码下方用 `^` 标出出错位置
+fn underline(src: &str, span: &Range<usize>) -> String {
+    let start = display_width(&shown(&src[..span.start]));
+    let width = display_width(&shown(&src[span.clone()])).max(1);
+    format!("{}{}", " ".repeat(start), "^".repeat(width))
+}
+
+fn parse_demo() {
+    // 左边是字面量的源码，右边是编译器解析出的值，两者应该一致
+    let cases: [(&str, &str); 6] = [
+        (r#""I'm writing \x52\x75\x73\x74!""#, "I'm writing \x52\x75\x73\x74!"),
+        (r#""\u{211D} \u{1F_980}\t\'\"""#, "\u{211D} \u{1F_980}\t\'\""),
+        ("\"The linebreak ->\\\n                   <- can be escaped\"", "The linebreak ->\
+                   <- can be escaped"),
+        (r#"r"Escapes don't work here: \x3F \u{211D}""#, r"Escapes don't work here: \x3F \u{211D}"),
+        (r###"r#"And then I said: "There is no escape!""#"###, r#"And then I said: "There is no escape!""#),
+        (r####"r###"A string with "# in it. And even "##!"###"####, r###"A string with "# in it. And even "##!"###),
+    ];
+    for (src, expected) in cases {
+        let value = parse_str_literal(src).unwrap();
+        println!("{}\n  => {:?}", src, value);
+        assert_eq!(value, expected);
+    }
+
+    let bytes = parse_literal(r#"b"\xFF\x00rust\n""#).unwrap();
+    println!("{:?}", bytes);
+    assert_eq!(bytes, Literal::Bytes(b"\xFF\x00rust\n".to_vec()));
+    assert_eq!(parse_byte_literal(r#"br"\d+""#), Ok(br"\d+".to_vec()));
+    // \r\n 按换行处理，与编译器读取源文件时一致
+    assert_eq!(parse_str_literal("\"a\r\nb\""), Ok("a\nb".to_string()));
+}
+
+fn error_demo() {
+    let cases = [
+        (r#""abc"#, LiteralErrorKind::Unterminated, 0..4),
+        (r#""abc" x"#, LiteralErrorKind::TrailingCharacters, 5..7),
+        (r#""\q""#, LiteralErrorKind::UnknownEscape('q'), 1..3),
+        (r#""\x4""#, LiteralErrorKind::HexEscapeTooShort, 1..4),
+        (r#""\x4g""#, LiteralErrorKind::InvalidHexDigit('g'), 4..5),
+        (r#""\xFF""#, LiteralErrorKind::HexEscapeOutOfRange(0xFF), 1..5),
+        (r#""\u211D""#, LiteralErrorKind::UnicodeEscapeMissingBrace, 1..3),
+        (r#""\u{}""#, LiteralErrorKind::UnicodeEscapeEmpty, 1..5),
+        (r#""\u{1234567}""#, LiteralErrorKind::UnicodeEscapeTooLong, 1..11),
+        (r#""\u{12""#, LiteralErrorKind::UnicodeEscapeUnterminated, 1..6),
+        (r#""\u{D800}""#, LiteralErrorKind::InvalidCodePoint(0xD800), 1..9),
+        (r#""中文\u{11_0000}""#, LiteralErrorKind::InvalidCodePoint(0x110000), 7..18),
+        (r#"b"\u{41}""#, LiteralErrorKind::UnicodeEscapeInByteString, 2..4),
+        (r#"b"中""#, LiteralErrorKind::NonAsciiInByteString('中'), 2..5),
+        ("\"a\rb\"", LiteralErrorKind::BareCarriageReturn, 2..3),
+        (r##"r#"abc"##, LiteralErrorKind::Unterminated, 0..6),
+        (r##"r#abc"##, LiteralErrorKind::ExpectedQuote, 2..3),
+        (r#"x"abc""#, LiteralErrorKind::ExpectedQuote, 0..1),
+        (r#"b"abc""#, LiteralErrorKind::UnexpectedByteString, 0..1),
+    ];
+    for (src, kind, span) in cases {
+        let err = parse_str_literal(src).unwrap_err();
+        println!("{}", shown(src));
+        println!("{}", underline(src, &span));
+        println!("错误：{}\n", err);
+        assert_eq!(err, LiteralError { span, kind });
+    }
+
+    let too_many = format!("r{}\"\"{}", "#".repeat(256), "#".repeat(256));
+    assert_eq!(parse_str_literal(&too_many).unwrap_err().kind, LiteralErrorKind::TooManyHashes(256));
+}
+
+fn print_demo() {
+    let cases = [
+        ("hello", r#""hello""#),
+        ("a\tb\n", r#""a\tb\n""#),
+        (r"C:\Users\rust", r#"r"C:\Users\rust""#),
+        (r#"^\d+"\w*"$"#, r##"r#"^\d+"\w*"$"#"##),
+        ("\"#", r##""\"#""##),
+        ("零宽\u{200B}空格\u{7}", r#""零宽\u{200b}空格\x07""#),
+    ];
+    for (value, expected) in cases {
+        let literal = to_literal(value);
+        println!("{} {}", pad_to_width(&format!("{:?}", value), 28), literal);
+        assert_eq!(literal, expected);
+        assert_eq!(parse_str_literal(&literal).as_deref(), Ok(value));
+    }
+
+    assert_eq!(to_byte_literal(&[0, b'A', 0xFF]), r#"b"\0A\xff""#);
+    assert_eq!(to_byte_literal(br"\\server\share"), r#"br"\\server\share""#);
+    assert_eq!(hashes_needed(r###"a"#b"##c"###), 3);
+}
+
+/// 多选一些容易出错的字符：引号、`#`、`\`、控制字符、零宽字符、组合标记；
+/// `visible` 为 true 时去掉看不见的字符，让原始字符串也能被选中
+fn random_string(rng: &mut XorShift64, visible: bool) -> String {
+    const TRICKY: &[char] = &[
+        '"', '"', '#', '#', '\\', '\n', '\r', '\t', '\0', '\x07', '\x7F', '\u{85}', 'r', 'b', 'u', 'x', '{', '}', ' ',
+        '中', '🦀', '\u{200B}', '\u{301}', '\u{FEFF}',
+    ];
+    (0..rng.below(12))
+        .map(|_| {
+            if rng.below(3) > 0 {
+                *rng.choose(TRICKY).unwrap()
+            } else {
+                loop {
+                    if let Some(c) = char::from_u32(rng.below(0x110000) as u32) {
+                        break c;
+                    }
+                }
+            }
+        })
+        .filter(|&c| !visible || !is_invisible(c))
+        .collect()
+}
+
+fn round_trip() {
+    let mut rng = XorShift64::new(37);
+    let rounds = 20_000;
+    let mut raw_count = 0;
+    for _ in 0..rounds {
+        let visible = rng.below(2) == 0;
+        let s = random_string(&mut rng, visible);
+        let literal = to_literal(&s);
+        raw_count += literal.starts_with('r') as usize;
+        assert_eq!(parse_str_literal(&literal), Ok(s.clone()), "{}", literal);
+        assert!(literal.len() <= escaped_literal(&s).len());
+        // 标准库 Debug 输出的也是合法的字面量
+        assert_eq!(parse_str_literal(&format!("{:?}", s)), Ok(s.clone()));
+        if let Some(raw) = raw_literal(&s) {
+            assert_eq!(parse_str_literal(&raw), Ok(s.clone()), "{}", raw);
+        }
+
+        let bytes: Vec<u8> = (0..rng.below(10)).map(|_| rng.next_u64() as u8).collect();
+        assert_eq!(parse_byte_literal(&to_byte_literal(&bytes)), Ok(bytes.clone()));
+        assert_eq!(parse_byte_literal(&format!("b\"{}\"", bytes.escape_ascii())), Ok(bytes.clone()));
+
+        // 随机破坏字面量：不能 panic，出错时 span 必须落在字符边界上
+        let mut broken = literal.clone();
+        let pos = rng.below(broken.chars().count() as u64 + 1) as usize;
+        let byte = broken.char_indices().nth(pos).map_or(broken.len(), |(i, _)| i);
+        broken.insert_str(byte, &random_string(&mut rng, false));
+        if let Err(e) = parse_literal(&broken) {
+            assert!(e.span.start <= e.span.end && broken.get(e.span.clone()).is_some(), "{:?} {:?}", broken, e);
+        }
+    }
+    println!("{} 轮往返测试通过，其中 {} 个生成了原始字符串", rounds, raw_count);
+}
@@ -0,0 +1,564 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::rng::XorShift64;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("单模式查找", Box::new(|| single_pattern())),
+        ("重叠匹配", Box::new(|| overlapping())),
+        ("替换前 n 个", Box::new(|| replace_n())),
+        ("多模式查找", Box::new(|| multi_pattern())),
+        ("和标准库对比", Box::new(|| agree_with_std())),
+        ("性能对比", Box::new(|| benchmark())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 子串查找
+/// 所有算法都按字节比较。UTF-8 是自同步的编码，合法的模式串只会在字符边界上匹配，
+/// 所以返回的字节下标可以直接用来切片。
+///
+/// `char` 模式可以先用 `encode_utf8` 转成字符串；`char::is_numeric` 这样的闭包模式不是子串查找，这里不支持
+pub trait Searcher {
+    fn name(&self) -> &'static str;
+
+    fn needle(&self) -> &str;
+
+    /// 从字节下标 start 开始的第一个匹配
+    fn find_at(&self, haystack: &str, start: usize) -> Option<usize>;
+}
+
+/// 下一个字符边界
+fn next_boundary(s: &str, at: usize) -> usize {
+    s[at..].chars().next().map_or(at + 1, |c| at + c.len_utf8())
+}
+
+/// # 查找所有匹配
+/// * 不重叠：和 `str::match_indices` 一样，一个匹配结束后才开始找下一个
+/// * 重叠：每个字符边界都检查一次，`"aaa"` 中的 `"aa"` 匹配两次
+///
+/// 空模式和标准库一样在每个字符边界上匹配
+pub fn find_all(searcher: &impl Searcher, haystack: &str, overlapping: bool) -> Vec<usize> {
+    let len = searcher.needle().len();
+    let mut found = vec![];
+    let mut start = 0;
+    while start <= haystack.len() {
+        let Some(at) = searcher.find_at(haystack, start) else { break };
+        found.push(at);
+        start = if overlapping || len == 0 { next_boundary(haystack, at) } else { at + len };
+    }
+    found
+}
+
+/// # 替换前 n 个匹配
+/// 和 `str::replacen` 的结果相同
+pub fn replacen(searcher: &impl Searcher, haystack: &str, to: &str, n: usize) -> String {
+    let len = searcher.needle().len();
+    let mut result = String::with_capacity(haystack.len());
+    let mut last = 0;
+    for at in find_all(searcher, haystack, false).into_iter().take(n) {
+        result.push_str(&haystack[last..at]);
+        result.push_str(to);
+        last = at + len;
+    }
+    result.push_str(&haystack[last..]);
+    result
+}
+
+/// # 朴素查找
+/// 每个位置都从头比较，最坏 O(mn)，作为对比的基准
+pub struct Naive {
+    needle: String,
+}
+
+impl Naive {
+    pub fn new(needle: &str) -> Self {
+        Naive { needle: needle.to_string() }
+    }
+}
+
+impl Searcher for Naive {
+    fn name(&self) -> &'static str {
+        "朴素"
+    }
+
+    fn needle(&self) -> &str {
+        &self.needle
+    }
+
+    fn find_at(&self, haystack: &str, start: usize) -> Option<usize> {
+        let (text, pattern) = (haystack.as_bytes(), self.needle.as_bytes());
+        if start + pattern.len() > text.len() {
+            return None;
+        }
+        (start..=text.len() - pattern.len()).find(|&i| &text[i..i + pattern.len()] == pattern)
+    }
+}
+
+/// # KMP
+/// 失配时不回退文本指针，而是根据前缀函数把模式串向右滑动。
+/// `prefix[i]` 是 `needle[..=i]` 最长的、既是真前缀又是后缀的长度，预处理 O(m)，查找 O(n)
+pub struct Kmp {
+    needle: String,
+    prefix: Vec<usize>,
+}
+
+impl Kmp {
+    pub fn new(needle: &str) -> Self {
+        let pattern = needle.as_bytes();
+        let mut prefix = vec![0; pattern.len()];
+        let mut k = 0;
+        for i in 1..pattern.len() {
+            while k > 0 && pattern[i] != pattern[k] {
+                k = prefix[k - 1];
+            }
+            if pattern[i] == pattern[k] {
+                k += 1;
+            }
+            prefix[i] = k;
+        }
+        Kmp { needle: needle.to_string(), prefix }
+    }
+}
+
+impl Searcher for Kmp {
+    fn name(&self) -> &'static str {
+        "KMP"
+    }
+
+    fn needle(&self) -> &str {
+        &self.needle
+    }
+
+    fn find_at(&self, haystack: &str, start: usize) -> Option<usize> {
+        let (text, pattern) = (haystack.as_bytes(), self.needle.as_bytes());
+        if pattern.is_empty() {
+            return (start <= text.len()).then_some(start);
+        }
+        let mut k = 0;
+        for (i, &b) in text.iter().enumerate().skip(start) {
+            while k > 0 && b != pattern[k] {
+                k = self.prefix[k - 1];
+            }
+            if b == pattern[k] {
+                k += 1;
+            }
+            if k == pattern.len() {
+                return Some(i + 1 - k);
+            }
+        }
+        None
+    }
+}
+
+/// # Boyer–Moore–Horspool
+/// 从右往左比较，失配时按窗口最后一个字节在模式串中最后出现的位置跳过，
+/// 模式串越长、字母表越大跳得越远；最坏 O(mn)
+pub struct Horspool {
+    needle: String,
+    shift: [usize; 256],
+}
+
+impl Horspool {
+    pub fn new(needle: &str) -> Self {
+        let pattern = needle.as_bytes();
+        let mut shift = [pattern.len().max(1); 256];
+        for (i, &b) in pattern.iter().enumerate().take(pattern.len().saturating_sub(1)) {
+            shift[b as usize] = pattern.len() - 1 - i;
+        }
+        Horspool { needle: needle.to_string(), shift }
+    }
+}
+
+impl Searcher for Horspool {
+    fn name(&self) -> &'static str {
+        "Horspool"
+    }
+
+    fn needle(&self) -> &str {
+        &self.needle
+    }
+
+    fn find_at(&self, haystack: &str, start: usize) -> Option<usize> {
+        let (text, pattern) = (haystack.as_bytes(), self.needle.as_bytes());
+        let m = pattern.len();
+        if m == 0 {
+            return (start <= text.len()).then_some(start);
+        }
+        let mut at = start;
+        while at + m <= text.len() {
+            let window = &text[at..at + m];
+            if window.iter().rev().zip(pattern.iter().rev()).all(|(a, b)| a == b) {
+                return Some(at);
+            }
+            at += self.shift[window[m - 1] as usize];
+        }
+        None
+    }
+}
+
+/// # Two-Way（Crochemore–Perrin）
+/// 标准库 `str::find` 在长模式串上用的就是这个算法：
+/// * 预处理时找到模式串的临界分解 `needle = u v`，先从左往右比较 v，再从右往左比较 u
+/// * 按周期跳过，最坏 O(n) 时间、O(1) 额外空间
+pub struct TwoWay {
+    needle: String,
+    /// 临界位置 - 1，u 的最后一个下标，u 为空时是 -1
+    ell: isize,
+    period: usize,
+    /// 模式串是不是以 period 为周期，是的话要记住已经匹配过的前缀
+    periodic: bool,
+}
+
+impl TwoWay {
+    pub fn new(needle: &str) -> Self {
+        let pattern = needle.as_bytes();
+        let (ell1, period1) = Self::maximal_suffix(pattern, false);
+        let (ell2, period2) = Self::maximal_suffix(pattern, true);
+        let (ell, period) = if ell1 > ell2 { (ell1, period1) } else { (ell2, period2) };
+        let u_len = (ell + 1) as usize;
+        let periodic = period + u_len <= pattern.len() && pattern[..u_len] == pattern[period..period + u_len];
+        let period = if periodic { period } else { u_len.max(pattern.len() - u_len) + 1 };
+        TwoWay { needle: needle.to_string(), ell, period, periodic }
+    }
+
+    /// 按字节序（reversed 时按逆序）最大的后缀，返回 (后缀起点 - 1, 周期)
+    fn maximal_suffix(x: &[u8], reversed: bool) -> (isize, usize) {
+        let (mut ms, mut j, mut k, mut p): (isize, usize, usize, usize) = (-1, 0, 1, 1);
+        while j + k < x.len() {
+            let a = x[j + k];
+            let b = x[(ms + k as isize) as usize];
+            let less = if reversed { a > b } else { a < b };
+            if less {
+                j += k;
+                k = 1;
+                p = (j as isize - ms) as usize;
+            } else if a == b {
+                if k != p {
+                    k += 1;
+                } else {
+                    j += p;
+                    k = 1;
+                }
+            } else {
+                ms = j as isize;
+                j = ms as usize + 1;
+                k = 1;
+                p = 1;
+            }
+        }
+        (ms, p)
+    }
+}
+
+impl Searcher for TwoWay {
+    fn name(&self) -> &'static str {
+        "Two-Way"
+    }
+
+    fn needle(&self) -> &str {
+        &self.needle
+    }
+
+    fn find_at(&self, haystack: &str, start: usize) -> Option<usize> {
+        let (y, x) = (haystack.as_bytes(), self.needle.as_bytes());
+        let (n, m) = (y.len(), x.len());
+        if m == 0 {
+            return (start <= n).then_some(start);
+        }
+        let ell = self.ell;
+        let mut j = start;
+        // 周期模式串中上一次已经确认匹配的前缀的末尾
+        let mut memory: isize = -1;
+        while j + m <= n {
+            let mut i = if self.periodic { ell.max(memory) + 1 } else { ell + 1 } as usize;
+            while i < m && x[i] == y[i + j] {
+                i += 1;
+            }
+            if i < m {
+                j += i - (ell + 1) as usize + 1;
+                memory = -1;
+                continue;
+            }
+            let floor = if self.periodic { memory } else { -1 };
+            let mut i = ell;
+            while i > floor && x[i as usize] == y[i as usize + j] {
+                i -= 1;
+            }
+            if i <= floor {
+                return Some(j);
+            }
+            j += self.period;
+            if self.periodic {
+                memory = (m - self.period) as isize - 1;
+            }
+        }
+        None
+    }
+}
+
+/// 一次多模式匹配，pattern 是模式串的下标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// # Aho–Corasick
+/// 所有模式串建成一棵字典树，再用 BFS 求出失配指针，补全成 256 路的自动机：
+/// 文本只扫描一遍，O(n + 匹配个数)。空模式串被忽略
+pub struct AhoCorasick {
+    patterns: Vec<String>,
+    next: Vec<[usize; 256]>,
+    /// 到达这个状态时匹配上的所有模式串（包括沿失配指针能到达的）
+    outputs: Vec<Vec<usize>>,
+}
+
+impl AhoCorasick {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.as_ref().to_string()).collect();
+        // 0 是根，字典树中不存在的边先用 usize::MAX 表示
+        let mut next = vec![[usize::MAX; 256]];
+        let mut outputs = vec![vec![]];
+        for (index, pattern) in patterns.iter().enumerate().filter(|(_, p)| !p.is_empty()) {
+            let mut state = 0;
+            for &b in pattern.as_bytes() {
+                if next[state][b as usize] == usize::MAX {
+                    next.push([usize::MAX; 256]);
+                    outputs.push(vec![]);
+                    next[state][b as usize] = next.len() - 1;
+                }
+                state = next[state][b as usize];
+            }
+            outputs[state].push(index);
+        }
+
+        let mut fail = vec![0; next.len()];
+        let mut queue = VecDeque::new();
+        for slot in next[0].iter_mut() {
+            match *slot {
+                usize::MAX => *slot = 0,
+                child => queue.push_back(child),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            // 失配指针指向更浅的状态，它的边已经补全了
+            let fallback_row = next[fail[state]];
+            for (b, &fallback) in fallback_row.iter().enumerate() {
+                let child = next[state][b];
+                if child == usize::MAX {
+                    next[state][b] = fallback;
+                } else {
+                    fail[child] = fallback;
+                    let inherited = outputs[fallback].clone();
+                    outputs[child].extend(inherited);
+                    queue.push_back(child);
+                }
+            }
+        }
+        AhoCorasick { patterns, next, outputs }
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// 所有匹配，包括互相重叠的，按结束位置排序
+    pub fn find_overlapping(&self, haystack: &str) -> Vec<Match> {
+        let mut found = vec![];
+        let mut state = 0;
+        for (i, &b) in haystack.as_bytes().iter().enumerate() {
+            state = self.next[state][b as usize];
+            for &pattern in &self.outputs[state] {
+                let len = self.patterns[pattern].len();
+                found.push(Match { pattern, start: i + 1 - len, end: i + 1 });
+            }
+        }
+        found
+    }
+
+    /// 不重叠的匹配：从左往右，同一位置开始的取最长的（leftmost-longest）
+    pub fn find_all(&self, haystack: &str) -> Vec<Match> {
+        let mut found = self.find_overlapping(haystack);
+        found.sort_by_key(|m| (m.start, std::cmp::Reverse(m.end)));
+        let mut last_end = 0;
+        found.retain(|m| {
+            let keep = m.start >= last_end;
+            if keep {
+                last_end = m.end;
+            }
+            keep
+        });
+        found
+    }
+
+    /// 第 i 个模式串替换成 `to[i]`，最多替换 n 处
+    pub fn replacen<S: AsRef<str>>(&self, haystack: &str, to: &[S], n: usize) -> String {
+        assert_eq!(to.len(), self.patterns.len(), "one replacement per pattern");
+        let mut result = String::with_capacity(haystack.len());
+        let mut last = 0;
+        for m in self.find_all(haystack).into_iter().take(n) {
+            result.push_str(&haystack[last..m.start]);
+            result.push_str(to[m.pattern].as_ref());
+            last = m.end;
+        }
+        result.push_str(&haystack[last..]);
+        result
+    }
+}
+
+fn searchers(needle: &str) -> Vec<Box<dyn Searcher>> {
+    vec![
+        Box::new(Naive::new(needle)),
+        Box::new(Kmp::new(needle)),
+        Box::new(Horspool::new(needle)),
+        Box::new(TwoWay::new(needle)),
+    ]
+}
+
+/// 让 `Box<dyn Searcher>` 也能传给 `find_all`、`replacen` 这些泛型函数
+impl Searcher for Box<dyn Searcher> {
+    fn name(&self) -> &'static str {
+        self.as_ref().name()
+    }
+
+    fn needle(&self) -> &str {
+        self.as_ref().needle()
+    }
+
+    fn find_at(&self, haystack: &str, start: usize) -> Option<usize> {
+        self.as_ref().find_at(haystack, start)
+    }
+}
+
+fn single_pattern() {
+    let haystack = "hello rust，你好 rust，rust 很安全";
+    for searcher in searchers("rust") {
+        let found = find_all(&searcher, haystack, false);
+        println!("{:<10} {:?}", searcher.name(), found);
+        assert_eq!(found, [6, 20, 27]);
+    }
+    for searcher in searchers("你好") {
+        let at = searcher.find_at(haystack, 0).unwrap();
+        assert_eq!(&haystack[at..], "你好 rust，rust 很安全");
+    }
+    // char 模式先转成字符串
+    let needle = '，'.encode_utf8(&mut [0; 4]).to_string();
+    assert_eq!(find_all(&Kmp::new(&needle), haystack, false), haystack.match_indices('，').map(|(i, _)| i).collect::<Vec<_>>());
+}
+
+fn overlapping() {
+    let haystack = "aaaa 哈哈哈哈";
+    for (needle, overlapping) in [("aa", false), ("aa", true), ("哈哈", false), ("哈哈", true), ("", false)] {
+        let found = find_all(&TwoWay::new(needle), haystack, overlapping);
+        println!("{:?} 重叠：{:<5} {:?}", needle, overlapping, found);
+    }
+    assert_eq!(find_all(&Kmp::new("aa"), haystack, true), [0, 1, 2]);
+    assert_eq!(find_all(&Horspool::new("哈哈"), haystack, true), [5, 8, 11]);
+    assert_eq!(find_all(&Horspool::new("哈哈"), haystack, false), [5, 11]);
+    assert_eq!(find_all(&Naive::new(""), "a哈", false), [0, 1, 4]);
+}
+
+fn replace_n() {
+    // 和 string_operating 中的例子相同
+    let s = "foo foo 123 foo";
+    for searcher in searchers("foo") {
+        assert_eq!(replacen(&searcher, s, "new", 2), "new new 123 foo");
+    }
+    assert_eq!(replacen(&Kmp::new("o"), s, "a", 3), s.replacen('o', "a", 3));
+    assert_eq!(replacen(&TwoWay::new("bar"), s, "foo_bar", 1), s);
+    assert_eq!(replacen(&Horspool::new(""), "abc", "-", 2), "abc".replacen("", "-", 2));
+    println!("{}", replacen(&TwoWay::new("foo"), s, "新", usize::MAX));
+}
+
+fn multi_pattern() {
+    let patterns = ["he", "she", "his", "hers", "生命", "生命周期"];
+    let ac = AhoCorasick::new(&patterns);
+    let haystack = "ushers 的生命周期";
+    for m in ac.find_overlapping(haystack) {
+        println!("{:>2}..{:<2} {}", m.start, m.end, ac.patterns()[m.pattern]);
+    }
+    let all: Vec<&str> = ac.find_all(haystack).iter().map(|m| &haystack[m.start..m.end]).collect();
+    println!("不重叠：{:?}", all);
+    assert_eq!(all, ["she", "生命周期"]);
+    assert_eq!(ac.find_overlapping(haystack).len(), 5);
+
+    let replaced = ac.replacen("she sells his hers", &["HE", "SHE", "HIS", "HERS", "", ""], usize::MAX);
+    println!("{}", replaced);
+    assert_eq!(replaced, "SHE sells HIS HERS");
+}
+
+/// 随机文本上和标准库以及逐个位置的暴力检查对比
+fn agree_with_std() {
+    let mut rng = XorShift64::new(45);
+    let alphabet = ["a", "b", "ab", "字", "🦀"];
+    let random = |rng: &mut XorShift64, max: u64| -> String {
+        (0..rng.below(max)).map(|_| *rng.choose(&alphabet).unwrap()).collect()
+    };
+    for _ in 0..3000 {
+        let haystack = random(&mut rng, 40);
+        let needle = random(&mut rng, 4);
+        let expected: Vec<usize> = haystack.match_indices(needle.as_str()).map(|(i, _)| i).collect();
+        let brute: Vec<usize> = (0..=haystack.len())
+            .filter(|&i| haystack.is_char_boundary(i) && haystack[i..].starts_with(needle.as_str()))
+            .collect();
+        let n = rng.below(4) as usize;
+        for searcher in searchers(&needle) {
+            assert_eq!(searcher.find_at(&haystack, 0), haystack.find(needle.as_str()), "{} {:?} {:?}", searcher.name(), haystack, needle);
+            assert_eq!(find_all(&searcher, &haystack, false), expected, "{} {:?} {:?}", searcher.name(), haystack, needle);
+            assert_eq!(find_all(&searcher, &haystack, true), brute, "{} {:?} {:?}", searcher.name(), haystack, needle);
+            assert_eq!(replacen(&searcher, &haystack, "_", n), haystack.replacen(needle.as_str(), "_", n));
+        }
+
+        let patterns: Vec<String> = (0..3).map(|_| random(&mut rng, 3)).collect();
+        let ac = AhoCorasick::new(&patterns);
+        let mut expected: Vec<Match> = vec![];
+        for (pattern, p) in patterns.iter().enumerate().filter(|(_, p)| !p.is_empty()) {
+            for start in find_all(&Kmp::new(p), &haystack, true) {
+                expected.push(Match { pattern, start, end: start + p.len() });
+            }
+        }
+        let mut found = ac.find_overlapping(&haystack);
+        found.sort_by_key(|m| (m.start, m.pattern));
+        expected.sort_by_key(|m| (m.start, m.pattern));
+        assert_eq!(found, expected, "{:?} {:?}", haystack, patterns);
+    }
+    println!("3000 组随机文本，四种单模式算法、Aho–Corasick 和标准库结果一致");
+}
+
+fn benchmark() {
+    let mut rng = XorShift64::new(7);
+    let words = ["rust", "安全", "ownership", "borrow", "生命周期", "trait", "a", "aaab"];
+    let mut haystack = String::new();
+    while haystack.len() < 512 * 1024 {
+        haystack.push_str(rng.choose(&words).unwrap());
+        haystack.push(' ');
+    }
+    // 最坏情况：大量部分匹配
+    let worst = "a".repeat(256 * 1024) + "b";
+    let cases = [
+        ("短模式", haystack.as_str(), "trait".to_string()),
+        ("长模式", haystack.as_str(), "ownership borrow 生命周期 rust 安全".to_string()),
+        ("周期性", worst.as_str(), "a".repeat(64) + "b"),
+    ];
+    println!("文本 {} KB（debug 模式下差距会被放大）", haystack.len() / 1024);
+    for (label, text, needle) in cases {
+        let start = Instant::now();
+        let expected = text.matches(needle.as_str()).count();
+        println!("{} {:<10} {:>10.2?}  {} 处", label, "str::find", start.elapsed(), expected);
+        for searcher in searchers(&needle) {
+            let start = Instant::now();
+            let count = find_all(&searcher, text, false).len();
+            println!("{} {:<10} {:>10.2?}", label, searcher.name(), start.elapsed());
+            assert_eq!(count, expected);
+        }
+    }
+}
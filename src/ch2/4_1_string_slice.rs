@@ -1,34 +1,13 @@
 #![allow(unused_variables)]
 
 
-use crate::{print_size_of_char, print_size_of_val};
-
-/// * 从代码设计角度来看，关于文件操作的类型和函数应该组织在一起，
-/// * 散落得到处都是，是难以管理和使用的。
-///
-/// * 而且通过 open(&mut f1) 进行调用，
-/// * 也远没有使用 f1.open() 来调用好，
-/// * 这就体现出了只使用基本类型的局限性：无法从更高的抽象层次去简化代码。
-type File = String;
-
-fn open(f: &mut File) -> bool {
-    true
-}
-
-fn close(f: &mut File) -> bool {
-    true
-}
+use std::io::Read;
 
-/// # 发散函数
-/// * unimplemented!() 告诉编译器该函数尚未实现，
-/// * unimplemented!() 标记通常意味着我们期望快速完成主要代码，
-/// * 回头再通过搜索这些标记来完成次要代码，类似的标记还有 todo!()，
-/// * 当代码执行到这种未实现的地方时，程序会直接报错。
-/// * 你可以反注释 read(&mut f1, &mut vec![]); 这行，然后再观察下结果。
-#[allow(dead_code)]
-fn read(f: &mut File, save_to: &mut Vec<u8>) -> ! {
-    unimplemented!()
-}
+use crate::{print_size_of_char, print_size_of_val};
+use crate::ch2::char_index::{CharEditExt, CharIndexExt};
+use crate::ch2::string_literal;
+use crate::ch2::unicode_inspect::Inspector;
+use crate::ch2::vfs::{OpenOptions, Vfs};
 
 pub fn main() {
     let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
@@ -53,11 +32,27 @@ pub fn main() {
 }
 
 
+/// * 从代码设计角度来看，关于文件操作的类型和函数应该组织在一起，
+/// * 散落得到处都是，是难以管理和使用的。
+///
+/// * 而且通过 open(&mut f1) 进行调用，
+/// * 也远没有使用 f1.open() 来调用好，
+/// * 所以这里用 vfs 模块中的 Vfs、File 类型，文件相关的操作都是它们的方法。
 fn basic() {
-    let mut f = File::from("file1.txt");
-    open(&mut f);
-    // read(&mut f, &mut vec![]);
-    close(&mut f);
+    let mut fs = Vfs::new();
+    fs.write("/file1.txt", "hello, rust").unwrap();
+
+    let mut f = fs.open("/file1.txt", &OpenOptions::new().read(true)).unwrap();
+    let mut content = String::new();
+    f.read_to_string(&mut content).unwrap();
+    println!("{}: {}", f.path(), content);
+    f.close().unwrap();
+
+    // 关闭之后再读会返回错误
+    match f.read_to_string(&mut content) {
+        Ok(_) => println!("读取成功"),
+        Err(e) => println!("读取失败：{}", e),
+    }
 }
 
 fn slice() {
@@ -88,8 +83,10 @@ fn slice() {
     println!("all:{},first_word:{}", all, f); //不可变引用
 }
 
+/// 第一个单词：到第一个非字母数字的字符为止
 fn first_world(s: &String) -> &str {
-    &s[..1]
+    let end = s.chars().position(|c| !c.is_alphanumeric()).unwrap_or(s.chars().count());
+    s.char_slice(..end).unwrap_or_default()
 }
 
 /// # 字符串基础
@@ -152,8 +149,10 @@ fn string_operating() {
     s1.insert(5, '你');
     println!("{}", s1);
 
-    s1.insert_str(8, "好啊"); //和字符串切片一样的问题，utf-8变长编码，位置不一定对
+    // s1.insert_str(7, "好啊"); //和字符串切片一样的问题，utf-8变长编码，字节 7 在 '你' 中间会 panic
+    s1.insert_at_char(6, "好啊").unwrap(); //按字符下标插入，越界返回 Err
     println!("{}", s1);
+    assert!(s1.insert_at_char(100, "!").is_err());
 
     /*
     替换 Replace
@@ -173,8 +172,11 @@ fn string_operating() {
 
     //修改字符串
     let mut s3 = String::from("I like rust!");
-    s3.replace_range(7..8, "R");
-    dbg!(s3);
+    s3.replace_range(7..8, "R"); //字节下标
+    dbg!(&s3);
+    let mut s3_cn = String::from("我喜欢rust!");
+    s3_cn.replace_char_range(3..4, "R").unwrap(); //字符下标，s3_cn.replace_range(3..4, "R") 会 panic
+    dbg!(s3_cn);
 
     /*
     删除 Delete
@@ -191,13 +193,18 @@ fn string_operating() {
     let mut s5 = String::from("测试remove方法");
     s5.remove(0);
     // s5.remove(1); //byte index 1 is not a char boundary 老问题了，字符串切片 utf-8编码
+    let removed = s5.remove_char_range(1..7).unwrap(); //按字符下标删除
+    dbg!(removed);
     dbg!(s5);
 
     // truncate
     let mut s6 = String::from("测试truncate");
     // s6.truncate(2); //assertion failed: self.is_char_boundary(new_len) 老问题了，字符串切片 utf-8编码
     s6.truncate(3);
-    dbg!(s6);
+    dbg!(&s6);
+    let mut s6_chars = String::from("测试truncate");
+    s6_chars.truncate_chars(2).unwrap(); //保留前 2 个字符
+    dbg!(s6_chars);
 
     // clear
     let mut s7 = String::from("测试clear"); //6+5=11
@@ -246,6 +253,9 @@ fn string_escape() {
                         The linebreak and indentation here ->\
                         <- can be escaped too!";
     println!("{}", long_string);
+    // 反过来，由字符串的值生成字面量源码
+    println!("{}", string_literal::to_literal(long_string));
+    println!("{}", string_literal::to_literal(r"C:\Users\rust"));
 }
 
 fn string_unescape() {
@@ -277,4 +287,7 @@ fn string_utf8() {
     let complex_string = String::from("holla中国人नमस्ते");
     let x1 = complex_string.chars().nth(7).unwrap();
     println!("{}", x1);
+
+    // 字符下标 7 是 '人'（字节 11），而字节下标 7 落在 '中'（字节 5..8）内部
+    print!("{}", Inspector::new(&complex_string));
 }
\ No newline at end of file
@@ -0,0 +1,313 @@
+use std::fmt::{Display, Formatter};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("逐字符分析", Box::new(|| inspect_table())),
+        ("字符下标与字节下标", Box::new(|| char_vs_byte_index())),
+        ("显示宽度", Box::new(|| display_width_demo())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 字符大类
+/// 不依赖外部 crate，按常见的 Unicode 区段粗略分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharCategory {
+    Letter,
+    Digit,
+    Whitespace,
+    Punctuation,
+    Symbol,
+    /// 组合标记，例如天城文的元音符号 `ा`，本身不占宽度
+    Mark,
+    Emoji,
+    /// 中日韩统一表意文字
+    Cjk,
+    Control,
+    /// 零宽字符、私有区等
+    Other,
+}
+
+impl Display for CharCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CharCategory::Letter => "letter",
+            CharCategory::Digit => "digit",
+            CharCategory::Whitespace => "space",
+            CharCategory::Punctuation => "punct",
+            CharCategory::Symbol => "symbol",
+            CharCategory::Mark => "mark",
+            CharCategory::Emoji => "emoji",
+            CharCategory::Cjk => "CJK",
+            CharCategory::Control => "control",
+            CharCategory::Other => "other",
+        };
+        // 直接写入 f，保留调用方的对齐、宽度设置
+        f.pad(name)
+    }
+}
+
+fn in_ranges(c: char, ranges: &[(u32, u32)]) -> bool {
+    let code = c as u32;
+    ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&code))
+}
+
+const CJK_IDEOGRAPHS: &[(u32, u32)] = &[
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xF900, 0xFAFF),
+    (0x20000, 0x2FA1F),
+    (0x30000, 0x3134F),
+];
+
+const EMOJI: &[(u32, u32)] = &[
+    (0x1F000, 0x1F2FF),
+    (0x1F300, 0x1F5FF),
+    (0x1F600, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F900, 0x1FAFF),
+    (0x2600, 0x27BF),
+];
+
+const COMBINING_MARKS: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0900, 0x0903),
+    (0x093A, 0x094F),
+    (0x0951, 0x0957),
+    (0x0962, 0x0963),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x20D0, 0x20FF),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+];
+
+const ZERO_WIDTH: &[(u32, u32)] = &[(0x200B, 0x200F), (0x2060, 0x2064), (0xFEFF, 0xFEFF)];
+
+/// 终端中占两列的区段：东亚全角字符与 emoji
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE30, 0xFE4F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x1F300, 0x1F64F),
+    (0x1F900, 0x1F9FF),
+    (0x20000, 0x3FFFD),
+];
+
+/// CJK 标点与全角标点，例如 `，` `。` `！`
+const WIDE_PUNCTUATION: &[(u32, u32)] = &[(0x3000, 0x303F), (0xFF01, 0xFF0F), (0xFF1A, 0xFF20), (0xFF3B, 0xFF40), (0xFF5B, 0xFF65)];
+
+pub fn category(c: char) -> CharCategory {
+    if c.is_control() {
+        CharCategory::Control
+    } else if in_ranges(c, COMBINING_MARKS) {
+        CharCategory::Mark
+    } else if in_ranges(c, ZERO_WIDTH) {
+        CharCategory::Other
+    } else if c.is_whitespace() {
+        CharCategory::Whitespace
+    } else if in_ranges(c, CJK_IDEOGRAPHS) {
+        CharCategory::Cjk
+    } else if in_ranges(c, EMOJI) {
+        CharCategory::Emoji
+    } else if c.is_numeric() {
+        CharCategory::Digit
+    } else if c.is_alphabetic() {
+        CharCategory::Letter
+    } else if c.is_ascii_punctuation() || in_ranges(c, WIDE_PUNCTUATION) || in_ranges(c, &[(0x2010, 0x205E)]) {
+        // ASCII 中 $ + < = > ^ ` | ~ 算作符号
+        if "$+<=>^`|~".contains(c) {
+            CharCategory::Symbol
+        } else {
+            CharCategory::Punctuation
+        }
+    } else if in_ranges(c, &[(0x00A2, 0x00BF), (0x2100, 0x2BFF)]) {
+        CharCategory::Symbol
+    } else {
+        CharCategory::Other
+    }
+}
+
+/// 字符在等宽终端中占的列数：组合标记、零宽字符为 0，全角字符为 2
+pub fn char_width(c: char) -> usize {
+    match category(c) {
+        CharCategory::Mark | CharCategory::Control => 0,
+        CharCategory::Other if in_ranges(c, ZERO_WIDTH) => 0,
+        _ if in_ranges(c, WIDE) => 2,
+        _ => 1,
+    }
+}
+
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// 按显示宽度右侧补空格，`format!("{:<8}")` 按字符个数补齐，遇到中文会错位
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(s));
+    format!("{}{}", s, " ".repeat(padding))
+}
+
+/// # 单个字符的信息
+#[derive(Debug, Clone)]
+pub struct CharInfo {
+    pub char_index: usize,
+    pub byte_offset: usize,
+    pub ch: char,
+    pub category: CharCategory,
+    pub width: usize,
+}
+
+impl CharInfo {
+    pub fn code_point(&self) -> String {
+        format!("U+{:04X}", self.ch as u32)
+    }
+
+    pub fn utf8(&self) -> Vec<u8> {
+        let mut buf = [0; 4];
+        self.ch.encode_utf8(&mut buf).as_bytes().to_vec()
+    }
+
+    pub fn utf16(&self) -> Vec<u16> {
+        let mut buf = [0; 2];
+        self.ch.encode_utf16(&mut buf).to_vec()
+    }
+
+    /// 组合标记前加虚线圆圈 `◌` 才能单独显示，控制字符显示转义形式
+    fn printable(&self) -> String {
+        match self.category {
+            CharCategory::Mark => format!("\u{25CC}{}", self.ch),
+            CharCategory::Control | CharCategory::Other => self.ch.escape_unicode().to_string(),
+            _ => self.ch.to_string(),
+        }
+    }
+}
+
+pub fn inspect(s: &str) -> Vec<CharInfo> {
+    s.char_indices()
+        .enumerate()
+        .map(|(char_index, (byte_offset, ch))| CharInfo {
+            char_index,
+            byte_offset,
+            ch,
+            category: category(ch),
+            width: char_width(ch),
+        })
+        .collect()
+}
+
+/// # 字符串分析表
+/// 用 `{}` 打印时输出每个字符一行的表格
+pub struct Inspector<'a> {
+    text: &'a str,
+}
+
+impl<'a> Inspector<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+}
+
+impl Display for Inspector<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "\"{}\"：{} 字节，{} 个字符，{} 个 UTF-16 单元，显示宽度 {}",
+            self.text,
+            self.text.len(),
+            self.text.chars().count(),
+            self.text.encode_utf16().count(),
+            display_width(self.text)
+        )?;
+        let header = [("字符", 6), ("字节", 6), ("", 10), ("码点", 9), ("UTF-8", 12), ("UTF-16", 10), ("分类", 8)];
+        for (title, width) in header {
+            write!(f, "{} ", pad_to_width(title, width))?;
+        }
+        writeln!(f, "宽度")?;
+        for info in inspect(self.text) {
+            let utf8: Vec<String> = info.utf8().iter().map(|b| format!("{:02X}", b)).collect();
+            let utf16: Vec<String> = info.utf16().iter().map(|u| format!("{:04X}", u)).collect();
+            writeln!(
+                f,
+                "{:>6} {:>6} {} {:<9} {:<12} {:<10} {:<8} {}",
+                info.char_index,
+                info.byte_offset,
+                pad_to_width(&info.printable(), 10),
+                info.code_point(),
+                utf8.join(" "),
+                utf16.join(" "),
+                info.category,
+                info.width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn inspect_table() {
+    print!("{}", Inspector::new("holla中国人नमस्ते"));
+    println!();
+    print!("{}", Inspector::new("Rust！🦀 é\u{200B}x"));
+
+    let info = inspect("a中🦀");
+    assert_eq!(info[1].utf8(), vec![0xE4, 0xB8, 0xAD]);
+    assert_eq!(info[2].utf16(), vec![0xD83E, 0xDD80]); // 超出 BMP 的字符需要代理对
+    assert_eq!(info[2].byte_offset, 4);
+    assert_eq!(category('中'), CharCategory::Cjk);
+    assert_eq!(category('！'), CharCategory::Punctuation);
+    assert_eq!(category('\u{093E}'), CharCategory::Mark);
+}
+
+/// `.chars().nth(7)` 数的是字符，`[7..]` 数的是字节
+fn char_vs_byte_index() {
+    let s = "holla中国人नमस्ते";
+    let info = inspect(s);
+    let seventh = &info[7];
+    println!("第 7 个字符：{:?}，位于字节 {}", seventh.ch, seventh.byte_offset);
+    assert_eq!(s.chars().nth(7), Some(seventh.ch));
+    assert_eq!(seventh.ch, '人');
+
+    // 字节 7 落在 '中'（字节 5..8）内部，不是字符边界
+    let containing = info.iter().rev().find(|i| i.byte_offset <= 7).unwrap();
+    println!(
+        "字节 7 落在 {:?}（字节 {}..{}）内部，is_char_boundary(7) = {}",
+        containing.ch,
+        containing.byte_offset,
+        containing.byte_offset + containing.ch.len_utf8(),
+        s.is_char_boundary(7)
+    );
+    assert!(!s.is_char_boundary(7));
+}
+
+fn display_width_demo() {
+    let rows = [("名称", "Rust"), ("作者", "Zhong"), ("emoji", "🦀🦀"), ("नमस्ते", "天城文")];
+    println!("按字符个数对齐：");
+    for (k, v) in rows {
+        println!("|{:<8}|{:<8}|", k, v);
+    }
+    println!("按显示宽度对齐：");
+    for (k, v) in rows {
+        println!("|{}|{}|", pad_to_width(k, 8), pad_to_width(v, 8));
+    }
+    assert_eq!(display_width("中国人"), 6);
+    assert_eq!(display_width("नमस्ते"), 4);
+    assert_eq!(display_width("e\u{301}"), 1);
+}
@@ -0,0 +1,378 @@
+use std::fmt::{Display, Formatter};
+use crate::rng::XorShift64;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("UTF-8 编码", Box::new(|| encode_demo())),
+        ("UTF-8 解码与错误", Box::new(|| decode_errors())),
+        ("有损解码", Box::new(|| lossy_demo())),
+        ("UTF-16 与代理对", Box::new(|| utf16_demo())),
+        ("与标准库随机对比", Box::new(|| cross_check())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// U+FFFD，解码失败时的替换字符 �
+pub const REPLACEMENT: char = '\u{FFFD}';
+
+/// # UTF-8 编码规则
+/// | 码点范围             | 字节数 | 格式                                |
+/// |---------------------|-------|-------------------------------------|
+/// | U+0000 ~ U+007F     | 1     | 0xxxxxxx                            |
+/// | U+0080 ~ U+07FF     | 2     | 110xxxxx 10xxxxxx                   |
+/// | U+0800 ~ U+FFFF     | 3     | 1110xxxx 10xxxxxx 10xxxxxx          |
+/// | U+10000 ~ U+10FFFF  | 4     | 11110xxx 10xxxxxx 10xxxxxx 10xxxxxx |
+///
+/// 返回缓冲区和实际使用的字节数，"每个汉字3个字节"就是第三行
+pub fn encode_char(c: char) -> ([u8; 4], usize) {
+    let code = c as u32;
+    let cont = |shift: u32| 0x80 | ((code >> shift) & 0x3F) as u8;
+    match code {
+        0..=0x7F => ([code as u8, 0, 0, 0], 1),
+        0x80..=0x7FF => ([0xC0 | (code >> 6) as u8, cont(0), 0, 0], 2),
+        0x800..=0xFFFF => ([0xE0 | (code >> 12) as u8, cont(6), cont(0), 0], 3),
+        _ => ([0xF0 | (code >> 18) as u8, cont(12), cont(6), cont(0)], 4),
+    }
+}
+
+pub fn encode(s: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        let (buf, len) = encode_char(c);
+        out.extend_from_slice(&buf[..len]);
+    }
+    out
+}
+
+/// # 解码错误的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf8ErrorKind {
+    /// 首字节不可能出现在开头：单独的续字节 10xxxxxx，或 0xF8 以上
+    InvalidLeadByte(u8),
+    /// 应该是续字节 10xxxxxx 的位置出现了其他字节
+    InvalidContinuation(u8),
+    /// 用了比必要更多的字节，例如 `C0 AF` 表示 '/'
+    Overlong,
+    /// U+D800 ~ U+DFFF 是 UTF-16 的代理码点，不能出现在 UTF-8 中
+    Surrogate,
+    /// 超过 U+10FFFF
+    OutOfRange,
+    /// 输入在多字节序列中间结束
+    Truncated { expected: usize, found: usize },
+}
+
+/// # 解码错误
+/// 字段含义与 `std::str::Utf8Error` 一致，方便对比：
+/// * valid_up_to：前面这么多字节是合法的
+/// * error_len：非法序列的长度，输入意外结束时为 None
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8Error {
+    pub valid_up_to: usize,
+    pub error_len: Option<usize>,
+    pub kind: Utf8ErrorKind,
+}
+
+impl Display for Utf8Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "字节 {} 处：", self.valid_up_to)?;
+        match self.kind {
+            Utf8ErrorKind::InvalidLeadByte(b) => write!(f, "非法的首字节 {:#04X}", b),
+            Utf8ErrorKind::InvalidContinuation(b) => write!(f, "期望续字节 10xxxxxx，实际是 {:#04X}", b),
+            Utf8ErrorKind::Overlong => write!(f, "超长编码"),
+            Utf8ErrorKind::Surrogate => write!(f, "代理码点不能用 UTF-8 编码"),
+            Utf8ErrorKind::OutOfRange => write!(f, "码点超过 U+10FFFF"),
+            Utf8ErrorKind::Truncated { expected, found } => write!(f, "序列需要 {} 字节，只剩 {} 字节", expected, found),
+        }
+    }
+}
+
+/// 从 `bytes[start..]` 解码一个字符，失败时返回 `(error_len, kind)`
+///
+/// 第二个字节的合法范围与首字节有关（Unicode 标准表 3-7），
+/// 在第二个字节就能排除超长编码、代理码点和超范围的码点：
+/// * E0 后只能是 A0..BF，否则是超长编码
+/// * ED 后只能是 80..9F，否则是代理码点
+/// * F0 后只能是 90..BF，否则是超长编码
+/// * F4 后只能是 80..8F，否则超过 U+10FFFF
+fn decode_one(bytes: &[u8], start: usize) -> Result<(char, usize), (Option<usize>, Utf8ErrorKind)> {
+    let lead = bytes[start];
+    let (len, second) = match lead {
+        0x00..=0x7F => return Ok((lead as char, 1)),
+        0x80..=0xBF => return Err((Some(1), Utf8ErrorKind::InvalidLeadByte(lead))),
+        0xC0 | 0xC1 => return Err((Some(1), Utf8ErrorKind::Overlong)),
+        0xC2..=0xDF => (2, 0x80..=0xBF),
+        0xE0 => (3, 0xA0..=0xBF),
+        0xED => (3, 0x80..=0x9F),
+        0xE1..=0xEF => (3, 0x80..=0xBF),
+        0xF0 => (4, 0x90..=0xBF),
+        0xF4 => (4, 0x80..=0x8F),
+        0xF1..=0xF3 => (4, 0x80..=0xBF),
+        0xF5..=0xF7 => return Err((Some(1), Utf8ErrorKind::OutOfRange)),
+        0xF8..=0xFF => return Err((Some(1), Utf8ErrorKind::InvalidLeadByte(lead))),
+    };
+
+    let mut code = (lead as u32) & (0x7F >> len);
+    for i in 1..len {
+        let Some(&b) = bytes.get(start + i) else {
+            return Err((None, Utf8ErrorKind::Truncated { expected: len, found: i }));
+        };
+        if i == 1 && !second.contains(&b) {
+            let kind = match (lead, b) {
+                (_, b) if b & 0xC0 != 0x80 => Utf8ErrorKind::InvalidContinuation(b),
+                (0xE0, _) | (0xF0, _) => Utf8ErrorKind::Overlong,
+                (0xED, _) => Utf8ErrorKind::Surrogate,
+                _ => Utf8ErrorKind::OutOfRange,
+            };
+            return Err((Some(1), kind));
+        }
+        if b & 0xC0 != 0x80 {
+            return Err((Some(i), Utf8ErrorKind::InvalidContinuation(b)));
+        }
+        code = code << 6 | (b & 0x3F) as u32;
+    }
+    // 上面已经排除了所有非法码点
+    let c = char::from_u32(code).expect("validated code point");
+    Ok((c, len))
+}
+
+pub fn decode(bytes: &[u8]) -> Result<String, Utf8Error> {
+    let mut out = String::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match decode_one(bytes, i) {
+            Ok((c, len)) => {
+                out.push(c);
+                i += len;
+            }
+            Err((error_len, kind)) => return Err(Utf8Error { valid_up_to: i, error_len, kind }),
+        }
+    }
+    Ok(out)
+}
+
+/// # 有损解码
+/// 每个非法序列（最长的合法前缀）替换成一个 U+FFFD，与 `String::from_utf8_lossy` 一致
+pub fn decode_lossy(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match decode_one(bytes, i) {
+            Ok((c, len)) => {
+                out.push(c);
+                i += len;
+            }
+            Err((Some(len), _)) => {
+                out.push(REPLACEMENT);
+                i += len;
+            }
+            Err((None, _)) => {
+                out.push(REPLACEMENT);
+                break;
+            }
+        }
+    }
+    out
+}
+
+/// # UTF-16 编码
+/// * BMP 内的字符：一个 16 位单元
+/// * U+10000 以上：减去 0x10000 后剩 20 位，高 10 位加 0xD800，低 10 位加 0xDC00，组成代理对
+pub fn encode_utf16(s: &str) -> Vec<u16> {
+    let mut out = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        let code = c as u32;
+        if code < 0x10000 {
+            out.push(code as u16);
+        } else {
+            let v = code - 0x10000;
+            out.push(0xD800 | (v >> 10) as u16);
+            out.push(0xDC00 | (v & 0x3FF) as u16);
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf16Error {
+    /// 出错的单元下标
+    pub index: usize,
+    /// 没有配对的代理单元
+    pub unit: u16,
+}
+
+impl Display for Utf16Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "单元 {} 处：未配对的代理 {:#06X}", self.index, self.unit)
+    }
+}
+
+/// 每次解码一个字符，未配对的代理返回 Err
+fn decode_utf16_iter(units: &[u16]) -> impl Iterator<Item = Result<char, Utf16Error>> + '_ {
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        let unit = *units.get(i)?;
+        let index = i;
+        i += 1;
+        let result = match unit {
+            0xD800..=0xDBFF => match units.get(i) {
+                Some(&low @ 0xDC00..=0xDFFF) => {
+                    i += 1;
+                    let code = 0x10000 + (((unit as u32) & 0x3FF) << 10 | ((low as u32) & 0x3FF));
+                    Ok(char::from_u32(code).expect("valid surrogate pair"))
+                }
+                _ => Err(Utf16Error { index, unit }),
+            },
+            0xDC00..=0xDFFF => Err(Utf16Error { index, unit }),
+            _ => Ok(char::from_u32(unit as u32).expect("non-surrogate BMP unit")),
+        };
+        Some(result)
+    })
+}
+
+pub fn decode_utf16(units: &[u16]) -> Result<String, Utf16Error> {
+    decode_utf16_iter(units).collect()
+}
+
+pub fn decode_utf16_lossy(units: &[u16]) -> String {
+    decode_utf16_iter(units).map(|r| r.unwrap_or(REPLACEMENT)).collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+fn encode_demo() {
+    for c in ['A', 'é', '中', '🦀'] {
+        let (buf, len) = encode_char(c);
+        let bits: Vec<String> = buf[..len].iter().map(|b| format!("{:08b}", b)).collect();
+        println!("{} U+{:04X} => {:<12} {}", c, c as u32, hex(&buf[..len]), bits.join(" "));
+        assert_eq!(&buf[..len], c.to_string().as_bytes());
+    }
+    assert_eq!(encode("中国人").len(), 9);
+}
+
+fn decode_errors() {
+    let cases: [(&str, &[u8]); 8] = [
+        ("合法", &[0xE4, 0xB8, 0xAD, 0x41]),
+        ("单独的续字节", &[0x41, 0x80, 0x42]),
+        ("超长编码 C0 AF", &[0xC0, 0xAF]),
+        ("超长编码 E0 80 AF", &[0xE0, 0x80, 0xAF]),
+        ("代理码点 U+D800", &[0xED, 0xA0, 0x80]),
+        ("超出范围 F4 90", &[0xF4, 0x90, 0x80, 0x80]),
+        ("截断", &[0x41, 0xE4, 0xB8]),
+        ("续字节非法", &[0xE4, 0x41, 0x42]),
+    ];
+    for (name, bytes) in cases {
+        match decode(bytes) {
+            Ok(s) => println!("{:<18} {:<12} => {:?}", name, hex(bytes), s),
+            Err(e) => println!("{:<18} {:<12} => {}", name, hex(bytes), e),
+        }
+    }
+
+    assert_eq!(decode(&[0xC0, 0xAF]).unwrap_err().kind, Utf8ErrorKind::Overlong);
+    assert_eq!(decode(&[0xED, 0xA0, 0x80]).unwrap_err().kind, Utf8ErrorKind::Surrogate);
+    assert_eq!(
+        decode(&[0x41, 0xE4, 0xB8]).unwrap_err(),
+        Utf8Error { valid_up_to: 1, error_len: None, kind: Utf8ErrorKind::Truncated { expected: 3, found: 2 } }
+    );
+}
+
+fn lossy_demo() {
+    let bytes = [0x68, 0x69, 0xFF, 0xE4, 0xB8, 0x41, 0xED, 0xA0, 0x80, 0xE4, 0xB8];
+    let lossy = decode_lossy(&bytes);
+    println!("{} => {:?}", hex(&bytes), lossy);
+    assert_eq!(lossy, String::from_utf8_lossy(&bytes));
+    // FF、E4 B8、ED、A0、80、末尾的 E4 B8 各替换成一个 �
+    assert_eq!(lossy, "hi\u{FFFD}\u{FFFD}A\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}");
+}
+
+fn utf16_demo() {
+    let s = "a中🦀";
+    let units = encode_utf16(s);
+    let formatted: Vec<String> = units.iter().map(|u| format!("{:04X}", u)).collect();
+    println!("{:?} => {}", s, formatted.join(" "));
+    assert_eq!(units, s.encode_utf16().collect::<Vec<_>>());
+    assert_eq!(decode_utf16(&units).unwrap(), s);
+
+    let broken = [0x61, 0xD83E, 0x62, 0xDD80];
+    match decode_utf16(&broken) {
+        Ok(s) => println!("{}", s),
+        Err(e) => println!("错误：{}", e),
+    }
+    assert_eq!(decode_utf16(&broken), Err(Utf16Error { index: 1, unit: 0xD83E }));
+    assert_eq!(decode_utf16_lossy(&broken), "a\u{FFFD}b\u{FFFD}");
+}
+
+/// 各种长度的字符都要覆盖到，纯随机的码点大多落在 4 字节区
+fn random_char(rng: &mut XorShift64) -> char {
+    let ranges: [(u64, u64); 4] = [(0x0, 0x7F), (0x80, 0x7FF), (0x800, 0xFFFF), (0x10000, 0x10FFFF)];
+    loop {
+        let (lo, hi) = ranges[rng.below(4) as usize];
+        if let Some(c) = char::from_u32((lo + rng.below(hi - lo + 1)) as u32) {
+            return c;
+        }
+    }
+}
+
+/// 合法文本中随机插入、删除、修改一些字节
+fn random_bytes(rng: &mut XorShift64) -> Vec<u8> {
+    let text: String = (0..rng.below(8)).map(|_| random_char(rng)).collect();
+    let mut bytes = text.into_bytes();
+    for _ in 0..rng.below(3) {
+        let pos = rng.below(bytes.len() as u64 + 1) as usize;
+        match rng.below(3) {
+            0 => bytes.insert(pos, rng.next_u64() as u8),
+            1 if pos < bytes.len() => {
+                bytes.remove(pos);
+            }
+            _ if pos < bytes.len() => bytes[pos] = rng.next_u64() as u8,
+            _ => {}
+        }
+    }
+    bytes
+}
+
+fn cross_check() {
+    let mut rng = XorShift64::new(2024);
+    let rounds = 100_000;
+
+    for _ in 0..rounds {
+        let c = random_char(&mut rng);
+        let (buf, len) = encode_char(c);
+        let mut expected = [0; 4];
+        assert_eq!(&buf[..len], c.encode_utf8(&mut expected).as_bytes(), "encode {:?}", c);
+    }
+
+    let mut invalid = 0;
+    for _ in 0..rounds {
+        let bytes = random_bytes(&mut rng);
+        match (decode(&bytes), std::str::from_utf8(&bytes)) {
+            (Ok(ours), Ok(std)) => assert_eq!(ours, std),
+            (Err(ours), Err(std)) => {
+                invalid += 1;
+                assert_eq!((ours.valid_up_to, ours.error_len), (std.valid_up_to(), std.error_len()), "{:02X?}", bytes);
+            }
+            (ours, std) => panic!("{:02X?}: {:?} vs {:?}", bytes, ours, std),
+        }
+        assert_eq!(decode_lossy(&bytes), String::from_utf8_lossy(&bytes));
+    }
+
+    for _ in 0..rounds {
+        let units: Vec<u16> = (0..rng.below(6))
+            .map(|_| match rng.below(3) {
+                0 => 0xD800 + rng.below(0x800) as u16,
+                _ => rng.next_u64() as u16,
+            })
+            .collect();
+        assert_eq!(decode_utf16(&units).ok(), String::from_utf16(&units).ok());
+        assert_eq!(decode_utf16_lossy(&units), String::from_utf16_lossy(&units));
+    }
+
+    println!("{} 轮随机测试通过，其中 {} 个非法 UTF-8 输入", rounds, invalid);
+}
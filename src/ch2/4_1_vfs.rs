@@ -0,0 +1,623 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::rc::Rc;
+
+use crate::ch2::unicode_inspect::pad_to_width;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("目录与文件", Box::new(|| dirs_and_files())),
+        ("读写与定位", Box::new(|| read_write_seek())),
+        ("打开方式与状态", Box::new(|| open_options())),
+        ("错误", Box::new(|| errors())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// 单个文件的最大字节数，写入或 `set_len` 超过时返回 `FileTooLarge`，避免定位到很远的位置后写入耗尽内存
+pub const MAX_FILE_LEN: usize = 64 << 20;
+
+/// 文件内容由文件系统和所有打开的句柄共享，删除文件后已打开的句柄仍然可以读写（与 Unix 一致）
+type Content = Rc<RefCell<Vec<u8>>>;
+
+#[derive(Debug)]
+enum Node {
+    File(Content),
+    Dir(BTreeMap<String, Node>),
+}
+
+fn error(kind: ErrorKind, message: String) -> io::Error {
+    io::Error::new(kind, message)
+}
+
+/// 把路径拆成各级名称，`.` 忽略，`..` 回到上一级，根目录的上一级还是根目录
+fn components(path: &str) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            name => parts.push(name.to_string()),
+        }
+    }
+    parts
+}
+
+fn join(parts: &[String]) -> String {
+    format!("/{}", parts.join("/"))
+}
+
+/// # 目录项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    pub name: String,
+    pub is_dir: bool,
+    /// 文件的字节数，目录为 0
+    pub len: usize,
+}
+
+/// # 内存中的文件系统
+/// * 路径用 `/` 分隔，相对路径从根目录算起
+/// * 出错时返回 `io::Error`，`ErrorKind` 与操作系统的文件系统一致，
+///   例如 `NotFound`、`AlreadyExists`、`IsADirectory`、`DirectoryNotEmpty`
+#[derive(Debug)]
+pub struct Vfs {
+    root: BTreeMap<String, Node>,
+}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Vfs {
+    pub fn new() -> Self {
+        Vfs { root: BTreeMap::new() }
+    }
+
+    fn lookup(&self, path: &str) -> io::Result<Option<&Node>> {
+        let parts = components(path);
+        let mut dir = &self.root;
+        let mut node = None;
+        for (i, part) in parts.iter().enumerate() {
+            if node.is_some() {
+                // 上一级是文件
+                return Err(error(ErrorKind::NotADirectory, format!("不是目录：{}", join(&parts[..i]))));
+            }
+            match dir.get(part) {
+                Some(Node::Dir(children)) => dir = children,
+                Some(file) => node = Some(file),
+                None => return Err(error(ErrorKind::NotFound, format!("找不到：{}", join(&parts[..=i])))),
+            }
+        }
+        Ok(node)
+    }
+
+    /// 找到目录，返回其子节点表
+    fn dir_mut(&mut self, parts: &[String]) -> io::Result<&mut BTreeMap<String, Node>> {
+        let mut dir = &mut self.root;
+        for (i, part) in parts.iter().enumerate() {
+            dir = match dir.get_mut(part) {
+                Some(Node::Dir(children)) => children,
+                Some(Node::File(_)) => {
+                    return Err(error(ErrorKind::NotADirectory, format!("不是目录：{}", join(&parts[..=i]))));
+                }
+                None => return Err(error(ErrorKind::NotFound, format!("目录不存在：{}", join(&parts[..=i])))),
+            };
+        }
+        Ok(dir)
+    }
+
+    /// 拆成父目录与最后一级名称，根目录没有名称
+    fn split(path: &str) -> io::Result<(Vec<String>, String)> {
+        let mut parts = components(path);
+        match parts.pop() {
+            Some(name) => Ok((parts, name)),
+            None => Err(error(ErrorKind::InvalidInput, "不能对根目录进行这个操作".to_string())),
+        }
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        self.lookup(path).is_ok()
+    }
+
+    pub fn is_dir(&self, path: &str) -> bool {
+        matches!(self.lookup(path), Ok(None))
+    }
+
+    pub fn is_file(&self, path: &str) -> bool {
+        matches!(self.lookup(path), Ok(Some(_)))
+    }
+
+    /// 父目录必须存在
+    pub fn create_dir(&mut self, path: &str) -> io::Result<()> {
+        let (parent, name) = Self::split(path)?;
+        let dir = self.dir_mut(&parent)?;
+        if dir.contains_key(&name) {
+            return Err(error(ErrorKind::AlreadyExists, format!("已存在：{}", join(&components(path)))));
+        }
+        dir.insert(name, Node::Dir(BTreeMap::new()));
+        Ok(())
+    }
+
+    /// 逐级创建，已存在的目录跳过
+    pub fn create_dir_all(&mut self, path: &str) -> io::Result<()> {
+        let parts = components(path);
+        for i in 1..=parts.len() {
+            match self.create_dir(&join(&parts[..i])) {
+                Err(e) if e.kind() == ErrorKind::AlreadyExists && self.is_dir(&join(&parts[..i])) => {}
+                result => result?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 创建或覆盖文件
+    pub fn write(&mut self, path: &str, contents: impl AsRef<[u8]>) -> io::Result<()> {
+        let options = OpenOptions::new().write(true).create(true).truncate(true);
+        self.open(path, &options)?.write_all(contents.as_ref())
+    }
+
+    pub fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        match self.lookup(path)? {
+            Some(Node::File(content)) => Ok(content.borrow().clone()),
+            _ => Err(error(ErrorKind::IsADirectory, format!("是目录：{}", join(&components(path))))),
+        }
+    }
+
+    pub fn read_to_string(&self, path: &str) -> io::Result<String> {
+        String::from_utf8(self.read(path)?).map_err(|e| error(ErrorKind::InvalidData, e.to_string()))
+    }
+
+    /// 列出目录内容，按名称排序
+    pub fn read_dir(&self, path: &str) -> io::Result<Vec<DirEntry>> {
+        let mut dir = &self.root;
+        for part in components(path) {
+            dir = match dir.get(&part) {
+                Some(Node::Dir(children)) => children,
+                Some(Node::File(_)) => return Err(error(ErrorKind::NotADirectory, format!("不是目录：{}", path))),
+                None => return Err(error(ErrorKind::NotFound, format!("找不到：{}", path))),
+            };
+        }
+        Ok(dir
+            .iter()
+            .map(|(name, node)| match node {
+                Node::File(content) => DirEntry { name: name.clone(), is_dir: false, len: content.borrow().len() },
+                Node::Dir(_) => DirEntry { name: name.clone(), is_dir: true, len: 0 },
+            })
+            .collect())
+    }
+
+    pub fn remove_file(&mut self, path: &str) -> io::Result<()> {
+        let (parent, name) = Self::split(path)?;
+        let dir = self.dir_mut(&parent)?;
+        match dir.get(&name) {
+            Some(Node::File(_)) => {
+                dir.remove(&name);
+                Ok(())
+            }
+            Some(Node::Dir(_)) => Err(error(ErrorKind::IsADirectory, format!("是目录：{}", join(&components(path))))),
+            None => Err(error(ErrorKind::NotFound, format!("找不到：{}", join(&components(path))))),
+        }
+    }
+
+    /// 只能删除空目录
+    pub fn remove_dir(&mut self, path: &str) -> io::Result<()> {
+        let (parent, name) = Self::split(path)?;
+        let dir = self.dir_mut(&parent)?;
+        match dir.get(&name) {
+            Some(Node::Dir(children)) if children.is_empty() => {
+                dir.remove(&name);
+                Ok(())
+            }
+            Some(Node::Dir(_)) => {
+                Err(error(ErrorKind::DirectoryNotEmpty, format!("目录不为空：{}", join(&components(path)))))
+            }
+            Some(Node::File(_)) => Err(error(ErrorKind::NotADirectory, format!("不是目录：{}", join(&components(path))))),
+            None => Err(error(ErrorKind::NotFound, format!("找不到：{}", join(&components(path))))),
+        }
+    }
+
+    /// # 打开文件
+    /// 规则与 `std::fs::OpenOptions` 相同，不符合时返回 `InvalidInput`：
+    /// * 至少要能读或能写
+    /// * `truncate`、`create`、`create_new` 需要 `write` 或 `append`
+    /// * `append` 时不能 `truncate`，除非同时 `create_new`
+    /// * 文件不存在时只有 `create`/`create_new` 才会创建，`create_new` 要求文件原本不存在
+    pub fn open(&mut self, path: &str, options: &OpenOptions) -> io::Result<File> {
+        if !options.read && !options.write && !options.append {
+            return Err(error(ErrorKind::InvalidInput, "打开文件时至少要能读或能写".to_string()));
+        }
+        match (options.write, options.append) {
+            (true, false) => {}
+            (false, false) if options.truncate || options.create || options.create_new => {
+                return Err(error(ErrorKind::InvalidInput, "truncate、create、create_new 需要同时指定 write 或 append".to_string()));
+            }
+            (false, false) => {}
+            (_, true) if options.truncate && !options.create_new => {
+                return Err(error(ErrorKind::InvalidInput, "append 不能和 truncate 一起使用".to_string()));
+            }
+            (_, true) => {}
+        }
+        let (parent, name) = Self::split(path)?;
+        let full_path = join(&components(path));
+        let dir = self.dir_mut(&parent)?;
+        let content = match dir.get(&name) {
+            Some(Node::Dir(_)) => return Err(error(ErrorKind::IsADirectory, format!("是目录：{}", full_path))),
+            Some(Node::File(_)) if options.create_new => {
+                return Err(error(ErrorKind::AlreadyExists, format!("已存在：{}", full_path)));
+            }
+            Some(Node::File(content)) => {
+                if options.truncate {
+                    content.borrow_mut().clear();
+                }
+                Rc::clone(content)
+            }
+            None if options.create || options.create_new => {
+                let content = Content::default();
+                dir.insert(name, Node::File(Rc::clone(&content)));
+                content
+            }
+            None => return Err(error(ErrorKind::NotFound, format!("找不到：{}", full_path))),
+        };
+        Ok(File {
+            path: full_path,
+            content,
+            pos: 0,
+            readable: options.read,
+            writable: options.write || options.append,
+            append: options.append,
+            open: true,
+        })
+    }
+
+    fn fmt_dir(f: &mut Formatter<'_>, dir: &BTreeMap<String, Node>, prefix: &str) -> std::fmt::Result {
+        for (i, (name, node)) in dir.iter().enumerate() {
+            let last = i + 1 == dir.len();
+            let branch = if last { "└── " } else { "├── " };
+            match node {
+                Node::File(content) => writeln!(f, "{}{}{} ({} B)", prefix, branch, name, content.borrow().len())?,
+                Node::Dir(children) => {
+                    writeln!(f, "{}{}{}/", prefix, branch, name)?;
+                    let indent = if last { "    " } else { "│   " };
+                    Self::fmt_dir(f, children, &format!("{}{}", prefix, indent))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 以树状结构打印整个文件系统
+impl Display for Vfs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "/")?;
+        Self::fmt_dir(f, &self.root, "")
+    }
+}
+
+/// # 打开方式
+/// 与 `std::fs::OpenOptions` 用法相同的构建器
+#[derive(Debug, Clone, Default)]
+pub struct OpenOptions {
+    read: bool,
+    write: bool,
+    append: bool,
+    truncate: bool,
+    create: bool,
+    create_new: bool,
+}
+
+impl OpenOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(mut self, read: bool) -> Self {
+        self.read = read;
+        self
+    }
+
+    pub fn write(mut self, write: bool) -> Self {
+        self.write = write;
+        self
+    }
+
+    /// 每次写入前都移动到文件末尾
+    pub fn append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.truncate = truncate;
+        self
+    }
+
+    pub fn create(mut self, create: bool) -> Self {
+        self.create = create;
+        self
+    }
+
+    pub fn create_new(mut self, create_new: bool) -> Self {
+        self.create_new = create_new;
+        self
+    }
+}
+
+/// # 打开的文件
+/// 实现了 `Read`、`Write`、`Seek`，可以直接用 `read_to_string`、`write_all` 等方法；
+/// 关闭之后所有操作都返回错误
+#[derive(Debug)]
+pub struct File {
+    path: String,
+    content: Content,
+    pos: u64,
+    readable: bool,
+    writable: bool,
+    append: bool,
+    open: bool,
+}
+
+impl File {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn len(&self) -> usize {
+        self.content.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// 重复关闭返回错误
+    pub fn close(&mut self) -> io::Result<()> {
+        self.check_open()?;
+        self.open = false;
+        Ok(())
+    }
+
+    /// 截断或用 0 填充到指定长度，不移动读写位置；长度不能超过 `MAX_FILE_LEN`
+    pub fn set_len(&mut self, len: usize) -> io::Result<()> {
+        self.check(self.writable, "写入")?;
+        if len > MAX_FILE_LEN {
+            return Err(self.too_large());
+        }
+        self.content.borrow_mut().resize(len, 0);
+        Ok(())
+    }
+
+    fn too_large(&self) -> io::Error {
+        error(ErrorKind::FileTooLarge, format!("文件超过 {} 字节：{}", MAX_FILE_LEN, self.path))
+    }
+
+    fn check_open(&self) -> io::Result<()> {
+        if self.open {
+            Ok(())
+        } else {
+            Err(error(ErrorKind::Other, format!("文件已关闭：{}", self.path)))
+        }
+    }
+
+    fn check(&self, allowed: bool, action: &str) -> io::Result<()> {
+        self.check_open()?;
+        if allowed {
+            Ok(())
+        } else {
+            Err(error(ErrorKind::PermissionDenied, format!("打开方式不允许{}：{}", action, self.path)))
+        }
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.check(self.readable, "读取")?;
+        let content = self.content.borrow();
+        // 位置在文件末尾之后时读到 0 个字节
+        let start = usize::try_from(self.pos).map_or(content.len(), |pos| pos.min(content.len()));
+        let n = buf.len().min(content.len() - start);
+        buf[..n].copy_from_slice(&content[start..start + n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Write for File {
+    /// 读写位置超过文件末尾时，中间的空洞用 0 填充；写入后超过 `MAX_FILE_LEN` 时返回错误，文件不变
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check(self.writable, "写入")?;
+        let mut content = self.content.borrow_mut();
+        if self.append {
+            self.pos = content.len() as u64;
+        }
+        let range = usize::try_from(self.pos).ok().and_then(|start| Some((start, start.checked_add(buf.len())?)));
+        let (start, end) = match range {
+            Some((start, end)) if end <= MAX_FILE_LEN => (start, end),
+            _ => return Err(self.too_large()),
+        };
+        if content.len() < end {
+            content.resize(end, 0);
+        }
+        content[start..end].copy_from_slice(buf);
+        self.pos = end as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.check_open()
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.check_open()?;
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::End(n) => (self.len() as u64, n),
+            SeekFrom::Current(n) => (self.pos, n),
+        };
+        match base.checked_add_signed(offset) {
+            Some(n) => {
+                self.pos = n;
+                Ok(n)
+            }
+            None => Err(error(ErrorKind::InvalidInput, format!("定位到了文件开头之前：{}", self.path))),
+        }
+    }
+}
+
+fn dirs_and_files() {
+    let mut fs = Vfs::new();
+    fs.create_dir_all("/home/rust/projects").unwrap();
+    fs.create_dir("/tmp").unwrap();
+    fs.write("/home/rust/hello.txt", "你好，rust").unwrap();
+    fs.write("/home/rust/projects/main.rs", "fn main() {}\n").unwrap();
+    fs.write("tmp/../etc.conf", [0u8; 16]).unwrap();
+    print!("{}", fs);
+
+    for entry in fs.read_dir("/home/rust").unwrap() {
+        println!("{:<10} {}", entry.name, if entry.is_dir { "目录".to_string() } else { format!("{} 字节", entry.len) });
+    }
+    assert_eq!(fs.read_to_string("/home/./rust/hello.txt").unwrap(), "你好，rust");
+    assert!(fs.is_file("/etc.conf"));
+    assert!(fs.is_dir("/home/rust/projects"));
+
+    fs.remove_file("/home/rust/projects/main.rs").unwrap();
+    fs.remove_dir("/home/rust/projects").unwrap();
+    assert!(!fs.exists("/home/rust/projects"));
+    // 已存在的目录直接跳过
+    fs.create_dir_all("/home/rust").unwrap();
+}
+
+fn read_write_seek() {
+    let mut fs = Vfs::new();
+    let options = OpenOptions::new().read(true).write(true).create(true);
+    let mut f = fs.open("/data.bin", &options).unwrap();
+    f.write_all(b"hello world").unwrap();
+
+    f.seek(SeekFrom::Start(6)).unwrap();
+    let mut word = String::new();
+    f.read_to_string(&mut word).unwrap();
+    println!("从第 6 个字节读到：{:?}", word);
+    assert_eq!(word, "world");
+
+    f.seek(SeekFrom::End(-5)).unwrap();
+    f.write_all(b"RUST!").unwrap();
+    f.seek(SeekFrom::Current(3)).unwrap();
+    f.write_all(b"?").unwrap();
+    println!("{:?}", String::from_utf8_lossy(&fs.read("/data.bin").unwrap()));
+    assert_eq!(fs.read("/data.bin").unwrap(), b"hello RUST!\0\0\0?");
+
+    // 同一个文件的两个句柄各自有读写位置，内容是共享的
+    let mut reader = fs.open("/data.bin", &OpenOptions::new().read(true)).unwrap();
+    let mut head = [0; 5];
+    reader.read_exact(&mut head).unwrap();
+    f.set_len(5).unwrap();
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!((&head, rest.len()), (b"hello", 0));
+    assert_eq!(f.position(), 15);
+}
+
+fn open_options() {
+    let mut fs = Vfs::new();
+    fs.write("/log.txt", "first\n").unwrap();
+
+    let mut log = fs.open("/log.txt", &OpenOptions::new().append(true)).unwrap();
+    log.write_all(b"second\n").unwrap();
+    log.seek(SeekFrom::Start(0)).unwrap();
+    log.write_all(b"third\n").unwrap(); // append 模式总是写到末尾
+    assert_eq!(fs.read_to_string("/log.txt").unwrap(), "first\nsecond\nthird\n");
+
+    // 只读句柄不能写，关闭后不能再读
+    let mut f = fs.open("/log.txt", &OpenOptions::new().read(true)).unwrap();
+    let err = f.write_all(b"x").unwrap_err();
+    println!("{}", err);
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    f.close().unwrap();
+    assert!(!f.is_open());
+    let err = f.read(&mut [0; 4]).unwrap_err();
+    println!("{}", err);
+    assert!(f.close().is_err());
+
+    let truncate = OpenOptions::new().write(true).truncate(true);
+    fs.open("/log.txt", &truncate).unwrap();
+    assert_eq!(fs.read("/log.txt").unwrap().len(), 0);
+}
+
+fn errors() {
+    let mut fs = Vfs::new();
+    fs.create_dir("/docs").unwrap();
+    fs.write("/docs/a.txt", "a").unwrap();
+
+    let results: Vec<(&str, io::Result<()>)> = vec![
+        ("读取不存在的文件", fs.read("/docs/b.txt").map(|_| ())),
+        ("父目录不存在", fs.write("/nope/a.txt", "x")),
+        ("把文件当目录", fs.create_dir("/docs/a.txt/sub")),
+        ("目录已存在", fs.create_dir("/docs")),
+        ("删除非空目录", fs.remove_dir("/docs")),
+        ("打开目录", fs.open("/docs", &OpenOptions::new().read(true)).map(|_| ())),
+        ("create_new 但文件已存在", fs.open("/docs/a.txt", &OpenOptions::new().write(true).create_new(true)).map(|_| ())),
+        ("不读也不写", fs.open("/docs/a.txt", &OpenOptions::new()).map(|_| ())),
+        ("只读却要创建", fs.open("/docs/b.txt", &OpenOptions::new().read(true).create(true)).map(|_| ())),
+        ("追加又要截断", fs.open("/docs/a.txt", &OpenOptions::new().write(true).append(true).truncate(true)).map(|_| ())),
+    ];
+    let mut kinds = vec![];
+    for (name, result) in results {
+        let err = result.unwrap_err();
+        println!("{}{:?}：{}", pad_to_width(name, 26), err.kind(), err);
+        kinds.push(err.kind());
+    }
+    assert_eq!(
+        kinds,
+        vec![
+            ErrorKind::NotFound,
+            ErrorKind::NotFound,
+            ErrorKind::NotADirectory,
+            ErrorKind::AlreadyExists,
+            ErrorKind::DirectoryNotEmpty,
+            ErrorKind::IsADirectory,
+            ErrorKind::AlreadyExists,
+            ErrorKind::InvalidInput,
+            ErrorKind::InvalidInput,
+            ErrorKind::InvalidInput,
+        ]
+    );
+    // 出错时既不创建也不截断
+    assert!(!fs.exists("/docs/b.txt"));
+    assert_eq!(fs.read("/docs/a.txt").unwrap(), b"a");
+
+    let mut f = fs.open("/docs/a.txt", &OpenOptions::new().read(true)).unwrap();
+    assert_eq!(f.seek(SeekFrom::Current(-1)).unwrap_err().kind(), ErrorKind::InvalidInput);
+
+    // 定位到很远的位置后写入，返回错误而不是溢出或分配大量内存
+    let mut f = fs.open("/docs/a.txt", &OpenOptions::new().read(true).write(true)).unwrap();
+    for pos in [u64::MAX, 1 << 40, MAX_FILE_LEN as u64] {
+        f.seek(SeekFrom::Start(pos)).unwrap();
+        assert_eq!(f.write(b"x").unwrap_err().kind(), ErrorKind::FileTooLarge);
+        assert_eq!(f.read(&mut [0; 4]).unwrap(), 0);
+    }
+    assert_eq!(f.set_len(MAX_FILE_LEN + 1).unwrap_err().kind(), ErrorKind::FileTooLarge);
+    assert_eq!(fs.read("/docs/a.txt").unwrap(), b"a");
+}
@@ -1,9 +1,16 @@
+use crate::ch2::json_convert::{assert_round_trip, field, from_json_str, object, FromJson, FromJsonError, ToJson};
+use crate::ch2::json::JsonValue;
+use std::fmt::{Display, Formatter};
+
 pub fn main() {
     let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
         ("基础操作", Box::new(|| basic())),
         ("结构体内存", Box::new(|| struct_mem_sort())),
         ("元组结构", Box::new(|| tuple_struct())),
         ("单元结构体", Box::new(|| unit_like_struct())),
+        ("JSON 序列化", Box::new(|| user_json())),
+        ("构建与校验", Box::new(|| user_builder())),
+        ("更新", Box::new(|| user_update())),
     ];
 
     for (name, function) in functions.into_iter() {
@@ -14,14 +21,36 @@ pub fn main() {
     }
 }
 
-#[derive(Debug)]
-struct User {
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
     username: String,
     email: String,
     active: bool,
     sign_in_count: i64,
 }
 
+impl ToJson for User {
+    fn to_json(&self) -> JsonValue {
+        object([
+            ("username", self.username.to_json()),
+            ("email", self.email.to_json()),
+            ("active", self.active.to_json()),
+            ("sign_in_count", self.sign_in_count.to_json()),
+        ])
+    }
+}
+
+impl FromJson for User {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(User {
+            username: field(value, "username")?,
+            email: field(value, "email")?,
+            active: field(value, "active")?,
+            sign_in_count: field(value, "sign_in_count")?,
+        })
+    }
+}
+
 fn basic() {
     let u1 = User {
         active: false,
@@ -80,6 +109,21 @@ fn basic() {
     println!("{:?}", user2);
 }
 
+/// # JSON 序列化
+/// 字段一一对应 JSON 对象的键，见 json_convert 模块
+fn user_json() {
+    let users = vec![
+        build_user("foo@qq.com".to_string(), "foo".to_string()),
+        User { username: "张三".to_string(), email: "zhang@qq.com".to_string(), active: true, sign_in_count: 0 },
+    ];
+    println!("{}", users.to_json().to_pretty());
+    assert_round_trip(&users);
+
+    let err = from_json_str::<User>(r#"{"username": "foo", "active": true, "sign_in_count": 1}"#).unwrap_err();
+    println!("{}", err);
+    assert_eq!(err.to_string(), "类型不匹配，$：缺少字段 email");
+}
+
 fn build_user(email: String, username: String) -> User {
     User {
         active: false,
@@ -89,6 +133,286 @@ fn build_user(email: String, username: String) -> User {
     }
 }
 
+/// # 构建 User
+/// username、email 必须设置，active 默认 false，sign_in_count 默认 0。
+/// `build` 一次检查所有字段，把全部错误收集到 [`ValidationErrors`] 中，而不是遇到第一个错误就返回
+#[derive(Debug, Clone, Default)]
+pub struct UserBuilder {
+    username: Option<String>,
+    email: Option<String>,
+    active: bool,
+    sign_in_count: i64,
+}
+
+const USERNAME_LEN: std::ops::RangeInclusive<usize> = 2..=20;
+
+impl User {
+    pub fn builder() -> UserBuilder {
+        UserBuilder::default()
+    }
+
+    /// # 更新
+    /// 以当前的值为默认值创建 builder，相当于 `User { email, ..user }`，
+    /// 但只借用 self：不会像结构体更新语法那样把 username 移走，修改后的值也会重新校验
+    pub fn update(&self) -> UserBuilder {
+        UserBuilder {
+            username: Some(self.username.clone()),
+            email: Some(self.email.clone()),
+            active: self.active,
+            sign_in_count: self.sign_in_count,
+        }
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    pub fn sign_in_count(&self) -> i64 {
+        self.sign_in_count
+    }
+}
+
+impl UserBuilder {
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    pub fn sign_in_count(mut self, sign_in_count: i64) -> Self {
+        self.sign_in_count = sign_in_count;
+        self
+    }
+
+    pub fn build(self) -> Result<User, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        match &self.username {
+            None => errors.push("username", FieldErrorKind::Missing),
+            Some(username) => {
+                if let Err(kind) = validate_username(username) {
+                    errors.push("username", kind);
+                }
+            }
+        }
+        match &self.email {
+            None => errors.push("email", FieldErrorKind::Missing),
+            Some(email) => {
+                if let Err(reason) = validate_email(email) {
+                    errors.push("email", FieldErrorKind::InvalidEmail(reason));
+                }
+            }
+        }
+        if self.sign_in_count < 0 {
+            errors.push("sign_in_count", FieldErrorKind::Negative(self.sign_in_count));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(User {
+            username: self.username.unwrap(),
+            email: self.email.unwrap(),
+            active: self.active,
+            sign_in_count: self.sign_in_count,
+        })
+    }
+}
+
+/// 字母开头，只包含字母、数字和 `_`，字母包括中文，长度按字符计算
+fn validate_username(username: &str) -> Result<(), FieldErrorKind> {
+    let len = username.chars().count();
+    if !USERNAME_LEN.contains(&len) {
+        return Err(FieldErrorKind::Length { len, min: *USERNAME_LEN.start(), max: *USERNAME_LEN.end() });
+    }
+    if let Some(ch) = username.chars().find(|&c| !c.is_alphanumeric() && c != '_') {
+        return Err(FieldErrorKind::InvalidChar(ch));
+    }
+    match username.chars().next() {
+        Some(first) if !first.is_alphabetic() => Err(FieldErrorKind::InvalidStart(first)),
+        _ => Ok(()),
+    }
+}
+
+/// 只做语法检查：`本地部分@域名`
+/// * 本地部分由字母、数字和 `._%+-` 组成，`.` 不能在首尾，也不能连续出现
+/// * 域名至少两段，每段由字母、数字和 `-` 组成，`-` 不能在首尾，最后一段至少两个字母
+fn validate_email(email: &str) -> Result<(), &'static str> {
+    let (local, domain) = email.split_once('@').ok_or("缺少 @")?;
+    if local.is_empty() {
+        return Err("@ 前面不能为空");
+    }
+    if domain.contains('@') {
+        return Err("只能有一个 @");
+    }
+    if !local.chars().all(|c| c.is_ascii_alphanumeric() || "._%+-".contains(c)) {
+        return Err("@ 前面有不允许的字符");
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return Err("@ 前面的 . 位置不对");
+    }
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        return Err("域名至少要有两段");
+    }
+    for label in &labels {
+        if label.is_empty() || label.starts_with('-') || label.ends_with('-') {
+            return Err("域名中有空的段或者段的首尾是 -");
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err("域名中有不允许的字符");
+        }
+    }
+    let tld = labels[labels.len() - 1];
+    if tld.len() < 2 || !tld.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err("顶级域名至少是两个字母");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldErrorKind {
+    Missing,
+    /// 长度按字符计算
+    Length { len: usize, min: usize, max: usize },
+    InvalidChar(char),
+    InvalidStart(char),
+    InvalidEmail(&'static str),
+    Negative(i64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    field: &'static str,
+    kind: FieldErrorKind,
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}：", self.field)?;
+        match &self.kind {
+            FieldErrorKind::Missing => write!(f, "必须设置"),
+            FieldErrorKind::Length { len, min, max } => write!(f, "长度 {} 不在 {}..={} 之间", len, min, max),
+            FieldErrorKind::InvalidChar(ch) => write!(f, "不能包含 {:?}", ch),
+            FieldErrorKind::InvalidStart(ch) => write!(f, "不能以 {:?} 开头", ch),
+            FieldErrorKind::InvalidEmail(reason) => write!(f, "不是合法的邮箱，{}", reason),
+            FieldErrorKind::Negative(n) => write!(f, "不能是负数 {}", n),
+        }
+    }
+}
+
+/// # 所有字段的校验错误
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    fn push(&mut self, field: &'static str, kind: FieldErrorKind) {
+        self.errors.push(FieldError { field, kind });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// 某个字段的错误
+    pub fn field(&self, field: &str) -> Option<&FieldErrorKind> {
+        self.errors.iter().find(|e| e.field == field).map(|e| &e.kind)
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "校验失败，共 {} 个错误", self.errors.len())?;
+        for error in &self.errors {
+            write!(f, "\n  {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+fn user_builder() {
+    let user = User::builder().username("foo_bar").email("foo@qq.com").active(true).build().unwrap();
+    println!("{:?}", user);
+    assert_eq!(user, User { username: "foo_bar".to_string(), email: "foo@qq.com".to_string(), active: true, sign_in_count: 0 });
+    assert!(User::builder().username("张三").email("zhang.san+rust@mail.example.cn").build().is_ok());
+
+    // 所有错误一起报告
+    let errors = User::builder().username("1_bad name").email("foo@@bar").sign_in_count(-1).build().unwrap_err();
+    println!("{}", errors);
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors.field("username"), Some(&FieldErrorKind::InvalidChar(' ')));
+    assert_eq!(errors.field("email"), Some(&FieldErrorKind::InvalidEmail("只能有一个 @")));
+    assert_eq!(errors.field("sign_in_count"), Some(&FieldErrorKind::Negative(-1)));
+
+    let errors = User::builder().build().unwrap_err();
+    println!("{}", errors);
+    assert_eq!(errors.field("email"), Some(&FieldErrorKind::Missing));
+
+    let usernames = [("a", false), ("a_very_long_username_here", false), ("_foo", false), ("foo_1", true)];
+    for (username, ok) in usernames {
+        let result = validate_username(username);
+        println!("{:<28} {:?}", username, result);
+        assert_eq!(result.is_ok(), ok);
+    }
+    let emails = [
+        ("foo@bar.com", true),
+        ("foo.bar@bar.co", true),
+        ("foo", false),
+        ("@bar.com", false),
+        ("foo..bar@bar.com", false),
+        ("foo@bar", false),
+        ("foo@-bar.com", false),
+        ("foo@bar.c", false),
+        ("foo@bar.123", false),
+        ("foo bar@bar.com", false),
+    ];
+    for (email, ok) in emails {
+        let result = validate_email(email);
+        println!("{:<20} {:?}", email, result);
+        assert_eq!(result.is_ok(), ok);
+    }
+}
+
+fn user_update() {
+    let user1 = User::builder().username("foo").email("user1@qq.com").active(true).sign_in_count(1).build().unwrap();
+
+    // 和 User { email: ..., ..user1 } 一样只改 email，但 user1 仍然完整可用
+    let user2 = user1.update().email("user2@qq.com").build().unwrap();
+    println!("{:?}", user1);
+    println!("{:?}", user2);
+    assert_eq!(user2.username, user1.username);
+    assert_eq!((user2.email.as_str(), user2.active, user2.sign_in_count), ("user2@qq.com", true, 1));
+
+    // 更新同样会校验
+    let errors = user1.update().email("not an email").sign_in_count(user1.sign_in_count - 5).build().unwrap_err();
+    println!("{}", errors);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(user1.update().build(), Ok(user1.clone()));
+}
+
 
 #[derive(Debug)]
 struct File {
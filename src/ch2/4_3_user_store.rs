@@ -0,0 +1,491 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+
+use crate::ch2::structure::{User, UserBuilder, ValidationErrors};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("创建与查找", Box::new(|| create_and_find())),
+        ("唯一约束", Box::new(|| unique_constraints())),
+        ("登录与停用", Box::new(|| sign_in_and_deactivate())),
+        ("查询", Box::new(|| queries())),
+        ("保存与加载", Box::new(|| save_and_load())),
+        ("旧版本文件", Box::new(|| load_old_version())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UserId(u64);
+
+impl Display for UserId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreError {
+    Invalid(ValidationErrors),
+    UsernameTaken(String),
+    EmailTaken(String),
+    /// 按 id、用户名或邮箱都没有找到
+    NotFound(String),
+    /// 已停用的用户不能登录
+    Inactive(String),
+    /// 登录次数已经是 i64::MAX，不能再加 1
+    TooManySignIns(String),
+    /// 已经分配过 u64::MAX，没有下一个 id 了
+    IdsExhausted,
+}
+
+impl Display for StoreError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoreError::Invalid(errors) => write!(f, "{}", errors),
+            StoreError::UsernameTaken(name) => write!(f, "用户名 {} 已经被使用", name),
+            StoreError::EmailTaken(email) => write!(f, "邮箱 {} 已经被使用", email),
+            StoreError::NotFound(key) => write!(f, "没有找到用户 {}", key),
+            StoreError::Inactive(name) => write!(f, "用户 {} 已停用", name),
+            StoreError::TooManySignIns(name) => write!(f, "用户 {} 的登录次数已达上限", name),
+            StoreError::IdsExhausted => write!(f, "没有可分配的 id"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<ValidationErrors> for StoreError {
+    fn from(errors: ValidationErrors) -> Self {
+        StoreError::Invalid(errors)
+    }
+}
+
+/// # 用户存储
+/// * 用户名、邮箱都是唯一的，比较时不区分大小写
+/// * 索引保存的是小写的用户名、邮箱到 id 的映射，修改用户时同步更新
+/// * User 的字段不能直接修改，所有修改都经过 `User::update()` 重新校验
+#[derive(Debug, Clone, Default)]
+pub struct UserStore {
+    users: BTreeMap<UserId, User>,
+    by_username: HashMap<String, UserId>,
+    by_email: HashMap<String, UserId>,
+    next_id: u64,
+}
+
+fn key(s: &str) -> String {
+    s.to_lowercase()
+}
+
+impl UserStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    pub fn create(&mut self, builder: UserBuilder) -> Result<UserId, StoreError> {
+        let user = builder.build()?;
+        let id = self.next_id()?;
+        self.insert(id, user)?;
+        Ok(id)
+    }
+
+    /// 比已有的 id 都大；加载过 id 为 u64::MAX 的文件之后就分配不出来了
+    fn next_id(&self) -> Result<UserId, StoreError> {
+        self.next_id.checked_add(1).map(UserId).ok_or(StoreError::IdsExhausted)
+    }
+
+    /// 检查唯一约束后保存，id 由调用方指定，加载文件时保留原来的 id
+    fn insert(&mut self, id: UserId, user: User) -> Result<(), StoreError> {
+        self.check_unique(&user, None)?;
+        self.by_username.insert(key(user.username()), id);
+        self.by_email.insert(key(user.email()), id);
+        self.users.insert(id, user);
+        self.next_id = self.next_id.max(id.0);
+        Ok(())
+    }
+
+    /// except 是正在修改的用户自己，和自己原来的值相同不算冲突
+    fn check_unique(&self, user: &User, except: Option<UserId>) -> Result<(), StoreError> {
+        match self.by_username.get(&key(user.username())) {
+            Some(&other) if Some(other) != except => return Err(StoreError::UsernameTaken(user.username().to_string())),
+            _ => {}
+        }
+        match self.by_email.get(&key(user.email())) {
+            Some(&other) if Some(other) != except => Err(StoreError::EmailTaken(user.email().to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn get(&self, id: UserId) -> Option<&User> {
+        self.users.get(&id)
+    }
+
+    pub fn find_by_username(&self, username: &str) -> Option<(UserId, &User)> {
+        let &id = self.by_username.get(&key(username))?;
+        Some((id, &self.users[&id]))
+    }
+
+    pub fn find_by_email(&self, email: &str) -> Option<(UserId, &User)> {
+        let &id = self.by_email.get(&key(email))?;
+        Some((id, &self.users[&id]))
+    }
+
+    /// 用户名或邮箱，含有 `@` 的按邮箱查找
+    pub fn find(&self, login: &str) -> Option<(UserId, &User)> {
+        if login.contains('@') {
+            self.find_by_email(login)
+        } else {
+            self.find_by_username(login)
+        }
+    }
+
+    /// # 修改用户
+    /// 在 `User::update()` 得到的 builder 上修改，重新校验并检查唯一约束，失败时用户保持不变
+    pub fn update(&mut self, id: UserId, f: impl FnOnce(UserBuilder) -> UserBuilder) -> Result<&User, StoreError> {
+        let old = self.get(id).ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+        let new = f(old.update()).build()?;
+        self.check_unique(&new, Some(id))?;
+        let (old_username, old_email) = (key(old.username()), key(old.email()));
+        self.by_username.remove(&old_username);
+        self.by_email.remove(&old_email);
+        self.by_username.insert(key(new.username()), id);
+        self.by_email.insert(key(new.email()), id);
+        self.users.insert(id, new);
+        Ok(&self.users[&id])
+    }
+
+    /// # 登录
+    /// 用户名或邮箱登录，返回登录后的次数
+    pub fn sign_in(&mut self, login: &str) -> Result<i64, StoreError> {
+        let (id, user) = self.find(login).ok_or_else(|| StoreError::NotFound(login.to_string()))?;
+        if !user.active() {
+            return Err(StoreError::Inactive(user.username().to_string()));
+        }
+        let count = user.sign_in_count().checked_add(1).ok_or_else(|| StoreError::TooManySignIns(user.username().to_string()))?;
+        self.update(id, |builder| builder.sign_in_count(count))?;
+        Ok(count)
+    }
+
+    /// 停用，返回状态是否发生了变化
+    pub fn deactivate(&mut self, id: UserId) -> Result<bool, StoreError> {
+        self.set_active(id, false)
+    }
+
+    pub fn reactivate(&mut self, id: UserId) -> Result<bool, StoreError> {
+        self.set_active(id, true)
+    }
+
+    fn set_active(&mut self, id: UserId, active: bool) -> Result<bool, StoreError> {
+        let user = self.get(id).ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+        if user.active() == active {
+            return Ok(false);
+        }
+        self.update(id, |builder| builder.active(active))?;
+        Ok(true)
+    }
+
+    /// 按 id 顺序遍历
+    pub fn iter(&self) -> impl Iterator<Item = (UserId, &User)> {
+        self.users.iter().map(|(&id, user)| (id, user))
+    }
+
+    pub fn filter<'a>(&'a self, predicate: impl Fn(&User) -> bool + 'a) -> impl Iterator<Item = (UserId, &'a User)> + 'a {
+        self.iter().filter(move |(_, user)| predicate(user))
+    }
+
+    /// 启用中的用户，登录次数从多到少，次数相同时按用户名排序
+    pub fn active_by_sign_ins(&self) -> Vec<(UserId, &User)> {
+        let mut users: Vec<_> = self.filter(User::active).collect();
+        users.sort_by(|(_, a), (_, b)| b.sign_in_count().cmp(&a.sign_in_count()).then_with(|| a.username().cmp(b.username())));
+        users
+    }
+}
+
+/// 文件第一行，后面跟着版本号
+const MAGIC: &str = "head-first-rust-users";
+/// 当前写入的版本
+/// * 1：`用户名 邮箱 active 登录次数`，没有 id，加载时按行号分配
+/// * 2：`id 用户名 邮箱 active 登录次数`
+const VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingHeader,
+    UnsupportedVersion(String),
+    FieldCount { expected: usize, found: usize },
+    InvalidNumber(String),
+    InvalidBool(String),
+    DuplicateId(u64),
+    Store(StoreError),
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    /// line 从 1 开始
+    Parse { line: usize, kind: ParseErrorKind },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (line, kind) = match self {
+            LoadError::Io(e) => return write!(f, "读取失败：{}", e),
+            LoadError::Parse { line, kind } => (line, kind),
+        };
+        write!(f, "第 {} 行：", line)?;
+        match kind {
+            ParseErrorKind::MissingHeader => write!(f, "缺少文件头 `{} <版本>`", MAGIC),
+            ParseErrorKind::UnsupportedVersion(v) => write!(f, "不支持的版本 {}，最高支持 {}", v, VERSION),
+            ParseErrorKind::FieldCount { expected, found } => write!(f, "应该有 {} 个字段，实际 {} 个", expected, found),
+            ParseErrorKind::InvalidNumber(s) => write!(f, "{:?} 不是合法的数字", s),
+            ParseErrorKind::InvalidBool(s) => write!(f, "{:?} 不是 0 或 1", s),
+            ParseErrorKind::DuplicateId(id) => write!(f, "重复的 id {}", id),
+            ParseErrorKind::Store(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+impl UserStore {
+    /// # 保存成文本
+    /// 一行一个用户，字段用制表符分隔。用户名、邮箱经过校验，不会含有制表符和换行
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", MAGIC, VERSION);
+        for (id, user) in self.iter() {
+            let active = u8::from(user.active());
+            text += &format!("{}\t{}\t{}\t{}\t{}\n", id.0, user.username(), user.email(), active, user.sign_in_count());
+        }
+        text
+    }
+
+    /// # 从文本加载
+    /// 支持所有旧版本，空行和 `#` 开头的注释行被忽略，每个用户都重新校验
+    pub fn from_text(text: &str) -> Result<UserStore, LoadError> {
+        let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+        let error = |line, kind| LoadError::Parse { line, kind };
+        let version = match lines.next() {
+            Some((_, header)) => match header.split_once(' ') {
+                Some((MAGIC, version)) => match version.parse::<u32>() {
+                    Ok(v @ 1..=VERSION) => v,
+                    _ => return Err(error(1, ParseErrorKind::UnsupportedVersion(version.to_string()))),
+                },
+                _ => return Err(error(1, ParseErrorKind::MissingHeader)),
+            },
+            None => return Err(error(1, ParseErrorKind::MissingHeader)),
+        };
+
+        let mut store = UserStore::new();
+        for (n, line) in lines {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields: Vec<&str> = line.split('\t').collect();
+            let expected = if version == 1 { 4 } else { 5 };
+            if fields.len() != expected {
+                return Err(error(n, ParseErrorKind::FieldCount { expected, found: fields.len() }));
+            }
+            let id = match version {
+                1 => store.next_id().map_err(|e| error(n, ParseErrorKind::Store(e)))?.0,
+                _ => {
+                    let id = fields.remove(0);
+                    id.parse().map_err(|_| error(n, ParseErrorKind::InvalidNumber(id.to_string())))?
+                }
+            };
+            if store.users.contains_key(&UserId(id)) {
+                return Err(error(n, ParseErrorKind::DuplicateId(id)));
+            }
+            let active = match fields[2] {
+                "0" => false,
+                "1" => true,
+                other => return Err(error(n, ParseErrorKind::InvalidBool(other.to_string()))),
+            };
+            let count = fields[3].parse().map_err(|_| error(n, ParseErrorKind::InvalidNumber(fields[3].to_string())))?;
+            let user = User::builder()
+                .username(fields[0])
+                .email(fields[1])
+                .active(active)
+                .sign_in_count(count)
+                .build()
+                .map_err(|e| error(n, ParseErrorKind::Store(e.into())))?;
+            store.insert(UserId(id), user).map_err(|e| error(n, ParseErrorKind::Store(e)))?;
+        }
+        Ok(store)
+    }
+
+    /// 先写到临时文件再重命名，写到一半出错时不会破坏原来的文件
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, self.to_text())?;
+        std::fs::rename(&tmp, path)
+    }
+
+    pub fn load(path: &Path) -> Result<UserStore, LoadError> {
+        Self::from_text(&std::fs::read_to_string(path)?)
+    }
+}
+
+fn sample_store() -> UserStore {
+    let mut store = UserStore::new();
+    let users = [("foo", "foo@qq.com", 3), ("张三", "zhang@qq.com", 7), ("bar_bar", "bar@bar.com", 3), ("old_user", "old@bar.com", 10)];
+    for (username, email, count) in users {
+        store.create(User::builder().username(username).email(email).active(true).sign_in_count(count)).unwrap();
+    }
+    store
+}
+
+fn create_and_find() {
+    let mut store = UserStore::new();
+    let id = store.create(User::builder().username("foo").email("Foo@QQ.com").active(true)).unwrap();
+    println!("创建了 {}：{:?}", id, store.get(id));
+    assert_eq!(store.find_by_username("FOO").map(|(id, _)| id), Some(id));
+    assert_eq!(store.find_by_email("foo@qq.com").map(|(_, user)| user.username()), Some("foo"));
+    assert_eq!(store.find("foo@QQ.COM").map(|(id, _)| id), Some(id));
+    assert!(store.find("bar").is_none());
+
+    let err = store.create(User::builder().username("x").email("bad")).unwrap_err();
+    println!("{}", err);
+    assert!(matches!(err, StoreError::Invalid(ref e) if e.len() == 2));
+    assert_eq!(store.len(), 1);
+}
+
+fn unique_constraints() {
+    let mut store = sample_store();
+    let results = [
+        store.create(User::builder().username("FOO").email("another@qq.com")),
+        store.create(User::builder().username("another").email("ZHANG@qq.com")),
+    ];
+    for result in &results {
+        println!("{:?}", result.as_ref().map_err(ToString::to_string));
+    }
+    assert_eq!(results[0], Err(StoreError::UsernameTaken("FOO".to_string())));
+    assert_eq!(results[1], Err(StoreError::EmailTaken("ZHANG@qq.com".to_string())));
+
+    // 修改时和其他用户冲突会失败，和自己原来的值相同不算冲突
+    let (id, _) = store.find("foo").unwrap();
+    assert_eq!(store.update(id, |b| b.email("bar@bar.com")).unwrap_err(), StoreError::EmailTaken("bar@bar.com".to_string()));
+    assert!(store.update(id, |b| b.username("Foo")).is_ok());
+    let user = store.update(id, |b| b.email("foo@new.com")).unwrap();
+    println!("修改后：{:?}", user);
+    assert!(store.find("foo@qq.com").is_none());
+    assert_eq!(store.find("foo@new.com").map(|(id, _)| id), Some(id));
+    // 旧邮箱释放后可以被别人使用
+    assert!(store.create(User::builder().username("newbie").email("foo@qq.com")).is_ok());
+}
+
+fn sign_in_and_deactivate() {
+    let mut store = sample_store();
+    println!("foo 登录：{:?}", store.sign_in("foo"));
+    println!("用邮箱登录：{:?}", store.sign_in("foo@qq.com"));
+    assert_eq!(store.find("foo").unwrap().1.sign_in_count(), 5);
+
+    let (id, _) = store.find("foo").unwrap();
+    assert_eq!(store.deactivate(id), Ok(true));
+    assert_eq!(store.deactivate(id), Ok(false));
+    let err = store.sign_in("foo").unwrap_err();
+    println!("{}", err);
+    assert_eq!(err, StoreError::Inactive("foo".to_string()));
+    assert_eq!(store.reactivate(id), Ok(true));
+    assert_eq!(store.sign_in("foo"), Ok(6));
+
+    assert_eq!(store.sign_in("nobody"), Err(StoreError::NotFound("nobody".to_string())));
+    assert_eq!(store.deactivate(UserId(100)), Err(StoreError::NotFound("#100".to_string())));
+
+    // 登录次数到了 i64::MAX 之后返回错误，次数不变
+    let mut store = UserStore::from_text(&format!("head-first-rust-users 2\n1\tfoo\tfoo@qq.com\t1\t{}\n", i64::MAX)).unwrap();
+    let err = store.sign_in("foo").unwrap_err();
+    println!("{}", err);
+    assert_eq!(err, StoreError::TooManySignIns("foo".to_string()));
+    assert_eq!(store.get(UserId(1)).unwrap().sign_in_count(), i64::MAX);
+}
+
+fn queries() {
+    let mut store = sample_store();
+    let (id, _) = store.find("old_user").unwrap();
+    store.deactivate(id).unwrap();
+    println!("启用中的用户，按登录次数排序：");
+    for (id, user) in store.active_by_sign_ins() {
+        println!("  {} {} {}", id, user.username(), user.sign_in_count());
+    }
+    let names: Vec<&str> = store.active_by_sign_ins().iter().map(|(_, user)| user.username()).collect();
+    assert_eq!(names, ["张三", "bar_bar", "foo"]);
+
+    let qq: Vec<&str> = store.filter(|user| user.email().ends_with("@qq.com")).map(|(_, user)| user.username()).collect();
+    println!("qq 邮箱：{:?}", qq);
+    assert_eq!(qq, ["foo", "张三"]);
+}
+
+fn save_and_load() {
+    let mut store = sample_store();
+    store.sign_in("张三").unwrap();
+    let (id, _) = store.find("bar_bar").unwrap();
+    store.deactivate(id).unwrap();
+
+    let path = std::env::temp_dir().join("head_first_rust_users.txt");
+    store.save(&path).unwrap();
+    print!("{}", std::fs::read_to_string(&path).unwrap());
+
+    let loaded = UserStore::load(&path).unwrap();
+    assert_eq!(loaded.to_text(), store.to_text());
+    assert_eq!(loaded.find("张三").map(|(_, user)| user.sign_in_count()), Some(8));
+    assert!(!loaded.find("bar_bar").unwrap().1.active());
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(UserStore::load(&path), Err(LoadError::Io(_))));
+
+    let broken = [
+        "users 1\n",
+        "head-first-rust-users 9\n",
+        "head-first-rust-users 2\n1\tfoo\tfoo@qq.com\t1\n",
+        "head-first-rust-users 2\n1\tfoo\tfoo@qq.com\tyes\t0\n",
+        "head-first-rust-users 2\n1\tfoo\tfoo@qq.com\t1\t0\n1\tbar\tbar@qq.com\t1\t0\n",
+        "head-first-rust-users 2\n1\tfoo\tfoo@qq.com\t1\t0\n\n2\tFOO\tbar@qq.com\t1\t0\n",
+        "head-first-rust-users 2\n1\tfoo\tfoo@qq\t1\t-1\n",
+    ];
+    for text in broken {
+        let err = UserStore::from_text(text).unwrap_err();
+        println!("{}", err.to_string().replace('\n', " "));
+    }
+    assert!(matches!(UserStore::from_text(broken[5]), Err(LoadError::Parse { line: 4, kind: ParseErrorKind::Store(StoreError::UsernameTaken(_)) })));
+
+    // 文件里的 id 已经是 u64::MAX，可以加载，但不能再创建新用户
+    let mut store = UserStore::from_text(&format!("head-first-rust-users 2\n{}\tfoo\tfoo@qq.com\t1\t0\n", u64::MAX)).unwrap();
+    let err = store.create(User::builder().username("bar").email("bar@qq.com")).unwrap_err();
+    println!("{}", err);
+    assert_eq!(err, StoreError::IdsExhausted);
+    assert_eq!(store.len(), 1);
+}
+
+fn load_old_version() {
+    // 版本 1 没有 id，按出现的顺序分配
+    let text = "head-first-rust-users 1\n# 旧版本导出的文件\nfoo\tfoo@qq.com\t1\t3\nbar\tbar@qq.com\t0\t5\n";
+    let store = UserStore::from_text(text).unwrap();
+    for (id, user) in store.iter() {
+        println!("{} {:?}", id, user);
+    }
+    assert_eq!(store.find("bar").map(|(id, _)| id), Some(UserId(2)));
+    // 再保存时升级到当前版本
+    print!("{}", store.to_text());
+    assert!(store.to_text().starts_with("head-first-rust-users 2\n1\tfoo\t"));
+}
@@ -1,7 +1,12 @@
+use crate::ch2::json_convert::{assert_round_trip, field, from_json_str, object, tagged, unknown_variant, variant, variant_content, FromJson, FromJsonError, FromJsonErrorKind, ToJson};
+use crate::ch2::json::JsonValue;
+use std::fmt::{Display, Formatter};
+
 pub fn main() {
     let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
         ("枚举值", Box::new(|| enum_value())),
         ("Option 枚举值", Box::new(|| enum_option())),
+        ("JSON 序列化", Box::new(|| enum_json())),
     ];
 
     for (name, function) in functions.into_iter() {
@@ -13,22 +18,59 @@ pub fn main() {
 }
 
 /// # 扑克的花色
-#[derive(Debug)]
-#[allow(dead_code)]
-enum PokerSuit {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PokerSuit {
     Clubs,
     Spades,
     Diamonds,
     Hearts,
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
-struct PokerCard {
+impl PokerSuit {
+    pub const ALL: [PokerSuit; 4] = [PokerSuit::Clubs, PokerSuit::Spades, PokerSuit::Diamonds, PokerSuit::Hearts];
+}
+
+/// # 扑克牌
+/// * value 是点数 1..=13，1 是 A，11、12、13 分别是 J、Q、K
+/// * 字段是私有的，只能通过 [`PokerCard::new`] 构造，点数一定合法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PokerCard {
     suit: PokerSuit,
     value: u8,
 }
 
+/// 点数不在 1..=13 内
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidRank(pub u8);
+
+impl Display for InvalidRank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "点数 {} 不在 1..=13 内", self.0)
+    }
+}
+
+impl std::error::Error for InvalidRank {}
+
+impl PokerCard {
+    pub const RANKS: std::ops::RangeInclusive<u8> = 1..=13;
+
+    pub fn new(suit: PokerSuit, value: u8) -> Result<Self, InvalidRank> {
+        if Self::RANKS.contains(&value) {
+            Ok(PokerCard { suit, value })
+        } else {
+            Err(InvalidRank(value))
+        }
+    }
+
+    pub fn suit(&self) -> PokerSuit {
+        self.suit
+    }
+
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 enum PokerCardSuit {
@@ -47,31 +89,88 @@ enum PokerCartSuitOther {
     Hearts(char),
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
-enum Message {
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
     Quit, // 无关联数据
     Move { x: i32, y: i32 }, // 匿名结构体
     Write(String), // 包含一个字符串
     ChangeColor(i32, i32, i32), // 包含多个 i32
 }
 
+/// 没有关联数据的枚举直接写成成员名字符串
+impl ToJson for PokerSuit {
+    fn to_json(&self) -> JsonValue {
+        format!("{:?}", self).to_json()
+    }
+}
+
+impl FromJson for PokerSuit {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        match variant(value)? {
+            ("Clubs", None) => Ok(PokerSuit::Clubs),
+            ("Spades", None) => Ok(PokerSuit::Spades),
+            ("Diamonds", None) => Ok(PokerSuit::Diamonds),
+            ("Hearts", None) => Ok(PokerSuit::Hearts),
+            (tag, _) => Err(unknown_variant(tag)),
+        }
+    }
+}
+
+impl ToJson for PokerCard {
+    fn to_json(&self) -> JsonValue {
+        object([("suit", self.suit.to_json()), ("value", self.value.to_json())])
+    }
+}
+
+impl FromJson for PokerCard {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let rank = field(value, "value")?;
+        PokerCard::new(field(value, "suit")?, rank).map_err(|_| {
+            FromJsonError::new(FromJsonErrorKind::OutOfRange { target: "PokerCard.value", value: rank as f64 }).at_key("value")
+        })
+    }
+}
+
+/// 外部标记：`"Quit"`、`{"Move": {"x": 1, "y": 2}}`、`{"Write": "hi"}`、`{"ChangeColor": [255, 255, 0]}`
+impl ToJson for Message {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Message::Quit => "Quit".to_json(),
+            Message::Move { x, y } => tagged("Move", object([("x", x.to_json()), ("y", y.to_json())])),
+            Message::Write(s) => tagged("Write", s.to_json()),
+            Message::ChangeColor(r, g, b) => tagged("ChangeColor", (r, g, b).to_json()),
+        }
+    }
+}
+
+impl FromJson for Message {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        match variant(value)? {
+            ("Quit", None) => Ok(Message::Quit),
+            ("Move", Some(content)) => {
+                let position = |name| field(content, name).map_err(|e| e.at_key("Move"));
+                Ok(Message::Move { x: position("x")?, y: position("y")? })
+            }
+            ("Write", content) => Ok(Message::Write(variant_content("Write", content)?)),
+            ("ChangeColor", content) => {
+                let (r, g, b) = variant_content("ChangeColor", content)?;
+                Ok(Message::ChangeColor(r, g, b))
+            }
+            (tag, _) => Err(unknown_variant(tag)),
+        }
+    }
+}
+
 fn enum_value() {
     let hearts = PokerSuit::Hearts;
     let diamond = PokerSuit::Diamonds;
     print_suit(hearts);
     print_suit(diamond);
 
-    let c1 = PokerCard {
-        suit: PokerSuit::Clubs,
-        value: 1,
-    };
+    let c1 = PokerCard::new(PokerSuit::Clubs, 1).unwrap();
     println!("{:?}", c1);
 
-    let c2 = PokerCard {
-        suit: PokerSuit::Diamonds,
-        value: 13,
-    };
+    let c2 = PokerCard::new(PokerSuit::Diamonds, 13).unwrap();
     println!("{:?}", c2);
 
     // 数据信息 关联到成员
@@ -93,6 +192,34 @@ fn enum_value() {
     println!("{:?}", (m1, m2, m3));
 }
 
+/// # JSON 序列化
+/// 带数据的枚举成员写成只有一个键的对象，键是成员名
+fn enum_json() {
+    let cards = vec![
+        PokerCard::new(PokerSuit::Clubs, 1).unwrap(),
+        PokerCard::new(PokerSuit::Hearts, 13).unwrap(),
+    ];
+    println!("{}", assert_round_trip(&cards));
+
+    let messages = vec![
+        Message::Quit,
+        Message::Move { x: 1, y: -2 },
+        Message::Write("hello".to_string()),
+        Message::ChangeColor(255, 255, 0),
+    ];
+    println!("{}", assert_round_trip(&messages));
+
+    let err = from_json_str::<Vec<Message>>(r#"["Quit", {"Move": {"x": 1, "y": "2"}}]"#).unwrap_err();
+    println!("{}", err);
+    assert_eq!(err.to_string(), "类型不匹配，$[1].Move.y：期望 number，实际是 string");
+    assert!(from_json_str::<PokerSuit>(r#""Joker""#).is_err());
+
+    // 反序列化也要经过 PokerCard::new 校验点数
+    let err = from_json_str::<PokerCard>(r#"{"suit": "Hearts", "value": 14}"#).unwrap_err();
+    println!("{}", err);
+    assert!(err.to_string().contains("$.value"));
+}
+
 fn print_suit(card: PokerSuit) {
     println!("{:?}", card);
 }
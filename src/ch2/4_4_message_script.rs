@@ -0,0 +1,480 @@
+use std::fmt::{Display, Formatter, Write};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::ch2::enumeration::Message;
+use crate::ch2::unicode_inspect::display_width;
+use crate::rng::XorShift64;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("解析命令", Box::new(|| parse_commands())),
+        ("序列化", Box::new(|| serialize())),
+        ("错误位置", Box::new(|| error_positions())),
+        ("运行脚本", Box::new(|| run_scripts())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandErrorKind {
+    /// `expected` 描述期望的内容，`found` 为 None 表示已经到了行尾
+    Expected { expected: &'static str, found: Option<char> },
+    UnknownCommand(String),
+    /// 超出 i32 的范围
+    IntegerOutOfRange(String),
+    UnterminatedString,
+    InvalidEscape(char),
+    /// `\u{...}` 里不是合法的 Unicode 标量值
+    InvalidUnicodeEscape,
+    /// 命令的参数之后还有多余的内容
+    TrailingCharacters,
+}
+
+/// # 解析错误
+/// 行号、列号从 1 开始，列号按字符计数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: CommandErrorKind,
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "第 {} 行第 {} 列：", self.line, self.column)?;
+        match &self.kind {
+            CommandErrorKind::Expected { expected, found: Some(c) } => write!(f, "期望 {}，实际是 {:?}", expected, c),
+            CommandErrorKind::Expected { expected, found: None } => write!(f, "期望 {}，实际已到行尾", expected),
+            CommandErrorKind::UnknownCommand(name) => write!(f, "未知的命令 {:?}，可用的命令是 quit、move、write、color", name),
+            CommandErrorKind::IntegerOutOfRange(s) => write!(f, "{} 超出了 i32 的范围", s),
+            CommandErrorKind::UnterminatedString => write!(f, "字符串没有结束的引号"),
+            CommandErrorKind::InvalidEscape(c) => write!(f, "未知的转义 \\{}", c),
+            CommandErrorKind::InvalidUnicodeEscape => write!(f, "\\u{{...}} 里需要合法的 Unicode 码点"),
+            CommandErrorKind::TrailingCharacters => write!(f, "命令之后还有多余的内容"),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// 打印出错的那一行，并在下方用 `^` 标出位置
+pub fn show_error(src: &str, error: &CommandError) -> String {
+    let line = src.lines().nth(error.line - 1).unwrap_or("");
+    let prefix: String = line.chars().take(error.column - 1).collect();
+    format!("{}\n{}^", line, " ".repeat(display_width(&prefix)))
+}
+
+/// 解析一行命令，`line` 只用来报告错误位置
+struct LineParser<'a> {
+    src: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl LineParser<'_> {
+    fn error_at(&self, pos: usize, kind: CommandErrorKind) -> CommandError {
+        CommandError { line: self.line, column: self.src[..pos].chars().count() + 1, kind }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// 行尾或者 `#` 开始的注释
+    fn at_end(&self) -> bool {
+        matches!(self.peek(), None | Some('#'))
+    }
+
+    /// 一个参数之后必须是空白、注释或行尾，`move 1,2` 这样的写法报错
+    fn separator(&self) -> Result<(), CommandError> {
+        match self.peek() {
+            Some(c) if !c.is_whitespace() && c != '#' => {
+                Err(self.error_at(self.pos, CommandErrorKind::Expected { expected: "空格", found: Some(c) }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 空行、注释返回 None
+    fn command(&mut self) -> Result<Option<Message>, CommandError> {
+        self.skip_whitespace();
+        if self.at_end() {
+            return Ok(None);
+        }
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+            self.bump();
+        }
+        let message = match &self.src[start..self.pos] {
+            "" => return Err(self.error_at(start, CommandErrorKind::Expected { expected: "命令", found: self.peek() })),
+            "quit" => {
+                self.separator()?;
+                Message::Quit
+            }
+            "move" => Message::Move { x: self.integer()?, y: self.integer()? },
+            "write" => Message::Write(self.string()?),
+            "color" => Message::ChangeColor(self.integer()?, self.integer()?, self.integer()?),
+            name => return Err(self.error_at(start, CommandErrorKind::UnknownCommand(name.to_string()))),
+        };
+        self.skip_whitespace();
+        if !self.at_end() {
+            return Err(self.error_at(self.pos, CommandErrorKind::TrailingCharacters));
+        }
+        Ok(Some(message))
+    }
+
+    fn integer(&mut self) -> Result<i32, CommandError> {
+        self.separator()?;
+        self.skip_whitespace();
+        let start = self.pos;
+        if matches!(self.peek(), Some('-' | '+')) {
+            self.bump();
+        }
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error_at(start, CommandErrorKind::Expected { expected: "整数", found: self.src[start..].chars().next() }));
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        let text = &self.src[start..self.pos];
+        let value = text.parse().map_err(|_| self.error_at(start, CommandErrorKind::IntegerOutOfRange(text.to_string())))?;
+        self.separator()?;
+        Ok(value)
+    }
+
+    /// 双引号字符串，转义与 Rust 相同：`\"`、`\\`、`\n`、`\t`、`\r`、`\u{7f}`
+    fn string(&mut self) -> Result<String, CommandError> {
+        self.separator()?;
+        self.skip_whitespace();
+        let quote = self.pos;
+        if self.peek() != Some('"') {
+            return Err(self.error_at(quote, CommandErrorKind::Expected { expected: "带引号的字符串", found: self.peek() }));
+        }
+        self.bump();
+        let mut out = String::new();
+        loop {
+            let escape = self.pos;
+            match self.bump() {
+                None => return Err(self.error_at(quote, CommandErrorKind::UnterminatedString)),
+                Some('"') => break,
+                Some('\\') => match self.bump() {
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('r') => out.push('\r'),
+                    Some('u') => out.push(self.unicode_escape(escape)?),
+                    Some(c) => return Err(self.error_at(escape, CommandErrorKind::InvalidEscape(c))),
+                    None => return Err(self.error_at(quote, CommandErrorKind::UnterminatedString)),
+                },
+                Some(c) => out.push(c),
+            }
+        }
+        self.separator()?;
+        Ok(out)
+    }
+
+    /// `\u` 之后的 `{十六进制}`，错误指向反斜杠
+    fn unicode_escape(&mut self, escape: usize) -> Result<char, CommandError> {
+        let error = self.error_at(escape, CommandErrorKind::InvalidUnicodeEscape);
+        let rest = &self.src[self.pos..];
+        let hex = rest.strip_prefix('{').and_then(|s| s.split_once('}')).map(|(hex, _)| hex).ok_or(error.clone())?;
+        let code = (1..=6).contains(&hex.len()).then(|| u32::from_str_radix(hex, 16).ok()).flatten();
+        let c = code.and_then(char::from_u32).ok_or(error)?;
+        self.pos += hex.len() + 2;
+        Ok(c)
+    }
+}
+
+/// # 解析单条命令
+/// 允许前后的空白和行尾注释
+impl FromStr for Message {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = LineParser { src: s, pos: 0, line: 1 };
+        parser.command()?.ok_or_else(|| parser.error_at(s.len(), CommandErrorKind::Expected { expected: "命令", found: None }))
+    }
+}
+
+/// # 解析脚本
+/// * 每行一条命令，空行和 `#` 开始的注释会被忽略
+/// * 遇到第一个错误就返回
+pub fn parse_script(src: &str) -> Result<Vec<Message>, CommandError> {
+    let mut messages = vec![];
+    for (i, line) in src.lines().enumerate() {
+        if let Some(message) = (LineParser { src: line, pos: 0, line: i + 1 }).command()? {
+            messages.push(message);
+        }
+    }
+    Ok(messages)
+}
+
+fn write_quoted(f: &mut Formatter<'_>, s: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\t' => f.write_str("\\t")?,
+            '\r' => f.write_str("\\r")?,
+            c if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// # 序列化成命令
+/// 输出总能被 [`parse_script`] 原样解析回来
+impl Display for Message {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Message::Quit => write!(f, "quit"),
+            Message::Move { x, y } => write!(f, "move {} {}", x, y),
+            Message::Write(s) => {
+                write!(f, "write ")?;
+                write_quoted(f, s)
+            }
+            Message::ChangeColor(r, g, b) => write!(f, "color {} {} {}", r, g, b),
+        }
+    }
+}
+
+pub fn to_script(messages: &[Message]) -> String {
+    messages.iter().map(|m| format!("{}\n", m)).collect()
+}
+
+/// # 执行命令的状态
+/// * Move 移动到指定位置，Write 追加一行输出，ChangeColor 修改颜色
+/// * Quit 之后的命令不再执行
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct State {
+    pub position: (i32, i32),
+    pub color: (i32, i32, i32),
+    pub output: Vec<String>,
+    pub quit: bool,
+    /// 执行了的命令数
+    pub executed: usize,
+    /// Quit 之后被跳过的命令数
+    pub skipped: usize,
+}
+
+impl State {
+    pub fn apply(&mut self, message: &Message) {
+        if self.quit {
+            self.skipped += 1;
+            return;
+        }
+        match message {
+            Message::Quit => self.quit = true,
+            Message::Move { x, y } => self.position = (*x, *y),
+            Message::Write(s) => self.output.push(s.clone()),
+            Message::ChangeColor(r, g, b) => self.color = (*r, *g, *b),
+        }
+        self.executed += 1;
+    }
+}
+
+impl Display for State {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "位置：{:?}", self.position)?;
+        writeln!(f, "颜色：{:?}", self.color)?;
+        writeln!(f, "输出：{:?}", self.output)?;
+        write!(f, "执行 {} 条，跳过 {} 条，{}", self.executed, self.skipped, if self.quit { "已退出" } else { "未退出" })
+    }
+}
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Io(io::Error),
+    Parse(CommandError),
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptError::Io(e) => write!(f, "读取失败：{}", e),
+            ScriptError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+impl From<io::Error> for ScriptError {
+    fn from(e: io::Error) -> Self {
+        ScriptError::Io(e)
+    }
+}
+
+impl From<CommandError> for ScriptError {
+    fn from(e: CommandError) -> Self {
+        ScriptError::Parse(e)
+    }
+}
+
+/// # 运行脚本
+/// 先解析整个脚本再执行，有语法错误时一条命令都不执行
+pub fn run_script(src: &str) -> Result<State, CommandError> {
+    let mut state = State::default();
+    for message in &parse_script(src)? {
+        state.apply(message);
+    }
+    Ok(state)
+}
+
+pub fn run_file(path: &Path) -> Result<State, ScriptError> {
+    Ok(run_script(&std::fs::read_to_string(path)?)?)
+}
+
+/// # 解析命令
+fn parse_commands() {
+    let cases = [
+        ("quit", Message::Quit),
+        ("move 1 2", Message::Move { x: 1, y: 2 }),
+        ("  move -3   +4  # 前后可以有空白和注释", Message::Move { x: -3, y: 4 }),
+        (r#"write "hi""#, Message::Write("hi".to_string())),
+        (r#"write "说 \"你好\"\n# 不是注释""#, Message::Write("说 \"你好\"\n# 不是注释".to_string())),
+        (r#"write "\u{1F980}""#, Message::Write("🦀".to_string())),
+        ("color 255 255 0", Message::ChangeColor(255, 255, 0)),
+    ];
+    for (src, expected) in cases {
+        let message: Message = src.parse().unwrap();
+        println!("{:?} => {:?}", src, message);
+        assert_eq!(message, expected);
+    }
+
+    let script = "# 画一个黄色的点\ncolor 255 255 0\n\nmove 10 20\nwrite \"done\"\nquit\n";
+    let messages = parse_script(script).unwrap();
+    println!("{:?}", messages);
+    assert_eq!(messages.len(), 4);
+}
+
+fn random_message(rng: &mut XorShift64) -> Message {
+    const PIECES: [&str; 10] = ["a", " ", "\"", "\\", "\n", "\t", "#", "中文", "🦀", "\u{7}"];
+    let mut int = || match rng.below(4) {
+        0 => i32::MIN,
+        1 => i32::MAX,
+        _ => rng.next_u64() as i32 % 1000,
+    };
+    let (a, b, c) = (int(), int(), int());
+    match rng.below(4) {
+        0 => Message::Quit,
+        1 => Message::Move { x: a, y: b },
+        2 => Message::Write((0..rng.below(8)).map(|_| *rng.choose(&PIECES).unwrap()).collect()),
+        _ => Message::ChangeColor(a, b, c),
+    }
+}
+
+/// # 序列化
+/// 序列化再解析，得到的命令和原来的一样
+fn serialize() {
+    let messages = vec![
+        Message::Quit,
+        Message::Move { x: i32::MIN, y: i32::MAX },
+        Message::Write(String::new()),
+        Message::Write("引号 \" 反斜杠 \\ 换行 \n 制表 \t 响铃 \u{7}".to_string()),
+        Message::ChangeColor(255, 255, 0),
+    ];
+    let script = to_script(&messages);
+    print!("{}", script);
+    assert_eq!(parse_script(&script).unwrap(), messages);
+
+    let mut rng = XorShift64::new(7);
+    for _ in 0..1000 {
+        let message = random_message(&mut rng);
+        assert_eq!(message.to_string().parse::<Message>().unwrap(), message);
+    }
+    let messages: Vec<Message> = (0..200).map(|_| random_message(&mut rng)).collect();
+    assert_eq!(parse_script(&to_script(&messages)).unwrap(), messages);
+    println!("随机命令序列化后都能解析回来");
+}
+
+/// # 错误位置
+fn error_positions() {
+    let expected = |expected, found| CommandErrorKind::Expected { expected, found };
+    let cases = [
+        ("jump 1 2", CommandErrorKind::UnknownCommand("jump".to_string()), (1, 1)),
+        ("quit\n  Move 1 2", CommandErrorKind::UnknownCommand("Move".to_string()), (2, 3)),
+        ("-1", expected("命令", Some('-')), (1, 1)),
+        ("move 1", expected("整数", None), (1, 7)),
+        ("move 1,2", expected("空格", Some(',')), (1, 7)),
+        ("move x 2", expected("整数", Some('x')), (1, 6)),
+        ("move1 2", CommandErrorKind::UnknownCommand("move1".to_string()), (1, 1)),
+        ("color 255 255 2147483648", CommandErrorKind::IntegerOutOfRange("2147483648".to_string()), (1, 15)),
+        ("write hi", expected("带引号的字符串", Some('h')), (1, 7)),
+        ("write \"中文", CommandErrorKind::UnterminatedString, (1, 7)),
+        ("write \"中文\\q\"", CommandErrorKind::InvalidEscape('q'), (1, 10)),
+        ("write \"\\u{d800}\"", CommandErrorKind::InvalidUnicodeEscape, (1, 8)),
+        ("write \"a\"\"b\"", expected("空格", Some('"')), (1, 10)),
+        ("quit now", CommandErrorKind::TrailingCharacters, (1, 6)),
+        ("color 1 2 3 4", CommandErrorKind::TrailingCharacters, (1, 13)),
+    ];
+    for (src, kind, (line, column)) in cases {
+        let err = parse_script(src).unwrap_err();
+        println!("{}", err);
+        println!("{}", show_error(src, &err));
+        assert_eq!(err, CommandError { line, column, kind });
+    }
+
+    let err = "   # 只有注释".parse::<Message>().unwrap_err();
+    println!("{}", err);
+    assert_eq!(err.kind, expected("命令", None));
+}
+
+/// # 运行脚本
+/// 从文件读取命令，依次作用到状态上，最后打印状态
+fn run_scripts() {
+    let script = "\
+# 示例脚本
+color 255 255 0
+move 1 2
+write \"hello\"
+move -5 8
+write \"世界\"
+quit
+write \"不会执行\"
+";
+    let path = std::env::temp_dir().join("head_first_rust_messages.txt");
+    std::fs::write(&path, script).unwrap();
+    let state = run_file(&path).unwrap();
+    println!("{}", state);
+    assert_eq!(state.position, (-5, 8));
+    assert_eq!(state.color, (255, 255, 0));
+    assert_eq!(state.output, ["hello", "世界"]);
+    assert!(state.quit);
+    assert_eq!((state.executed, state.skipped), (6, 1));
+
+    // 有语法错误时整个脚本都不执行
+    std::fs::write(&path, "write \"a\"\nmove 1\n").unwrap();
+    let err = run_file(&path).unwrap_err();
+    println!("{}", err);
+    assert!(matches!(err, ScriptError::Parse(CommandError { line: 2, .. })));
+
+    std::fs::remove_file(&path).unwrap();
+    let err = run_file(&path).unwrap_err();
+    println!("{}", err);
+    assert!(matches!(err, ScriptError::Io(_)));
+}
@@ -0,0 +1,557 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::ch2::enumeration::{PokerCard, PokerSuit};
+use crate::rng::XorShift64;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("牌与记号", Box::new(|| card_notation())),
+        ("洗牌与发牌", Box::new(|| shuffle_and_deal())),
+        ("五张牌", Box::new(|| five_card_hands())),
+        ("同牌型比较", Box::new(|| tie_breaking())),
+        ("七张牌", Box::new(|| seven_card_hands())),
+        ("牌型统计", Box::new(|| hand_statistics())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+impl PokerSuit {
+    pub fn letter(self) -> char {
+        match self {
+            PokerSuit::Clubs => 'C',
+            PokerSuit::Spades => 'S',
+            PokerSuit::Diamonds => 'D',
+            PokerSuit::Hearts => 'H',
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            PokerSuit::Clubs => '♣',
+            PokerSuit::Spades => '♠',
+            PokerSuit::Diamonds => '♦',
+            PokerSuit::Hearts => '♥',
+        }
+    }
+
+    /// 字母不区分大小写，也接受花色符号
+    pub fn from_char(c: char) -> Option<Self> {
+        PokerSuit::ALL.into_iter().find(|suit| suit.letter() == c.to_ascii_uppercase() || suit.symbol() == c)
+    }
+}
+
+/// 比较大小时的点数：A 最大，记作 14
+fn high_rank(value: u8) -> u8 {
+    if value == 1 { 14 } else { value }
+}
+
+fn rank_str(rank: u8) -> &'static str {
+    const NAMES: [&str; 15] = ["", "A", "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A"];
+    NAMES[rank as usize]
+}
+
+/// # 牌的记号
+/// * 点数在前、花色在后：`AS`、`10H`、`QD`
+/// * `{:#}` 用花色符号代替字母：`A♠`、`10♥`
+impl Display for PokerCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let suit = if f.alternate() { self.suit().symbol() } else { self.suit().letter() };
+        write!(f, "{}{}", rank_str(self.value()), suit)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    Empty,
+    UnknownRank(String),
+    UnknownSuit(String),
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "空的牌"),
+            ParseCardError::UnknownRank(s) => write!(f, "{:?} 的点数无效，应该是 A、2..10、J、Q、K", s),
+            ParseCardError::UnknownSuit(s) => write!(f, "{:?} 的花色无效，应该是 C、S、D、H", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// 点数也接受 `T` 表示 10，大小写都可以
+impl FromStr for PokerCard {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let suit_char = chars.next_back().ok_or(ParseCardError::Empty)?;
+        let suit = PokerSuit::from_char(suit_char).ok_or_else(|| ParseCardError::UnknownSuit(s.to_string()))?;
+        let value = match chars.as_str().to_ascii_uppercase().as_str() {
+            "A" => 1,
+            "T" | "10" => 10,
+            "J" => 11,
+            "Q" => 12,
+            "K" => 13,
+            digit @ ("2" | "3" | "4" | "5" | "6" | "7" | "8" | "9") => digit.parse().unwrap(),
+            _ => return Err(ParseCardError::UnknownRank(s.to_string())),
+        };
+        Ok(PokerCard::new(suit, value).expect("点数已经检查过"))
+    }
+}
+
+/// 解析空白分隔的多张牌，例如 `"AS KS 10H"`
+pub fn parse_cards(s: &str) -> Result<Vec<PokerCard>, ParseCardError> {
+    s.split_whitespace().map(str::parse).collect()
+}
+
+fn join_cards(cards: &[PokerCard]) -> String {
+    cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+/// 剩余的牌不够发
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotEnoughCards {
+    pub wanted: usize,
+    pub left: usize,
+}
+
+impl Display for NotEnoughCards {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "要发 {} 张牌，只剩 {} 张", self.wanted, self.left)
+    }
+}
+
+impl std::error::Error for NotEnoughCards {}
+
+/// # 一副牌
+/// * 新牌按花色、点数排好，共 52 张
+/// * 洗牌使用可复现的随机数，相同的种子洗出相同的顺序
+/// * `cards[0]` 是牌堆顶，发牌从顶上发
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck {
+    cards: Vec<PokerCard>,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deck {
+    pub fn new() -> Self {
+        let cards = PokerSuit::ALL
+            .into_iter()
+            .flat_map(|suit| PokerCard::RANKS.map(move |value| PokerCard::new(suit, value).unwrap()))
+            .collect();
+        Deck { cards }
+    }
+
+    pub fn shuffled(seed: u64) -> Self {
+        let mut deck = Self::new();
+        deck.shuffle(&mut XorShift64::new(seed));
+        deck
+    }
+
+    pub fn shuffle(&mut self, rng: &mut XorShift64) {
+        rng.shuffle(&mut self.cards);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn cards(&self) -> &[PokerCard] {
+        &self.cards
+    }
+
+    pub fn deal_one(&mut self) -> Option<PokerCard> {
+        if self.cards.is_empty() { None } else { Some(self.cards.remove(0)) }
+    }
+
+    /// 从牌堆顶发 n 张；不够时返回错误，牌堆不变
+    pub fn deal(&mut self, n: usize) -> Result<Vec<PokerCard>, NotEnoughCards> {
+        if n > self.cards.len() {
+            return Err(NotEnoughCards { wanted: n, left: self.cards.len() });
+        }
+        Ok(self.cards.drain(..n).collect())
+    }
+}
+
+/// # 牌型
+/// 成员的顺序就是大小顺序，派生的 `Ord` 直接可以比较
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl Category {
+    pub const ALL: [Category; 9] = [
+        Category::HighCard,
+        Category::OnePair,
+        Category::TwoPair,
+        Category::ThreeOfAKind,
+        Category::Straight,
+        Category::Flush,
+        Category::FullHouse,
+        Category::FourOfAKind,
+        Category::StraightFlush,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::HighCard => "高牌",
+            Category::OnePair => "一对",
+            Category::TwoPair => "两对",
+            Category::ThreeOfAKind => "三条",
+            Category::Straight => "顺子",
+            Category::Flush => "同花",
+            Category::FullHouse => "葫芦",
+            Category::FourOfAKind => "四条",
+            Category::StraightFlush => "同花顺",
+        }
+    }
+}
+
+/// # 一手牌的大小
+/// * 先比牌型，牌型相同再依次比较 `ranks`
+/// * `ranks` 按“张数多的在前、同样张数点数大的在前”排列，例如葫芦 KKK44 是 `[13, 4]`，
+///   两对 QQ77A 是 `[12, 7, 14]`；顺子只看最大的一张，A2345 的最大牌是 5
+/// * 花色不参与比较，所以两手牌可能一样大
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandValue {
+    category: Category,
+    ranks: Vec<u8>,
+}
+
+impl HandValue {
+    pub fn category(&self) -> Category {
+        self.category
+    }
+
+    /// 比较用的点数，A 记作 14
+    pub fn ranks(&self) -> &[u8] {
+        &self.ranks
+    }
+}
+
+impl Display for HandValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.category.name())?;
+        for rank in &self.ranks {
+            write!(f, " {}", rank_str(*rank))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandError {
+    /// 只能从 5 到 7 张牌中选
+    WrongSize(usize),
+    Duplicate(PokerCard),
+}
+
+impl Display for HandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandError::WrongSize(n) => write!(f, "需要 5 到 7 张牌，实际是 {} 张", n),
+            HandError::Duplicate(card) => write!(f, "{} 出现了不止一次", card),
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
+/// # 计算五张牌的大小
+fn evaluate5(cards: &[PokerCard; 5]) -> HandValue {
+    let mut counts = [0u8; 15];
+    for card in cards {
+        counts[high_rank(card.value()) as usize] += 1;
+    }
+    // (张数, 点数)，张数多的在前，同样张数点数大的在前
+    let mut groups: Vec<(u8, u8)> = (2..=14u8).rev().filter(|&r| counts[r as usize] > 0).map(|r| (counts[r as usize], r)).collect();
+    groups.sort_by_key(|&(count, rank)| Reverse((count, rank)));
+
+    let flush = cards.iter().all(|c| c.suit() == cards[0].suit());
+    let straight_high = if groups.len() == 5 {
+        let (high, low) = (groups[0].1, groups[4].1);
+        if high - low == 4 {
+            Some(high)
+        } else if high == 14 && groups[1].1 == 5 {
+            // A 当作 1 用：A2345
+            Some(5)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    let ranks: Vec<u8> = groups.iter().map(|&(_, rank)| rank).collect();
+    let (category, ranks) = match (straight_high, flush, groups[0].0, groups[1].0) {
+        (Some(high), true, _, _) => (Category::StraightFlush, vec![high]),
+        (_, _, 4, _) => (Category::FourOfAKind, ranks),
+        (_, _, 3, 2) => (Category::FullHouse, ranks),
+        (_, true, _, _) => (Category::Flush, ranks),
+        (Some(high), _, _, _) => (Category::Straight, vec![high]),
+        (_, _, 3, _) => (Category::ThreeOfAKind, ranks),
+        (_, _, 2, 2) => (Category::TwoPair, ranks),
+        (_, _, 2, _) => (Category::OnePair, ranks),
+        _ => (Category::HighCard, ranks),
+    };
+    HandValue { category, ranks }
+}
+
+/// # 选出的最好五张牌
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub value: HandValue,
+    /// 按比较时的顺序排列，例如葫芦先放三条，A2345 的 A 放最后
+    pub cards: [PokerCard; 5],
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}（{}）", self.value, join_cards(&self.cards))
+    }
+}
+
+/// # 从 5 到 7 张牌中选出最大的五张
+/// * 7 张牌一共 C(7,5) = 21 种选法，全部算一遍取最大
+/// * 牌不能重复
+pub fn best_hand(cards: &[PokerCard]) -> Result<Hand, HandError> {
+    if !(5..=7).contains(&cards.len()) {
+        return Err(HandError::WrongSize(cards.len()));
+    }
+    let mut seen = HashSet::new();
+    if let Some(card) = cards.iter().find(|card| !seen.insert(**card)) {
+        return Err(HandError::Duplicate(*card));
+    }
+
+    let n = cards.len();
+    let mut best: Option<(HandValue, [PokerCard; 5])> = None;
+    for mask in 0u32..(1 << n) {
+        if mask.count_ones() != 5 {
+            continue;
+        }
+        let chosen: Vec<PokerCard> = (0..n).filter(|i| mask & (1 << i) != 0).map(|i| cards[i]).collect();
+        let chosen: [PokerCard; 5] = chosen.try_into().unwrap();
+        let value = evaluate5(&chosen);
+        if best.as_ref().is_none_or(|(best_value, _)| value > *best_value) {
+            best = Some((value, chosen));
+        }
+    }
+
+    let (value, mut chosen) = best.unwrap();
+    let order = |card: &PokerCard| {
+        let rank = high_rank(card.value());
+        let rank = if value.ranks == [5] && rank == 14 { 1 } else { rank };
+        let position = value.ranks.iter().position(|&r| r == rank).unwrap_or(0);
+        (position, Reverse(rank))
+    };
+    chosen.sort_by_key(order);
+    Ok(Hand { value, cards: chosen })
+}
+
+/// 计算几手牌的大小，返回最大的那些下标，平局时有多个
+pub fn winners(hands: &[Vec<PokerCard>]) -> Result<Vec<usize>, HandError> {
+    let values = hands.iter().map(|cards| best_hand(cards).map(|hand| hand.value)).collect::<Result<Vec<_>, _>>()?;
+    let Some(max) = values.iter().max() else {
+        return Ok(vec![]);
+    };
+    Ok(values.iter().enumerate().filter(|(_, v)| *v == max).map(|(i, _)| i).collect())
+}
+
+fn hand(s: &str) -> Hand {
+    best_hand(&parse_cards(s).unwrap()).unwrap()
+}
+
+/// # 牌与记号
+/// 构造时检查点数，解析和打印互为逆操作
+fn card_notation() {
+    let ace = PokerCard::new(PokerSuit::Spades, 1).unwrap();
+    let ten = PokerCard::new(PokerSuit::Hearts, 10).unwrap();
+    println!("{} {} / {:#} {:#}", ace, ten, ace, ten);
+    assert_eq!((ace.to_string(), ten.to_string()), ("AS".to_string(), "10H".to_string()));
+
+    for value in [0, 14] {
+        let err = PokerCard::new(PokerSuit::Clubs, value).unwrap_err();
+        println!("PokerCard::new(Clubs, {}): {}", value, err);
+    }
+
+    assert_eq!("10h".parse(), Ok(ten));
+    assert_eq!("TH".parse(), Ok(ten));
+    assert_eq!("A♠".parse(), Ok(ace));
+    for bad in ["", "1S", "11H", "AX", "10"] {
+        let err = bad.parse::<PokerCard>().unwrap_err();
+        println!("{:?}: {}", bad, err);
+    }
+
+    // 52 张牌打印之后都能解析回来
+    for card in Deck::new().cards() {
+        assert_eq!(card.to_string().parse(), Ok(*card));
+        assert_eq!(format!("{:#}", card).parse(), Ok(*card));
+    }
+    println!("{}", join_cards(&parse_cards("AS KD qc jh 10s").unwrap()));
+}
+
+/// # 洗牌与发牌
+fn shuffle_and_deal() {
+    let deck = Deck::new();
+    println!("新牌：{} ... {}", join_cards(&deck.cards()[..5]), join_cards(&deck.cards()[47..]));
+    assert_eq!(deck.len(), 52);
+    assert_eq!(deck.cards().iter().collect::<HashSet<_>>().len(), 52);
+
+    let mut deck = Deck::shuffled(2024);
+    assert_eq!(deck, Deck::shuffled(2024));
+    assert_ne!(deck, Deck::shuffled(2025));
+    println!("洗牌后：{} ...", join_cards(&deck.cards()[..8]));
+
+    let players: Vec<Vec<PokerCard>> = (0..4).map(|_| deck.deal(2).unwrap()).collect();
+    let board = deck.deal(5).unwrap();
+    for (i, hole) in players.iter().enumerate() {
+        println!("玩家 {}：{}", i + 1, join_cards(hole));
+    }
+    println!("公共牌：{}，剩余 {} 张", join_cards(&board), deck.len());
+
+    let err = deck.deal(40).unwrap_err();
+    println!("{}", err);
+    assert_eq!(deck.len(), 39);
+    while deck.deal_one().is_some() {}
+    assert!(deck.is_empty());
+}
+
+/// # 五张牌的牌型
+fn five_card_hands() {
+    let examples = [
+        ("10S JS QS KS AS", Category::StraightFlush),
+        ("AH 2H 3H 4H 5H", Category::StraightFlush),
+        ("9C 9D 9H 9S 2D", Category::FourOfAKind),
+        ("KC KD KH 4S 4D", Category::FullHouse),
+        ("2D 7D 9D JD KD", Category::Flush),
+        ("10C JD QH KS AC", Category::Straight),
+        ("AD 2C 3H 4S 5D", Category::Straight),
+        ("7C 7D 7H KS 2D", Category::ThreeOfAKind),
+        ("QC QD 7H 7S AD", Category::TwoPair),
+        ("JC JD 9H 5S 2D", Category::OnePair),
+        ("QC 10D 7H 5S 2D", Category::HighCard),
+        // A 只能在两头，KA23 4 不是顺子
+        ("KC AD 2H 3S 4D", Category::HighCard),
+    ];
+    for (cards, category) in examples {
+        let hand = hand(cards);
+        println!("{:<18} => {}", cards, hand);
+        assert_eq!(hand.value.category(), category);
+    }
+
+    println!("{}", best_hand(&parse_cards("AS KS QS JS").unwrap()).unwrap_err());
+    println!("{}", best_hand(&parse_cards("AS KS QS JS AS").unwrap()).unwrap_err());
+}
+
+/// # 同牌型比较
+/// 牌型相同时按 `ranks` 逐个比较，花色不影响大小
+fn tie_breaking() {
+    let cases = [
+        // 左边大于右边
+        ("2C 3D 4H 5S 6D", "AD 2C 3H 4S 5D"),
+        ("AH 2H 3H 4H 5H", "KC KD KH KS AD"),
+        ("2C 2D 2H 2S 3D", "AC AD AH KS KD"),
+        ("3C 3D 3H 2S 2D", "2C 2D 2H AS AD"),
+        ("AD 6D 5D 4D 2D", "KH QH JH 9H 8H"),
+        ("AD KD 5D 4D 3D", "AH QH JH 9H 8H"),
+        ("KC KD 2H 2S 3D", "QC QD JH JS AD"),
+        ("KC KD 5H 5S 3D", "KH KS 4H 4S AD"),
+        ("KC KD 5H 5S 7D", "KH KS 5C 5D 6D"),
+        ("9C 9D AH 3S 2D", "9H 9S KH QS JD"),
+        ("9C 9D AH 8S 2D", "9H 9S AC 7S 6D"),
+        ("AC QD 9H 7S 3D", "AH QS 9D 7C 2D"),
+    ];
+    for (left, right) in cases {
+        let (a, b) = (hand(left).value, hand(right).value);
+        println!("{} > {}", a, b);
+        assert!(a > b, "{} 应该大于 {}", left, right);
+    }
+
+    // 点数全同、花色不同，一样大
+    let (a, b) = (hand("AC KD 9H 7S 3D").value, hand("AH KS 9D 7C 3H").value);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+}
+
+/// # 七张牌（德州扑克）
+/// 两张手牌加五张公共牌，从中选最大的五张
+fn seven_card_hands() {
+    let board = "KS QS JS 2D 2C";
+    let players = ["AS 3H", "2H 2S", "KD KH", "9C 8D"];
+    let hands: Vec<Vec<PokerCard>> = players.iter().map(|hole| parse_cards(&format!("{} {}", hole, board)).unwrap()).collect();
+    println!("公共牌：{}", board);
+    for (hole, cards) in players.iter().zip(&hands) {
+        println!("{} => {}", hole, best_hand(cards).unwrap());
+    }
+    let best = winners(&hands).unwrap();
+    println!("赢家：{:?}", best.iter().map(|&i| players[i]).collect::<Vec<_>>());
+    assert_eq!(best, vec![1]);
+
+    // 最好的五张都在公共牌上，平分底池
+    let board = "10H JD QC KS AD";
+    let hands: Vec<Vec<PokerCard>> = ["2C 3C", "4H 5H"].iter().map(|hole| parse_cards(&format!("{} {}", hole, board)).unwrap()).collect();
+    assert_eq!(winners(&hands).unwrap(), vec![0, 1]);
+    println!("{} => 平分", board);
+
+    // 七张里有两个三条：取大的三条加小的一对
+    let full = hand("8C 8D 8H 3S 3D 3C 2D");
+    println!("{}", full);
+    assert_eq!(full.value.ranks(), &[8, 3]);
+    // 六张同花：取最大的五张
+    let flush = hand("2H 4H 6H 8H 10H QH AS");
+    println!("{}", flush);
+    assert_eq!(flush.value.ranks(), &[12, 10, 8, 6, 4]);
+}
+
+/// # 牌型统计
+/// 枚举全部 C(52,5) = 2598960 种五张牌，每种牌型的数量和理论值一致
+fn hand_statistics() {
+    const EXPECTED: [u32; 9] = [1302540, 1098240, 123552, 54912, 10200, 5108, 3744, 624, 40];
+    let cards = Deck::new().cards().to_vec();
+    let mut counts = [0u32; 9];
+    for a in 0..52 {
+        for b in a + 1..52 {
+            for c in b + 1..52 {
+                for d in c + 1..52 {
+                    for e in d + 1..52 {
+                        let value = evaluate5(&[cards[a], cards[b], cards[c], cards[d], cards[e]]);
+                        counts[value.category() as usize] += 1;
+                    }
+                }
+            }
+        }
+    }
+    let total: u32 = counts.iter().sum();
+    for (category, count) in Category::ALL.iter().zip(counts).rev() {
+        println!("{:>8} {:>9.5}% {}", count, count as f64 * 100.0 / total as f64, category.name());
+    }
+    assert_eq!(counts, EXPECTED);
+}
@@ -1,9 +1,12 @@
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::fs::File;
-use std::io::{Error, Read};
+use std::io::{self, Error, Read, Write};
 use std::ops::Add;
 use num::abs;
+use crate::ch2::json;
+use crate::ch2::mod_int::ModInt;
+use crate::ch2::vfs::{self, OpenOptions, Vfs};
 
 pub fn main() {
     let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
@@ -105,13 +108,33 @@ struct Point2<T> {
 ///     Err(E),
 /// }
 /// ```
+/// * 读取文件得到 `Result<String, io::Error>`
+/// * 解析 JSON 得到 `Result<JsonValue, JsonError>`
+/// * 路径查询得到 `Option<&JsonValue>`
 fn enum_generics() {
-    match read_file_to_string("test.json") {
-        Ok(contents) => {
-            println!("{}", contents);
-        }
-        Err(error) => {
-            println!("Error reading file: {}", error);
+    // test.json 在项目根目录，missing.json 不存在，broken.json 的内容格式错误
+    let broken = "{\n  \"name\": \"head_first_rust\",\n  \"edition\": 2021,\n}";
+    for path in ["test.json", "missing.json", "broken.json"] {
+        let contents = if path == "broken.json" { Ok(broken.to_string()) } else { read_file_to_string(path) };
+        match contents {
+            Ok(contents) => match json::parse(&contents) {
+                Ok(value) => {
+                    println!("{}", value);
+                    for query in ["name", "chapters[1].title", "chapters[2].lessons[0]", "homepage", "authors[0]"] {
+                        match value.query(query) {
+                            Some(v) => println!("{} = {}", query, v),
+                            None => println!("{} 不存在", query),
+                        }
+                    }
+                }
+                Err(error) => {
+                    println!("Error parsing {}: {}", path, error);
+                    println!("{}", json::show_error(&contents, &error));
+                }
+            },
+            Err(error) => {
+                println!("Error reading file: {}", error);
+            }
         }
     }
 }
@@ -186,6 +209,10 @@ impl Point<f64, f64> {
 fn const_generics() {
     let array_wrapper = ArrayWrapper::new([1, 2, 3]);
     println!("Element at index 2: {:?}", array_wrapper.get(2));
+
+    // const 泛型参数也可以是数值意义上的“配置”：模数不同就是不同的类型
+    let x = ModInt::<7>::new(10);
+    println!("10 mod 7 = {}", x);
 }
 
 
@@ -242,18 +269,19 @@ impl<T: Add<T, Output=T>> Add for NewPoint<T> {
 
 
 #[derive(Debug)]
-#[allow(dead_code)]
 enum FileState {
     Open,
     Closed,
 }
 
+/// # 运行在内存文件系统上的文件
+/// 打开后持有 `vfs::File` 句柄，读到的内容保存在 `data` 中
 #[derive(Debug)]
-#[allow(dead_code)]
 struct MyFile {
     name: String,
     data: Vec<u8>,
     state: FileState,
+    handle: Option<vfs::File>,
 }
 
 impl MyFile {
@@ -262,7 +290,44 @@ impl MyFile {
             name: String::from(name),
             data: vec![],
             state: FileState::Closed,
+            handle: None,
+        }
+    }
+
+    /// 文件不存在时创建，已经打开时返回错误
+    pub fn open(&mut self, fs: &mut Vfs) -> io::Result<()> {
+        if let FileState::Open = self.state {
+            return Err(io::Error::other(format!("{} 已经打开", self.name)));
         }
+        let options = OpenOptions::new().read(true).write(true).create(true);
+        self.handle = Some(fs.open(&self.name, &options)?);
+        self.state = FileState::Open;
+        Ok(())
+    }
+
+    fn handle(&mut self) -> io::Result<&mut vfs::File> {
+        self.handle
+            .as_mut()
+            .ok_or_else(|| io::Error::other(format!("{} 没有打开", self.name)))
+    }
+
+    /// 从当前位置读到文件末尾，追加到 data，返回读到的字节数
+    pub fn read(&mut self) -> io::Result<usize> {
+        let mut buf = Vec::new();
+        let n = self.handle()?.read_to_end(&mut buf)?;
+        self.data.extend(buf);
+        Ok(n)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.handle()?.write_all(bytes)
+    }
+
+    pub fn close(&mut self) -> io::Result<()> {
+        self.handle()?.close()?;
+        self.handle = None;
+        self.state = FileState::Closed;
+        Ok(())
     }
 }
 
@@ -281,6 +346,87 @@ impl Display for MyFile {
     }
 }
 
+/// # 类型状态（typestate）版本的 MyFile
+/// * 上面的 MyFile 在运行时检查状态，对关闭的文件调用 read 只能得到 Err
+/// * 这里把状态放进类型参数：`MyFile<Closed>` 没有 read/write 方法，误用在编译期就会报错
+/// * `open` 消耗关闭的文件返回打开的文件，`close` 再变回去，旧的值被移走后无法继续使用
+mod typestate {
+    use std::fmt::{Display, Formatter};
+    use std::fs;
+    use std::io::{self, Read, Write};
+    use std::path::{Path, PathBuf};
+
+    /// 打开状态持有真正的文件句柄
+    #[derive(Debug)]
+    pub struct Open {
+        file: fs::File,
+    }
+
+    #[derive(Debug)]
+    pub struct Closed;
+
+    /// 状态在 Display 中的名称，与运行时版本的 FileState 一致
+    pub trait State {
+        const NAME: &'static str;
+    }
+
+    impl State for Open {
+        const NAME: &'static str = "OPEN file";
+    }
+
+    impl State for Closed {
+        const NAME: &'static str = "CLOSED file";
+    }
+
+    #[derive(Debug)]
+    pub struct MyFile<S: State> {
+        path: PathBuf,
+        data: Vec<u8>,
+        state: S,
+    }
+
+    impl<S: State> MyFile<S> {
+        pub fn data(&self) -> &[u8] {
+            &self.data
+        }
+    }
+
+    impl MyFile<Closed> {
+        pub fn new(path: impl AsRef<Path>) -> Self {
+            MyFile { path: path.as_ref().to_path_buf(), data: vec![], state: Closed }
+        }
+
+        /// 文件不存在时创建
+        pub fn open(self) -> io::Result<MyFile<Open>> {
+            let file = fs::OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&self.path)?;
+            Ok(MyFile { path: self.path, data: self.data, state: Open { file } })
+        }
+    }
+
+    impl MyFile<Open> {
+        /// 从当前位置读到文件末尾，追加到 data，返回读到的字节数
+        pub fn read(&mut self) -> io::Result<usize> {
+            self.state.file.read_to_end(&mut self.data)
+        }
+
+        pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+            self.state.file.write_all(bytes)
+        }
+
+        /// 写入的内容落盘后关闭，句柄随 Open 一起被 drop
+        pub fn close(self) -> io::Result<MyFile<Closed>> {
+            self.state.file.sync_all()?;
+            Ok(MyFile { path: self.path, data: self.data, state: Closed })
+        }
+    }
+
+    impl<S: State> Display for MyFile<S> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "<{} ({})>", self.path.display(), S::NAME)
+        }
+    }
+}
+
 fn example() {
     let p1 = NewPoint::new(1.0, 2.0);
     let p2 = NewPoint::new(2.0, 1.0);
@@ -291,7 +437,40 @@ fn example() {
     println!("{:?}", add(p3, p4));
 
     // 文件状态
-    let f = MyFile::new("test.json");
+    let mut fs = Vfs::new();
+    fs.write("/test.json", r#"{"name": "rust"}"#).unwrap();
+    let mut f = MyFile::new("/test.json");
     println!("{:?}", f); // Debug trait
     println!("{}", f); // Display trait
+
+    f.open(&mut fs).unwrap();
+    println!("{}", f);
+    let n = f.read().unwrap();
+    println!("读取 {} 字节：{}", n, String::from_utf8_lossy(&f.data));
+    f.write(b"\n").unwrap();
+    assert!(f.open(&mut fs).is_err()); // 不能重复打开
+    f.close().unwrap();
+    println!("{}", f);
+    assert!(f.read().is_err()); // 关闭后不能读
+    assert_eq!(fs.read_to_string("/test.json").unwrap(), "{\"name\": \"rust\"}\n");
+
+    // 类型状态版本，读写真实的文件
+    let path = std::env::temp_dir().join("head_first_rust_my_file.txt");
+    let _ = std::fs::remove_file(&path);
+    let f = typestate::MyFile::new(&path);
+    println!("{}", f);
+    let mut f = f.open().unwrap();
+    println!("{}", f);
+    f.write(b"hello typestate").unwrap();
+    let f = f.close().unwrap();
+    println!("{}", f);
+    // f.read(); // 编译错误：MyFile<Closed> 没有 read 方法
+    // f.close(); // 编译错误：已经关闭的文件不能再关闭
+
+    let mut f = f.open().unwrap();
+    let n = f.read().unwrap();
+    println!("读取 {} 字节：{}", n, String::from_utf8_lossy(f.data()));
+    assert_eq!(f.data(), b"hello typestate");
+    f.close().unwrap();
+    std::fs::remove_file(&path).unwrap();
 }
\ No newline at end of file
@@ -0,0 +1,684 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Index;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("解析与打印", Box::new(|| parse_and_print())),
+        ("路径查询", Box::new(|| path_query())),
+        ("错误位置", Box::new(|| errors())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # JSON 值
+/// * 数字统一用 f64 保存，与 JavaScript 一致，超过 2^53 的整数会损失精度
+/// * 对象保留键的原始顺序，重复的键后出现的覆盖先出现的
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+/// 用下标访问不存在的键或元素时返回的值
+static NULL: JsonValue = JsonValue::Null;
+
+impl JsonValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// 只有没有小数部分、且在 i64 范围内的数字才能转换
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonValue::Number(n) if n.fract() == 0.0 && *n >= i64::MIN as f64 && *n < i64::MAX as f64 => Some(*n as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Vec<(String, JsonValue)>> {
+        match self {
+            JsonValue::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// 对象中键对应的值
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.as_object()?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// # 路径查询
+    /// 语法类似 JavaScript：`a.b[0].c`，键中有 `.` `[` 等字符时写成 `a["x.y"]`；
+    /// 路径不存在或语法错误时返回 None
+    pub fn query(&self, path: &str) -> Option<&JsonValue> {
+        let mut current = self;
+        for segment in parse_path(path)? {
+            current = match segment {
+                PathSegment::Key(key) => current.get(&key)?,
+                PathSegment::Index(i) => current.as_array()?.get(i)?,
+            };
+        }
+        Some(current)
+    }
+
+    /// 两个空格缩进的多行格式，等价于 `format!("{:#}", value)`
+    pub fn to_pretty(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// 没有多余空白的单行格式，等价于 `value.to_string()`
+    pub fn to_compact(&self) -> String {
+        self.to_string()
+    }
+
+    fn write(&self, out: &mut Formatter<'_>, pretty: bool, depth: usize) -> std::fmt::Result {
+        let newline = |out: &mut Formatter<'_>, depth: usize| -> std::fmt::Result {
+            if pretty {
+                write!(out, "\n{}", "  ".repeat(depth))?;
+            }
+            Ok(())
+        };
+        match self {
+            JsonValue::Null => write!(out, "null"),
+            JsonValue::Bool(b) => write!(out, "{}", b),
+            JsonValue::Number(n) => write_number(out, *n),
+            JsonValue::String(s) => write_string(out, s),
+            JsonValue::Array(items) if items.is_empty() => write!(out, "[]"),
+            JsonValue::Array(items) => {
+                write!(out, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(out, ",")?;
+                    }
+                    newline(out, depth + 1)?;
+                    item.write(out, pretty, depth + 1)?;
+                }
+                newline(out, depth)?;
+                write!(out, "]")
+            }
+            JsonValue::Object(members) if members.is_empty() => write!(out, "{{}}"),
+            JsonValue::Object(members) => {
+                write!(out, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(out, ",")?;
+                    }
+                    newline(out, depth + 1)?;
+                    write_string(out, key)?;
+                    write!(out, "{}", if pretty { ": " } else { ":" })?;
+                    value.write(out, pretty, depth + 1)?;
+                }
+                newline(out, depth)?;
+                write!(out, "}}")
+            }
+        }
+    }
+}
+
+/// 整数不带小数点；很大或很小的数用科学计数法；NaN 和无穷大不是合法的 JSON，输出 null
+fn write_number(out: &mut Formatter<'_>, n: f64) -> std::fmt::Result {
+    if !n.is_finite() {
+        write!(out, "null")
+    } else if n.fract() == 0.0 && n.abs() < 1e16 {
+        write!(out, "{}", n as i64)
+    } else if n.abs() >= 1e16 || n.abs() < 1e-6 {
+        write!(out, "{:e}", n)
+    } else {
+        write!(out, "{}", n)
+    }
+}
+
+fn write_string(out: &mut Formatter<'_>, s: &str) -> std::fmt::Result {
+    write!(out, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            '\n' => write!(out, "\\n")?,
+            '\r' => write!(out, "\\r")?,
+            '\t' => write!(out, "\\t")?,
+            '\u{8}' => write!(out, "\\b")?,
+            '\u{c}' => write!(out, "\\f")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{}", c)?,
+        }
+    }
+    write!(out, "\"")
+}
+
+/// `{}` 输出单行格式，`{:#}` 输出缩进格式
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write(f, f.alternate(), 0)
+    }
+}
+
+/// 键不存在时返回 `Null`，而不是 panic
+impl Index<&str> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, key: &str) -> &JsonValue {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+/// 下标越界或不是数组时返回 `Null`
+impl Index<usize> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: usize) -> &JsonValue {
+        self.as_array().and_then(|items| items.get(index)).unwrap_or(&NULL)
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(n: f64) -> Self {
+        JsonValue::Number(n)
+    }
+}
+
+impl From<i64> for JsonValue {
+    fn from(n: i64) -> Self {
+        JsonValue::Number(n as f64)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> Self {
+        JsonValue::String(s.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(s: String) -> Self {
+        JsonValue::String(s)
+    }
+}
+
+impl From<Vec<JsonValue>> for JsonValue {
+    fn from(items: Vec<JsonValue>) -> Self {
+        JsonValue::Array(items)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+fn parse_path(path: &str) -> Option<Vec<PathSegment>> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+    let mut first = true;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']')?;
+            let inner = &after[..end];
+            let segment = match inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                Some(key) => PathSegment::Key(key.to_string()),
+                None => PathSegment::Index(inner.parse().ok()?),
+            };
+            segments.push(segment);
+            rest = &after[end + 1..];
+        } else {
+            // 第一个键前面没有 `.`，之后的键必须以 `.` 开头
+            let after = if first { rest } else { rest.strip_prefix('.')? };
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return None;
+            }
+            segments.push(PathSegment::Key(after[..end].to_string()));
+            rest = &after[end..];
+        }
+        first = false;
+    }
+    Some(segments)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonErrorKind {
+    UnexpectedEnd,
+    /// `expected` 描述期望的内容，例如 "`,` 或 `]`"
+    Expected { expected: &'static str, found: char },
+    InvalidNumber,
+    InvalidEscape(char),
+    /// `\u` 后面不是 4 位十六进制数
+    InvalidUnicodeEscape,
+    /// 没有配对的 UTF-16 代理项，例如单独的 `\uD83E`
+    LoneSurrogate,
+    /// 字符串中不能直接出现 U+0000 ~ U+001F
+    ControlCharacter(char),
+    TrailingCharacters,
+    /// 嵌套层数超过 [`MAX_DEPTH`]
+    TooDeep,
+}
+
+/// # 解析错误
+/// 行号、列号从 1 开始，列号按字符计数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: JsonErrorKind,
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "第 {} 行第 {} 列：", self.line, self.column)?;
+        match self.kind {
+            JsonErrorKind::UnexpectedEnd => write!(f, "输入意外结束"),
+            JsonErrorKind::Expected { expected, found } => write!(f, "期望 {}，实际是 {:?}", expected, found),
+            JsonErrorKind::InvalidNumber => write!(f, "数字格式错误"),
+            JsonErrorKind::InvalidEscape(c) => write!(f, "未知的转义 \\{}", c),
+            JsonErrorKind::InvalidUnicodeEscape => write!(f, "\\u 后面需要 4 位十六进制数"),
+            JsonErrorKind::LoneSurrogate => write!(f, "没有配对的代理项"),
+            JsonErrorKind::ControlCharacter(c) => write!(f, "字符串中不能出现控制字符 {:?}", c),
+            JsonErrorKind::TrailingCharacters => write!(f, "JSON 值之后还有多余的字符"),
+            JsonErrorKind::TooDeep => write!(f, "嵌套超过 {} 层", MAX_DEPTH),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// 最大嵌套层数，防止恶意输入导致栈溢出
+pub const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+/// # 解析 JSON 文本
+/// 严格遵循 RFC 8259：不允许注释、尾逗号、单引号字符串
+pub fn parse(src: &str) -> Result<JsonValue, JsonError> {
+    let mut parser = Parser { src, pos: 0 };
+    parser.skip_whitespace();
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.pos < src.len() {
+        return Err(parser.error(JsonErrorKind::TrailingCharacters));
+    }
+    Ok(value)
+}
+
+impl Parser<'_> {
+    /// 当前位置的行号、列号
+    fn error(&self, kind: JsonErrorKind) -> JsonError {
+        let before = &self.src[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        JsonError { line, column: before[line_start..].chars().count() + 1, kind }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// 当前字符不符合预期时的错误
+    fn unexpected(&self, expected: &'static str) -> JsonError {
+        match self.peek() {
+            Some(found) => self.error(JsonErrorKind::Expected { expected, found }),
+            None => self.error(JsonErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), JsonError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        if depth >= MAX_DEPTH {
+            return Err(self.error(JsonErrorKind::TooDeep));
+        }
+        match self.peek() {
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => Ok(JsonValue::String(self.string()?)),
+            Some('-' | '0'..='9') => self.number(),
+            Some('t') => self.literal("true", JsonValue::Bool(true)),
+            Some('f') => self.literal("false", JsonValue::Bool(false)),
+            Some('n') => self.literal("null", JsonValue::Null),
+            _ => Err(self.unexpected("JSON 值")),
+        }
+    }
+
+    fn literal(&mut self, word: &'static str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        for expected in word.chars() {
+            if self.peek() != Some(expected) {
+                return Err(self.unexpected(word));
+            }
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+
+    fn object(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        self.pos += 1;
+        let mut members: Vec<(String, JsonValue)> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.unexpected("字符串形式的键"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':', "`:`")?;
+            self.skip_whitespace();
+            let value = self.value(depth + 1)?;
+            match members.iter_mut().find(|(k, _)| *k == key) {
+                Some(member) => member.1 = value,
+                None => members.push((key, value)),
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(members));
+                }
+                _ => return Err(self.unexpected("`,` 或 `}`")),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.unexpected("`,` 或 `]`")),
+            }
+        }
+    }
+
+    /// `-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?`
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| -> Result<(), JsonError> {
+            if !matches!(p.peek(), Some('0'..='9')) {
+                return Err(p.unexpected("数字"));
+            }
+            while matches!(p.peek(), Some('0'..='9')) {
+                p.pos += 1;
+            }
+            Ok(())
+        };
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        if self.peek() == Some('0') {
+            self.pos += 1;
+            // 不允许前导零，例如 012
+            if matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.error(JsonErrorKind::InvalidNumber));
+            }
+        } else {
+            digits(self)?;
+        }
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            digits(self)?;
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            digits(self)?;
+        }
+        match self.src[start..self.pos].parse::<f64>() {
+            Ok(n) if n.is_finite() => Ok(JsonValue::Number(n)),
+            // 例如 1e400 超出 f64 范围
+            _ => {
+                self.pos = start;
+                Err(self.error(JsonErrorKind::InvalidNumber))
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let hex = self.src.get(self.pos..self.pos + 4).filter(|h| h.chars().all(|c| c.is_ascii_hexdigit()));
+        match hex {
+            Some(hex) => {
+                self.pos += 4;
+                Ok(u32::from_str_radix(hex, 16).unwrap())
+            }
+            None => Err(self.error(JsonErrorKind::InvalidUnicodeEscape)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            match self.bump() {
+                None => return Err(self.error(JsonErrorKind::UnexpectedEnd)),
+                Some('"') => return Ok(out),
+                Some('\\') => {
+                    let c = self.bump().ok_or(self.error(JsonErrorKind::UnexpectedEnd))?;
+                    match c {
+                        '"' | '\\' | '/' => out.push(c),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        'u' => out.push(self.unicode_escape(start)?),
+                        c => {
+                            self.pos = start;
+                            return Err(self.error(JsonErrorKind::InvalidEscape(c)));
+                        }
+                    }
+                }
+                Some(c) if (c as u32) < 0x20 => {
+                    self.pos = start;
+                    return Err(self.error(JsonErrorKind::ControlCharacter(c)));
+                }
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    /// `\uXXXX`，超出 BMP 的字符写成两个代理项 `\uD83E\uDD80`
+    fn unicode_escape(&mut self, start: usize) -> Result<char, JsonError> {
+        let high = self.hex4()?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if !self.src[self.pos..].starts_with("\\u") {
+                    self.pos = start;
+                    return Err(self.error(JsonErrorKind::LoneSurrogate));
+                }
+                self.pos += 2;
+                let low = self.hex4()?;
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    self.pos = start;
+                    return Err(self.error(JsonErrorKind::LoneSurrogate));
+                }
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+            }
+            0xDC00..=0xDFFF => {
+                self.pos = start;
+                return Err(self.error(JsonErrorKind::LoneSurrogate));
+            }
+            code => code,
+        };
+        Ok(char::from_u32(code).expect("surrogates are handled above"))
+    }
+}
+
+/// 打印出错的那一行，并在下方用 `^` 标出位置
+pub fn show_error(src: &str, error: &JsonError) -> String {
+    let line = src.lines().nth(error.line - 1).unwrap_or("");
+    let prefix: String = line.chars().take(error.column - 1).collect();
+    format!("{}\n{}^", line, " ".repeat(crate::ch2::unicode_inspect::display_width(&prefix)))
+}
+
+const SAMPLE: &str = r#"{
+    "name": "head_first_rust",
+    "tags": ["rust", "教程", "\u2764\uFE0F"],
+    "chapters": [
+        {"id": 2, "title": "基本类型", "lessons": 8, "done": true},
+        {"id": 8, "title": "泛型和特征", "lessons": 3, "done": false}
+    ],
+    "rating": 4.5,
+    "escape": "tab\there \"quoted\" \ud83e\udd80",
+    "a.b": {"c": [10, 20, -3e-8, 1.5E20]},
+    "license": null
+}"#;
+
+fn parse_and_print() {
+    let value = parse(SAMPLE).unwrap();
+    println!("{:#}", value);
+    println!("{}", value);
+
+    // 输出的文本重新解析后得到同样的值
+    assert_eq!(parse(&value.to_compact()).unwrap(), value);
+    assert_eq!(parse(&value.to_pretty()).unwrap(), value);
+    assert_eq!(value["escape"].as_str(), Some("tab\there \"quoted\" 🦀"));
+    assert_eq!(value["tags"][2].as_str(), Some("❤️"));
+    assert_eq!(value["a.b"]["c"][3].as_f64(), Some(1.5e20));
+    assert!(value["license"].is_null());
+    assert!(value["不存在"][5].is_null());
+
+    assert_eq!(parse("[1, 2.50, -0, 1e2, {}]").unwrap().to_compact(), "[1,2.5,0,100,{}]");
+    assert_eq!(parse(r#"{"k": 1, "k": 2}"#).unwrap().to_compact(), r#"{"k":2}"#);
+    assert_eq!(JsonValue::String("\u{1}\n".to_string()).to_compact(), r#""\u0001\n""#);
+}
+
+fn path_query() {
+    let value = parse(SAMPLE).unwrap();
+    let paths = [
+        "name",
+        "tags[1]",
+        "chapters[1].title",
+        "chapters[0].done",
+        r#"["a.b"].c[0]"#,
+        "chapters[5].title",
+        "chapters.title",
+        "tags[x]",
+    ];
+    for path in paths {
+        match value.query(path) {
+            Some(v) => println!("{:<20} = {}", path, v),
+            None => println!("{:<20} 不存在", path),
+        }
+    }
+    assert_eq!(value.query("chapters[1].title").and_then(JsonValue::as_str), Some("泛型和特征"));
+    assert_eq!(value.query("chapters[0].lessons").and_then(JsonValue::as_i64), Some(8));
+    assert_eq!(value.query(r#"["a.b"].c[0]"#).and_then(JsonValue::as_i64), Some(10));
+    assert_eq!(value.query(""), Some(&value));
+    assert_eq!(value.query("chapters[5]"), None);
+    assert_eq!(value.query("tags..x"), None);
+}
+
+fn errors() {
+    let cases = [
+        ("{\"a\": 1,}", JsonErrorKind::Expected { expected: "字符串形式的键", found: '}' }, (1, 9)),
+        ("[1, 2\n 3]", JsonErrorKind::Expected { expected: "`,` 或 `]`", found: '3' }, (2, 2)),
+        ("{\"名字\" 1}", JsonErrorKind::Expected { expected: "`:`", found: '1' }, (1, 7)),
+        ("[01]", JsonErrorKind::InvalidNumber, (1, 3)),
+        ("[1.]", JsonErrorKind::Expected { expected: "数字", found: ']' }, (1, 4)),
+        ("[1e400]", JsonErrorKind::InvalidNumber, (1, 2)),
+        ("\"a\\qb\"", JsonErrorKind::InvalidEscape('q'), (1, 3)),
+        ("\"\\u12\"", JsonErrorKind::InvalidUnicodeEscape, (1, 4)),
+        ("\"\\ud83e!\"", JsonErrorKind::LoneSurrogate, (1, 2)),
+        ("\"a\tb\"", JsonErrorKind::ControlCharacter('\t'), (1, 3)),
+        ("[true, nul]", JsonErrorKind::Expected { expected: "null", found: ']' }, (1, 11)),
+        ("{\"a\": [1, 2]", JsonErrorKind::UnexpectedEnd, (1, 13)),
+        ("{} {}", JsonErrorKind::TrailingCharacters, (1, 4)),
+        ("'single'", JsonErrorKind::Expected { expected: "JSON 值", found: '\'' }, (1, 1)),
+    ];
+    for (src, kind, (line, column)) in cases {
+        let err = parse(src).unwrap_err();
+        println!("{}", show_error(src, &err));
+        println!("错误：{}\n", err);
+        assert_eq!(err, JsonError { line, column, kind });
+    }
+
+    let deep = format!("{}{}", "[".repeat(MAX_DEPTH + 1), "]".repeat(MAX_DEPTH + 1));
+    assert_eq!(parse(&deep).unwrap_err().kind, JsonErrorKind::TooDeep);
+    let ok = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+    assert!(parse(&ok).is_ok());
+}
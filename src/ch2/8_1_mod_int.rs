@@ -0,0 +1,394 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("const 泛型模数", Box::new(|| const_modulus())),
+        ("快速幂与逆元", Box::new(|| pow_and_inverse())),
+        ("运行时模数", Box::new(|| runtime_modulus())),
+        ("扩展欧几里得", Box::new(|| ext_gcd())),
+        ("Miller–Rabin 素性测试", Box::new(|| primality())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// 常用的大素数模数
+pub const MOD_998244353: u64 = 998_244_353;
+pub const MOD_1E9_7: u64 = 1_000_000_007;
+
+/// # 扩展欧几里得算法
+/// 返回 `(g, x, y)`，满足 `a * x + b * y = g = gcd(a, b)`
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return if a < 0 { (-a, -1, 0) } else { (a, 1, 0) };
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    (g, y, x - (a / b) * y)
+}
+
+/// 模 m 下的乘法逆元，a 与 m 不互素时返回 None
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    let (g, x, _) = extended_gcd(a as i128, m as i128);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+/// 快速幂：按指数的二进制位逐位平方
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    if m == 1 {
+        return 0;
+    }
+    let mut base = base % m;
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// 乘法先放大到 u128，避免溢出
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// # Miller–Rabin 素性测试
+/// 对 u64 范围，使用前 12 个素数作为底数即可得到确定性的结果
+pub fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // n - 1 = d * 2^s，d 为奇数
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'witness: for a in BASES {
+        let mut x = mod_pow(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// # 模 M 整数
+/// * 模数是 const 泛型参数，不同模数的值是不同的类型，不能混用
+/// * 内部值始终在 `[0, M)` 范围内
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: impl Into<ModInt<M>>) -> Self {
+        value.into()
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        Self(mod_pow(self.0, exp, M))
+    }
+
+    /// 乘法逆元，与 M 不互素时返回 None
+    pub fn inv(self) -> Option<Self> {
+        mod_inverse(self.0, M).map(Self)
+    }
+}
+
+macro_rules! impl_mod_int_from {
+    ($($t:ty),*) => {
+        $(
+            impl<const M: u64> From<$t> for ModInt<M> {
+                fn from(v: $t) -> Self {
+                    Self((v as i128).rem_euclid(M as i128) as u64)
+                }
+            }
+        )*
+    };
+}
+
+impl_mod_int_from!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl<const M: u64> From<u128> for ModInt<M> {
+    fn from(v: u128) -> Self {
+        Self((v % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(((self.0 as u128 + rhs.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(mul_mod(self.0, rhs.0, M))
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(if self.0 == 0 { 0 } else { M - self.0 })
+    }
+}
+
+/// 除法即乘以逆元
+///
+/// # Panics
+/// 除数与 M 不互素时 panic，与整数除以 0 一致
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv().expect("divisor is not invertible modulo M")
+    }
+}
+
+/// 为 `+=` 等复合赋值运算符统一实现
+macro_rules! impl_assign_ops {
+    ([$($g:tt)*] $ty:ty) => {
+        impl<$($g)*> AddAssign for $ty {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<$($g)*> SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<$($g)*> MulAssign for $ty {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<$($g)*> DivAssign for $ty {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+    };
+}
+
+impl_assign_ops!([const M: u64] ModInt<M>);
+
+/// # 运行时模数
+/// 模数在运行时才知道（例如读取用户输入）时使用，
+/// 两个操作数的模数必须相同
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl DynModInt {
+    pub fn new(value: i128, modulus: u64) -> Self {
+        assert!(modulus > 0, "modulus must be positive");
+        Self { value: value.rem_euclid(modulus as i128) as u64, modulus }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        Self { value: mod_pow(self.value, exp, self.modulus), ..self }
+    }
+
+    pub fn inv(self) -> Option<Self> {
+        mod_inverse(self.value, self.modulus).map(|value| Self { value, ..self })
+    }
+
+    fn check(&self, rhs: &Self) {
+        assert_eq!(self.modulus, rhs.modulus, "operands have different moduli");
+    }
+}
+
+impl Display for DynModInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+impl Add for DynModInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.check(&rhs);
+        let value = (self.value as u128 + rhs.value as u128) % self.modulus as u128;
+        Self { value: value as u64, ..self }
+    }
+}
+
+impl Sub for DynModInt {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for DynModInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.check(&rhs);
+        Self { value: mul_mod(self.value, rhs.value, self.modulus), ..self }
+    }
+}
+
+impl Neg for DynModInt {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let value = if self.value == 0 { 0 } else { self.modulus - self.value };
+        Self { value, ..self }
+    }
+}
+
+impl Div for DynModInt {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self.check(&rhs);
+        self * rhs.inv().expect("divisor is not invertible modulo the modulus")
+    }
+}
+
+impl_assign_ops!([] DynModInt);
+
+type Mint = ModInt<MOD_1E9_7>;
+
+fn const_modulus() {
+    let a = ModInt::<7>::new(10);
+    let b = ModInt::<7>::new(-3);
+    println!("10 ≡ {} (mod 7)，-3 ≡ {} (mod 7)", a, b);
+    println!("a + b = {}，a - b = {}，a * b = {}，-a = {}", a + b, a - b, a * b, -a);
+    assert_eq!((a + b).value(), 0);
+    assert_eq!((a * b).value(), 5);
+
+    // 不同模数是不同的类型
+    // let c = a + ModInt::<11>::new(1); //mismatched types: expected `ModInt<7>`, found `ModInt<11>`
+
+    let mut sum = Mint::new(0);
+    for i in 1..=100_000u64 {
+        sum += Mint::new(i * i);
+    }
+    println!("1² + 2² + ... + 100000² ≡ {} (mod 1e9+7)", sum);
+    let n = 100_000u128;
+    assert_eq!(sum, Mint::from(n * (n + 1) * (2 * n + 1) / 6));
+}
+
+fn pow_and_inverse() {
+    let two = Mint::new(2);
+    println!("2^100 ≡ {} (mod 1e9+7)", two.pow(100));
+
+    // 费马小定理：a^(p-1) ≡ 1 (mod p)
+    assert_eq!(two.pow(MOD_1E9_7 - 1), Mint::new(1));
+
+    let three = ModInt::<MOD_998244353>::new(3);
+    let inv = three.inv().unwrap();
+    println!("3 的逆元 (mod 998244353)：{}", inv);
+    assert_eq!(three * inv, ModInt::new(1));
+    assert_eq!(ModInt::<MOD_998244353>::new(1) / three, inv);
+
+    // 与模数不互素时没有逆元
+    assert_eq!(ModInt::<12>::new(8).inv(), None);
+    assert_eq!(ModInt::<12>::new(5).inv(), Some(ModInt::new(5)));
+}
+
+fn runtime_modulus() {
+    let m = 13;
+    let a = DynModInt::new(9, m);
+    let b = DynModInt::new(-5, m);
+    println!("{} + {} = {}", a, b, a + b);
+    println!("{} / {} = {}", a, b, a / b);
+    assert_eq!((a / b) * b, a);
+    assert_eq!(a.pow(12).value(), 1);
+
+    let mut x = DynModInt::new(1, m);
+    x *= a;
+    x -= b;
+    assert_eq!(x, DynModInt::new(1, m));
+}
+
+fn ext_gcd() {
+    let (g, x, y) = extended_gcd(240, 46);
+    println!("240 * {} + 46 * {} = {}", x, y, g);
+    assert_eq!(g, 2);
+    assert_eq!(240 * x + 46 * y, g);
+
+    assert_eq!(mod_inverse(17, 3120), Some(2753)); // RSA 教科书示例
+    assert_eq!(mod_inverse(6, 9), None);
+}
+
+fn primality() {
+    let small: Vec<u64> = (0..50).filter(|&n| is_prime(n)).collect();
+    println!("50 以内的素数：{:?}", small);
+
+    // 与试除法对比
+    let trial = |n: u64| n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+    assert!((0..10_000).all(|n| is_prime(n) == trial(n)));
+
+    for n in [MOD_998244353, MOD_1E9_7, 561, 3_215_031_751, u64::MAX, 18_446_744_073_709_551_557] {
+        println!("{} 是素数吗？{}", n, is_prime(n));
+    }
+    assert!(is_prime(18_446_744_073_709_551_557)); // u64 范围内最大的素数
+    assert!(!is_prime(561)); // Carmichael 数
+    assert!(!is_prime(3_215_031_751)); // 2、3、5、7 的强伪素数
+}
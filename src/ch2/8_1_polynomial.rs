@@ -0,0 +1,430 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+use num::complex::Complex;
+use num::rational::Ratio;
+use num::traits::NumOps;
+use num::{Integer, One, ToPrimitive, Zero};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("基本运算", Box::new(|| arithmetic())),
+        ("求值与求导", Box::new(|| eval_and_derivative())),
+        ("长除法与 GCD", Box::new(|| division_and_gcd())),
+        ("复数根", Box::new(|| complex_roots())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// 多项式系数需要满足的约束：能加减乘除、有 0 和 1
+pub trait Coefficient: Clone + PartialEq + Zero + One + NumOps {
+    /// 除法是否精确：整数、有理数是精确的，长除法时检查能否整除；
+    /// 浮点数每次除法都有舍入，`(1.0 / 49.0) * 49.0 != 1.0`，不做这个检查
+    const EXACT: bool = true;
+}
+
+macro_rules! impl_coefficient {
+    ($exact:literal: $($ty:ty),*) => {
+        $(impl Coefficient for $ty {
+            const EXACT: bool = $exact;
+        })*
+    };
+}
+
+impl_coefficient!(true: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_coefficient!(false: f32, f64);
+
+impl<T: Clone + Integer> Coefficient for Ratio<T> {}
+
+/// # 多项式
+/// * 系数按升幂存储：`coeffs[i]` 是 `x^i` 的系数
+/// * 末尾不保留 0，零多项式的系数为空
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T> {
+    coeffs: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolyError {
+    /// 除以零多项式
+    DivisionByZero,
+    /// 整数系数无法整除，例如 `x / 2x`
+    Inexact,
+}
+
+impl Display for PolyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolyError::DivisionByZero => write!(f, "除数是零多项式"),
+            PolyError::Inexact => write!(f, "系数无法整除"),
+        }
+    }
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// 按升幂传入系数
+    pub fn new(coeffs: Vec<T>) -> Self {
+        let mut p = Self { coeffs };
+        p.trim();
+        p
+    }
+
+    /// 按降幂传入系数，和书写顺序一致：`[3, -2, 1]` 是 `3x^2 - 2x + 1`
+    pub fn from_descending(mut coeffs: Vec<T>) -> Self {
+        coeffs.reverse();
+        Self::new(coeffs)
+    }
+
+    pub fn zero() -> Self {
+        Self { coeffs: vec![] }
+    }
+
+    /// `c · x^n`
+    pub fn monomial(c: T, n: usize) -> Self {
+        let mut coeffs = vec![T::zero(); n];
+        coeffs.push(c);
+        Self::new(coeffs)
+    }
+
+    fn trim(&mut self) {
+        while self.coeffs.last().is_some_and(|c| c.is_zero()) {
+            self.coeffs.pop();
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// 零多项式没有次数
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    pub fn leading(&self) -> Option<&T> {
+        self.coeffs.last()
+    }
+
+    /// 秦九韶算法（Horner）：`((a_n·x + a_{n-1})·x + ...)·x + a_0`
+    pub fn eval(&self, x: T) -> T {
+        self.coeffs.iter().rev().fold(T::zero(), |acc, c| acc * x.clone() + c.clone())
+    }
+
+    pub fn derivative(&self) -> Self {
+        let mut factor = T::zero();
+        let coeffs = self
+            .coeffs
+            .iter()
+            .skip(1)
+            .map(|c| {
+                factor = factor.clone() + T::one();
+                c.clone() * factor.clone()
+            })
+            .collect();
+        Self::new(coeffs)
+    }
+
+    /// # 长除法
+    /// 返回 `(商, 余数)`，满足 `self = 商 · divisor + 余数` 且余数次数小于除数
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), PolyError> {
+        let lead = divisor.leading().ok_or(PolyError::DivisionByZero)?;
+        let d = divisor.coeffs.len();
+        let mut rem = self.coeffs.clone();
+        if rem.len() < d {
+            return Ok((Self::zero(), self.clone()));
+        }
+        let mut quotient = vec![T::zero(); rem.len() - d + 1];
+        for i in (0..quotient.len()).rev() {
+            let top = rem[i + d - 1].clone();
+            let q = top.clone() / lead.clone();
+            if T::EXACT && q.clone() * lead.clone() != top {
+                return Err(PolyError::Inexact);
+            }
+            for (j, c) in divisor.coeffs.iter().enumerate() {
+                rem[i + j] = rem[i + j].clone() - q.clone() * c.clone();
+            }
+            // 浮点数相减可能留下很小的误差，这一项按定义就是 0
+            rem[i + d - 1] = T::zero();
+            quotient[i] = q;
+        }
+        Ok((Self::new(quotient), Self::new(rem)))
+    }
+
+    /// 首项系数化为 1；整数系数不能被首项整除时返回 `Inexact`
+    pub fn monic(&self) -> Result<Self, PolyError> {
+        let lead = self.leading().ok_or(PolyError::DivisionByZero)?.clone();
+        let coeffs = self
+            .coeffs
+            .iter()
+            .map(|c| {
+                let q = c.clone() / lead.clone();
+                if T::EXACT && q.clone() * lead.clone() != *c {
+                    return Err(PolyError::Inexact);
+                }
+                Ok(q)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(coeffs))
+    }
+
+    /// # 最大公因式
+    /// 辗转相除，结果化为首一多项式；需要系数能整除（如 Ratio），
+    /// 整数系数化不成首一时返回 `Inexact`，f64 系数的舍入误差会让余数很难恰好为 0
+    pub fn gcd(&self, other: &Self) -> Result<Self, PolyError> {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, r) = a.div_rem(&b)?;
+            a = b;
+            b = r;
+        }
+        if a.is_zero() {
+            return Ok(a);
+        }
+        a.monic()
+    }
+}
+
+impl<T: Coefficient + ToPrimitive> Polynomial<T> {
+    /// 把系数转成 f64 后在复数上求值
+    pub fn eval_complex(&self, z: Complex<f64>) -> Complex<f64> {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(Complex::zero(), |acc, c| acc * z + c.to_f64().unwrap_or(f64::NAN))
+    }
+
+    /// # Durand–Kerner 求全部复数根
+    /// 对首一多项式 p，同时迭代 n 个近似根：
+    /// `z_i ← z_i - p(z_i) / Π_{j≠i}(z_i - z_j)`
+    pub fn roots(&self) -> Vec<Complex<f64>> {
+        let n = match self.degree() {
+            None | Some(0) => return vec![],
+            Some(n) => n,
+        };
+        let lead = self.leading().and_then(|c| c.to_f64()).unwrap_or(f64::NAN);
+        let monic: Vec<f64> = self.coeffs.iter().map(|c| c.to_f64().unwrap_or(f64::NAN) / lead).collect();
+        let eval = |z: Complex<f64>| monic.iter().rev().fold(Complex::zero(), |acc: Complex<f64>, c| acc * z + c);
+
+        // 初始值取 (0.4 + 0.9i)^k，避免落在实轴或单位根上
+        let seed = Complex::new(0.4, 0.9);
+        let mut roots: Vec<Complex<f64>> = (0..n as i32).map(|k| seed.powi(k)).collect();
+        for _ in 0..1000 {
+            let mut max_step: f64 = 0.0;
+            for i in 0..n {
+                let denominator: Complex<f64> = (0..n)
+                    .filter(|&j| j != i)
+                    .map(|j| roots[i] - roots[j])
+                    .product();
+                let step: Complex<f64> = eval(roots[i]) / denominator;
+                roots[i] -= step;
+                max_step = max_step.max(step.norm());
+            }
+            if max_step < 1e-15 {
+                break;
+            }
+        }
+        roots
+    }
+}
+
+impl<T: Coefficient> Add for Polynomial<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (mut long, short) = if self.coeffs.len() >= rhs.coeffs.len() { (self, rhs) } else { (rhs, self) };
+        for (i, c) in short.coeffs.into_iter().enumerate() {
+            long.coeffs[i] = long.coeffs[i].clone() + c;
+        }
+        Self::new(long.coeffs)
+    }
+}
+
+impl<T: Coefficient + Neg<Output = T>> Neg for Polynomial<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(self.coeffs.into_iter().map(|c| -c).collect())
+    }
+}
+
+impl<T: Coefficient> Sub for Polynomial<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let len = self.coeffs.len().max(rhs.coeffs.len());
+        let at = |p: &Self, i: usize| p.coeffs.get(i).cloned().unwrap_or_else(T::zero);
+        Self::new((0..len).map(|i| at(&self, i) - at(&rhs, i)).collect())
+    }
+}
+
+impl<T: Coefficient> Mul for Polynomial<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+        let mut coeffs = vec![T::zero(); self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in rhs.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j].clone() + a.clone() * b.clone();
+            }
+        }
+        Self::new(coeffs)
+    }
+}
+
+/// 按降幂输出，如 `3x^2 - 2x + 1`；系数为 1 时省略（常数项除外）
+impl<T: Coefficient + Display + PartialOrd + Neg<Output = T>> Display for Polynomial<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut first = true;
+        for (power, c) in self.coeffs.iter().enumerate().rev() {
+            if c.is_zero() {
+                continue;
+            }
+            let negative = *c < T::zero();
+            let abs = if negative { -c.clone() } else { c.clone() };
+            match (first, negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+            if !abs.is_one() || power == 0 {
+                write!(f, "{}", abs)?;
+            }
+            match power {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{}", power)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+type Q = Ratio<i64>;
+
+fn q(n: i64) -> Q {
+    Q::from_integer(n)
+}
+
+fn arithmetic() {
+    let p = Polynomial::from_descending(vec![3, -2, 1]);
+    let r = Polynomial::from_descending(vec![1, 1]);
+    println!("p = {}", p);
+    println!("r = {}", r);
+    println!("p + r = {}", p.clone() + r.clone());
+    println!("p - r = {}", p.clone() - r.clone());
+    println!("p * r = {}", p.clone() * r.clone());
+    println!("-p = {}", -p.clone());
+
+    assert_eq!(p.to_string(), "3x^2 - 2x + 1");
+    assert_eq!((p.clone() * r.clone()).to_string(), "3x^3 + x^2 - x + 1");
+    assert_eq!((p.clone() - p.clone()).degree(), None);
+
+    let half = Polynomial::new(vec![Q::new(1, 2), Q::new(-3, 4)]);
+    println!("有理数系数：{}", half);
+    assert_eq!(half.to_string(), "-3/4x + 1/2");
+}
+
+fn eval_and_derivative() {
+    let p = Polynomial::from_descending(vec![2.0, 0.0, -3.0, 1.0]);
+    println!("p(x) = {}，p(2) = {}", p, p.eval(2.0));
+    println!("p'(x) = {}，p''(x) = {}", p.derivative(), p.derivative().derivative());
+    assert_eq!(p.eval(2.0), 11.0);
+    assert_eq!(p.derivative(), Polynomial::from_descending(vec![6.0, 0.0, -3.0]));
+
+    let i = Complex::new(0.0, 1.0);
+    println!("p(i) = {}", p.eval_complex(i));
+    assert_eq!(p.eval_complex(i), Complex::new(1.0, -5.0));
+}
+
+fn division_and_gcd() {
+    // (x^3 - 2x^2 - 4) / (x - 3) = x^2 + x + 3 余 5
+    let a = Polynomial::from_descending(vec![q(1), q(-2), q(0), q(-4)]);
+    let b = Polynomial::from_descending(vec![q(1), q(-3)]);
+    let (quotient, rem) = a.div_rem(&b).unwrap();
+    println!("({}) / ({}) = {} 余 {}", a, b, quotient, rem);
+    assert_eq!(quotient.clone() * b.clone() + rem.clone(), a);
+    assert_eq!(rem, Polynomial::new(vec![q(5)]));
+
+    // 整数系数不能整除时返回错误
+    let x = Polynomial::from_descending(vec![1, 0]);
+    let two_x = Polynomial::from_descending(vec![2, 0]);
+    assert_eq!(x.div_rem(&two_x), Err(PolyError::Inexact));
+    assert_eq!(x.div_rem(&Polynomial::zero()), Err(PolyError::DivisionByZero));
+
+    // 浮点数系数不检查整除：x / 49x = 1/49
+    let x = Polynomial::from_descending(vec![1.0, 0.0]);
+    let (quotient, rem) = x.div_rem(&Polynomial::from_descending(vec![49.0, 0.0])).unwrap();
+    assert_eq!(quotient.coeffs(), &[1.0 / 49.0]);
+    assert!(rem.is_zero());
+    // (x^2 + 1) / (3x - 1)，余数的次数小于除数
+    let (quotient, rem) = Polynomial::from_descending(vec![1.0f64, 0.0, 1.0]).div_rem(&Polynomial::from_descending(vec![3.0, -1.0])).unwrap();
+    println!("(x^2 + 1) / (3x - 1) = {} 余 {}", quotient, rem);
+    assert_eq!(rem.degree(), Some(0));
+    assert!((rem.coeffs()[0] - 10.0 / 9.0).abs() < 1e-12);
+
+    // (x - 1)(x + 2)² 与 (x + 2)(x - 5) 的最大公因式是 x + 2
+    let xm1 = Polynomial::from_descending(vec![q(1), q(-1)]);
+    let xp2 = Polynomial::from_descending(vec![q(1), q(2)]);
+    let xm5 = Polynomial::from_descending(vec![q(1), q(-5)]);
+    let f = xm1 * xp2.clone() * xp2.clone();
+    let g = Polynomial::monomial(q(3), 0) * xp2.clone() * xm5;
+    let d = f.gcd(&g).unwrap();
+    println!("gcd({}, {}) = {}", f, g, d);
+    assert_eq!(d, xp2);
+
+    // 整数系数的 2x + 3 化不成首一多项式
+    let p = Polynomial::new(vec![2i64, 3]);
+    assert_eq!(p.monic(), Err(PolyError::Inexact));
+    assert_eq!(p.gcd(&p), Err(PolyError::Inexact));
+    assert_eq!(Polynomial::new(vec![3i64, 3]).monic(), Ok(Polynomial::new(vec![1, 1])));
+}
+
+fn complex_roots() {
+    // x^2 + 1 的根是 ±i
+    let p = Polynomial::from_descending(vec![1, 0, 1]);
+    show_roots(&p);
+
+    // x^3 - 6x^2 + 11x - 6 = (x - 1)(x - 2)(x - 3)
+    let p = Polynomial::from_descending(vec![1.0, -6.0, 11.0, -6.0]);
+    let mut roots = show_roots(&p);
+    roots.sort_by(|a, b| a.re.total_cmp(&b.re));
+    for (root, expected) in roots.iter().zip([1.0, 2.0, 3.0]) {
+        assert!((root - expected).norm() < 1e-9);
+    }
+
+    // x^5 - 1：五次单位根
+    let p = Polynomial::from_descending(vec![q(1), q(0), q(0), q(0), q(0), q(-1)]);
+    for root in show_roots(&p) {
+        assert!((root.norm() - 1.0).abs() < 1e-9);
+        assert!(p.eval_complex(root).norm() < 1e-9);
+    }
+}
+
+fn show_roots<T>(p: &Polynomial<T>) -> Vec<Complex<f64>>
+where
+    T: Coefficient + ToPrimitive + Display + PartialOrd + Neg<Output = T>,
+{
+    let roots = p.roots();
+    println!("{} 的根：", p);
+    for root in &roots {
+        println!("  {:.6}", root);
+    }
+    roots
+}
@@ -0,0 +1,563 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use crate::ch2::json::{self, JsonError, JsonValue};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("基本类型", Box::new(|| primitives())),
+        ("容器类型", Box::new(|| containers())),
+        ("自定义类型", Box::new(|| custom_types())),
+        ("错误路径", Box::new(|| errors())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 转换成 JSON
+pub trait ToJson {
+    fn to_json(&self) -> JsonValue;
+}
+
+/// # 从 JSON 转换
+/// 出错时 [`FromJsonError::path`] 指出是哪一层的哪个值不符合要求
+pub trait FromJson: Sized {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FromJsonErrorKind {
+    TypeMismatch { expected: &'static str, found: &'static str },
+    MissingField(String),
+    /// 数字超出目标类型的范围，或者要求整数时有小数部分
+    OutOfRange { target: &'static str, value: f64 },
+    /// 数组、字符的长度不对，例如元组、char
+    WrongLength { expected: usize, found: usize },
+    UnknownVariant(String),
+    /// 字符串无法解析成 HashMap 的键
+    InvalidKey(String),
+}
+
+/// # 转换错误
+/// `path` 与 [`JsonValue::query`] 的语法相同，例如 `.users[1].email`，空字符串表示最外层
+#[derive(Debug, Clone, PartialEq)]
+pub struct FromJsonError {
+    pub path: String,
+    pub kind: FromJsonErrorKind,
+}
+
+impl FromJsonError {
+    pub fn new(kind: FromJsonErrorKind) -> Self {
+        FromJsonError { path: String::new(), kind }
+    }
+
+    /// 错误发生在对象的某个字段中，逐层向外返回时把路径补在前面
+    pub fn at_key(mut self, key: &str) -> Self {
+        let segment = if key.chars().all(|c| c.is_alphanumeric() || c == '_') && !key.is_empty() {
+            format!(".{}", key)
+        } else {
+            format!("[\"{}\"]", key)
+        };
+        self.path.insert_str(0, &segment);
+        self
+    }
+
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.path.insert_str(0, &format!("[{}]", index));
+        self
+    }
+}
+
+impl Display for FromJsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${}：", self.path)?;
+        match &self.kind {
+            FromJsonErrorKind::TypeMismatch { expected, found } => write!(f, "期望 {}，实际是 {}", expected, found),
+            FromJsonErrorKind::MissingField(name) => write!(f, "缺少字段 {}", name),
+            FromJsonErrorKind::OutOfRange { target, value } => write!(f, "{} 超出了 {} 的范围", value, target),
+            FromJsonErrorKind::WrongLength { expected, found } => write!(f, "长度应该是 {}，实际是 {}", expected, found),
+            FromJsonErrorKind::UnknownVariant(name) => write!(f, "未知的枚举成员 {}", name),
+            FromJsonErrorKind::InvalidKey(key) => write!(f, "无效的键 {:?}", key),
+        }
+    }
+}
+
+impl std::error::Error for FromJsonError {}
+
+/// JSON 值的类型名称，用于错误信息
+pub fn type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
+pub fn mismatch(expected: &'static str, found: &JsonValue) -> FromJsonError {
+    FromJsonError::new(FromJsonErrorKind::TypeMismatch { expected, found: type_name(found) })
+}
+
+/// # 读取对象的字段
+/// 字段不存在时当作 null 处理，所以 `Option<T>` 字段可以省略，其他类型报告缺少字段
+pub fn field<T: FromJson>(value: &JsonValue, name: &str) -> Result<T, FromJsonError> {
+    if value.as_object().is_none() {
+        return Err(mismatch("object", value));
+    }
+    match value.get(name) {
+        Some(v) => T::from_json(v).map_err(|e| e.at_key(name)),
+        None => T::from_json(&JsonValue::Null)
+            .map_err(|_| FromJsonError::new(FromJsonErrorKind::MissingField(name.to_string()))),
+    }
+}
+
+/// 按给定顺序构造对象
+pub fn object<const N: usize>(members: [(&str, JsonValue); N]) -> JsonValue {
+    JsonValue::Object(members.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+}
+
+/// # 外部标记的枚举
+/// 与 serde 的默认格式相同：
+/// * 无数据的成员：`"Quit"`
+/// * 有数据的成员：`{"Write": "hi"}`、`{"Move": {"x": 1, "y": 2}}`、`{"ChangeColor": [1, 2, 3]}`
+pub fn tagged(tag: &str, content: JsonValue) -> JsonValue {
+    JsonValue::Object(vec![(tag.to_string(), content)])
+}
+
+/// 拆出成员名和数据，无数据的成员数据为 None
+pub fn variant(value: &JsonValue) -> Result<(&str, Option<&JsonValue>), FromJsonError> {
+    match value {
+        JsonValue::String(tag) => Ok((tag, None)),
+        JsonValue::Object(members) if members.len() == 1 => Ok((&members[0].0, Some(&members[0].1))),
+        JsonValue::Object(members) => {
+            Err(FromJsonError::new(FromJsonErrorKind::WrongLength { expected: 1, found: members.len() }))
+        }
+        _ => Err(mismatch("string 或 object", value)),
+    }
+}
+
+/// 取出有数据成员的数据，并在出错路径中加上成员名
+pub fn variant_content<T: FromJson>(tag: &str, content: Option<&JsonValue>) -> Result<T, FromJsonError> {
+    match content {
+        Some(content) => T::from_json(content).map_err(|e| e.at_key(tag)),
+        None => Err(FromJsonError::new(FromJsonErrorKind::TypeMismatch { expected: "object", found: "string" })),
+    }
+}
+
+pub fn unknown_variant(tag: &str) -> FromJsonError {
+    FromJsonError::new(FromJsonErrorKind::UnknownVariant(tag.to_string()))
+}
+
+/// # 序列化与反序列化的错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    Parse(JsonError),
+    Convert(FromJsonError),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::Parse(e) => write!(f, "JSON 格式错误，{}", e),
+            DecodeError::Convert(e) => write!(f, "类型不匹配，{}", e),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+pub fn to_json_string<T: ToJson + ?Sized>(value: &T) -> String {
+    value.to_json().to_compact()
+}
+
+pub fn from_json_str<T: FromJson>(src: &str) -> Result<T, DecodeError> {
+    let value = json::parse(src).map_err(DecodeError::Parse)?;
+    T::from_json(&value).map_err(DecodeError::Convert)
+}
+
+/// # 往返检查
+/// 转换成 JSON 文本、重新解析、再转换回来，结果必须与原值相等；返回 JSON 文本
+pub fn assert_round_trip<T: ToJson + FromJson + PartialEq + Debug>(value: &T) -> String {
+    let text = to_json_string(value);
+    let back: T = from_json_str(&text).unwrap_or_else(|e| panic!("{:?} -> {} 无法转换回来：{}", value, text, e));
+    assert_eq!(&back, value, "往返结果不一致：{}", text);
+    text
+}
+
+impl ToJson for JsonValue {
+    fn to_json(&self) -> JsonValue {
+        self.clone()
+    }
+}
+
+impl FromJson for JsonValue {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(value.clone())
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Bool(*self)
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        value.as_bool().ok_or_else(|| mismatch("boolean", value))
+    }
+}
+
+/// 整数先转换成 f64；读取时要求没有小数部分且在目标类型的范围内。
+/// JSON 数字用 f64 保存，绝对值超过 2^53 的 i64/u64 会损失精度
+macro_rules! impl_json_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ToJson for $ty {
+                fn to_json(&self) -> JsonValue {
+                    JsonValue::Number(*self as f64)
+                }
+            }
+
+            impl FromJson for $ty {
+                fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+                    let n = value.as_f64().ok_or_else(|| mismatch("number", value))?;
+                    // MAX + 1 是 2 的幂，转成 f64 没有误差；`MAX as f64` 对 64 位整数会进位成 MAX + 1
+                    let upper = <$ty>::MAX as f64 + 1.0;
+                    if n.fract() != 0.0 || n < <$ty>::MIN as f64 || n >= upper {
+                        return Err(FromJsonError::new(FromJsonErrorKind::OutOfRange { target: stringify!($ty), value: n }));
+                    }
+                    Ok(n as $ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_json_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl ToJson for f64 {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Number(*self)
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        value.as_f64().ok_or_else(|| mismatch("number", value))
+    }
+}
+
+impl ToJson for f32 {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Number(*self as f64)
+    }
+}
+
+impl FromJson for f32 {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        f64::from_json(value).map(|n| n as f32)
+    }
+}
+
+/// char 写成只有一个字符的字符串
+impl ToJson for char {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.to_string())
+    }
+}
+
+impl FromJson for char {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let s = value.as_str().ok_or_else(|| mismatch("string", value))?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FromJsonError::new(FromJsonErrorKind::WrongLength { expected: 1, found: s.chars().count() })),
+        }
+    }
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.to_string())
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.clone())
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        value.as_str().map(str::to_string).ok_or_else(|| mismatch("string", value))
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> JsonValue {
+        (**self).to_json()
+    }
+}
+
+impl<T: ToJson> ToJson for Box<T> {
+    fn to_json(&self) -> JsonValue {
+        (**self).to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Box<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        T::from_json(value).map(Box::new)
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> JsonValue {
+        self.as_slice().to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let items = value.as_array().ok_or_else(|| mismatch("array", value))?;
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| T::from_json(item).map_err(|e| e.at_index(i)))
+            .collect()
+    }
+}
+
+/// None 写成 null。`Option<Option<T>>` 的 `Some(None)` 也会写成 null，读回来是 None
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Some(v) => v.to_json(),
+            None => JsonValue::Null,
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        match value {
+            JsonValue::Null => Ok(None),
+            v => T::from_json(v).map(Some),
+        }
+    }
+}
+
+/// 键用 Display 转换成字符串，按字符串排序输出，保证结果稳定
+impl<K: Display, V: ToJson> ToJson for HashMap<K, V> {
+    fn to_json(&self) -> JsonValue {
+        let mut members: Vec<(String, JsonValue)> = self.iter().map(|(k, v)| (k.to_string(), v.to_json())).collect();
+        members.sort_by(|a, b| a.0.cmp(&b.0));
+        JsonValue::Object(members)
+    }
+}
+
+/// 键用 FromStr 从字符串解析，所以 `HashMap<u32, V>` 这样的数字键也可以
+impl<K: FromStr + Eq + Hash, V: FromJson> FromJson for HashMap<K, V> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let members = value.as_object().ok_or_else(|| mismatch("object", value))?;
+        members
+            .iter()
+            .map(|(k, v)| {
+                let key = k
+                    .parse()
+                    .map_err(|_| FromJsonError::new(FromJsonErrorKind::InvalidKey(k.clone())).at_key(k))?;
+                Ok((key, V::from_json(v).map_err(|e| e.at_key(k))?))
+            })
+            .collect()
+    }
+}
+
+/// 元组写成数组
+macro_rules! impl_json_tuple {
+    ($len:expr; $($name:ident $index:tt),+) => {
+        impl<$($name: ToJson),+> ToJson for ($($name,)+) {
+            fn to_json(&self) -> JsonValue {
+                JsonValue::Array(vec![$(self.$index.to_json()),+])
+            }
+        }
+
+        impl<$($name: FromJson),+> FromJson for ($($name,)+) {
+            fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+                let items = value.as_array().ok_or_else(|| mismatch("array", value))?;
+                if items.len() != $len {
+                    return Err(FromJsonError::new(FromJsonErrorKind::WrongLength { expected: $len, found: items.len() }));
+                }
+                Ok(($($name::from_json(&items[$index]).map_err(|e| e.at_index($index))?,)+))
+            }
+        }
+    };
+}
+
+impl_json_tuple!(1; A 0);
+impl_json_tuple!(2; A 0, B 1);
+impl_json_tuple!(3; A 0, B 1, C 2);
+impl_json_tuple!(4; A 0, B 1, C 2, D 3);
+
+fn primitives() {
+    println!("{}", assert_round_trip(&true));
+    println!("{}", assert_round_trip(&-128i8));
+    println!("{}", assert_round_trip(&u32::MAX));
+    println!("{}", assert_round_trip(&(1i64 << 53)));
+    println!("{}", assert_round_trip(&0.1f64));
+    println!("{}", assert_round_trip(&1.5f32));
+    println!("{}", assert_round_trip(&'中'));
+    println!("{}", assert_round_trip(&"引号\"与换行\n".to_string()));
+
+    assert_eq!(from_json_str::<u8>("256").unwrap_err().to_string(), "类型不匹配，$：256 超出了 u8 的范围");
+    assert!(from_json_str::<i32>("1.5").is_err());
+    // 2^64、2^63 刚好超出 u64、i64 的范围，不能饱和成 MAX
+    assert!(from_json_str::<u64>("18446744073709551616").is_err());
+    assert!(from_json_str::<i64>("9223372036854775808").is_err());
+    assert_eq!(from_json_str::<i64>("-9223372036854775808").unwrap(), i64::MIN);
+    assert_eq!(from_json_str::<u32>("4294967295").unwrap(), u32::MAX);
+    assert!(from_json_str::<u32>("4294967296").is_err());
+    assert!(from_json_str::<char>("\"ab\"").is_err());
+}
+
+fn containers() {
+    println!("{}", assert_round_trip(&vec![1, 2, 3]));
+    println!("{}", assert_round_trip(&vec![Some("a".to_string()), None]));
+    println!("{}", assert_round_trip(&(1u8, "two".to_string(), [3.0f64].to_vec())));
+    println!("{}", assert_round_trip(&Vec::<Vec<i32>>::new()));
+
+    let mut scores = HashMap::new();
+    scores.insert("Blue".to_string(), vec![10, 50]);
+    scores.insert("Yellow".to_string(), vec![]);
+    println!("{}", assert_round_trip(&scores));
+
+    let mut by_id: HashMap<u32, Option<bool>> = HashMap::new();
+    by_id.insert(7, Some(true));
+    by_id.insert(42, None);
+    let text = assert_round_trip(&by_id);
+    println!("{}", text);
+    assert_eq!(text, r#"{"42":null,"7":true}"#);
+}
+
+/// 不依赖 derive，一个结构体与一个外部标记枚举的完整示例，
+/// 课程中的 User、Post、PokerCard、Message 等类型也是这样实现的
+#[derive(Debug, Clone, PartialEq)]
+struct Rectangle {
+    width: u32,
+    height: u32,
+    label: Option<String>,
+}
+
+impl ToJson for Rectangle {
+    fn to_json(&self) -> JsonValue {
+        object([
+            ("width", self.width.to_json()),
+            ("height", self.height.to_json()),
+            ("label", self.label.to_json()),
+        ])
+    }
+}
+
+impl FromJson for Rectangle {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(Rectangle {
+            width: field(value, "width")?,
+            height: field(value, "height")?,
+            label: field(value, "label")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect(Rectangle),
+    Triangle(f64, f64, f64),
+}
+
+impl ToJson for Shape {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Shape::Empty => "Empty".to_json(),
+            Shape::Circle(r) => tagged("Circle", r.to_json()),
+            Shape::Rect(rect) => tagged("Rect", rect.to_json()),
+            Shape::Triangle(a, b, c) => tagged("Triangle", (a, b, c).to_json()),
+        }
+    }
+}
+
+impl FromJson for Shape {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        match variant(value)? {
+            ("Empty", None) => Ok(Shape::Empty),
+            ("Circle", content) => Ok(Shape::Circle(variant_content("Circle", content)?)),
+            ("Rect", content) => Ok(Shape::Rect(variant_content("Rect", content)?)),
+            ("Triangle", content) => {
+                let (a, b, c) = variant_content("Triangle", content)?;
+                Ok(Shape::Triangle(a, b, c))
+            }
+            (tag, _) => Err(unknown_variant(tag)),
+        }
+    }
+}
+
+fn custom_types() {
+    let shapes = vec![
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Rect(Rectangle { width: 30, height: 50, label: Some("门".to_string()) }),
+        Shape::Rect(Rectangle { width: 1, height: 1, label: None }),
+        Shape::Triangle(3.0, 4.0, 5.0),
+    ];
+    println!("{}", shapes.to_json().to_pretty());
+    assert_round_trip(&shapes);
+
+    // Option 字段可以省略
+    let rect: Rectangle = from_json_str(r#"{"width": 2, "height": 3}"#).unwrap();
+    assert_eq!(rect, Rectangle { width: 2, height: 3, label: None });
+}
+
+fn errors() {
+    let cases = [
+        (r#"[{"Rect": {"width": 1, "height": -1}}]"#, "$[0].Rect.height：-1 超出了 u32 的范围"),
+        (r#"[{"Rect": {"width": 1}}]"#, "$[0].Rect：缺少字段 height"),
+        (r#"["Empty", {"Hexagon": 6}]"#, "$[1]：未知的枚举成员 Hexagon"),
+        (r#"[{"Triangle": [1, 2]}]"#, "$[0].Triangle：长度应该是 3，实际是 2"),
+        (r#"[{"Triangle": [1, "2", 3]}]"#, "$[0].Triangle[1]：期望 number，实际是 string"),
+        (r#"["Circle"]"#, "$[0]：期望 object，实际是 string"),
+        (r#"[{"Rect": {"width": 1, "height": 1, "label": 5}}]"#, r#"$[0].Rect.label：期望 string，实际是 number"#),
+    ];
+    for (src, expected) in cases {
+        let err = from_json_str::<Vec<Shape>>(src).unwrap_err();
+        println!("{}\n  {}", src, err);
+        assert_eq!(err.to_string(), format!("类型不匹配，{}", expected));
+    }
+
+    let err = from_json_str::<Vec<Shape>>("[\"Empty\",").unwrap_err();
+    println!("{}", err);
+    assert!(matches!(err, DecodeError::Parse(_)));
+
+    let mut map = HashMap::new();
+    map.insert("a b".to_string(), 1);
+    let err = from_json_str::<HashMap<String, String>>(&to_json_string(&map)).unwrap_err();
+    println!("{}", err);
+    assert_eq!(err.to_string(), "类型不匹配，$[\"a b\"]：期望 string，实际是 number");
+}
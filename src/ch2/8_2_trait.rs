@@ -1,5 +1,10 @@
 use std::fmt::{Debug, Display, Formatter};
 
+use head_first_rust_derive::{FieldDisplay, Summary};
+
+use crate::ch2::json::JsonValue;
+use crate::ch2::json_convert::{assert_round_trip, field, object, FromJson, FromJsonError, ToJson};
+
 pub fn main() {
     let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
         ("基本示例", Box::new(|| basic())),
@@ -10,6 +15,8 @@ pub fn main() {
         ("有条件实现方法或特征", Box::new(|| condition_bound())),
         ("函数返回 Trait", Box::new(|| return_impl_trait())),
         ("newtype", Box::new(|| new_type())),
+        ("ToJson/FromJson 特征", Box::new(|| json_traits())),
+        ("派生宏", Box::new(|| derive_macros())),
     ];
 
     for (name, function) in functions.into_iter() {
@@ -34,6 +41,8 @@ fn basic() {
 fn orphan_rule() {
     let post = Post::new("Head first Rust".to_string(), "Zhong".to_string(), "一本介绍Rust的书籍".to_string());
     println!("{}", post);
+    assert_eq!(post.to_string(), "Title: Head first Rust\nAuthor: Zhong\nContent: 一本介绍Rust的书籍");
+    assert_eq!(post.summarize(), "文章：Head first Rust，作者：Zhong");
 }
 
 pub trait Summary {
@@ -46,6 +55,10 @@ pub trait Say {
     }
 }
 
+/// Summary 由 head_first_rust_derive 中的派生宏实现，
+/// 展开后和手写的 `impl Summary for Post` 相同：`format!("文章：{}，作者：{}", self.title, self.author)`
+#[derive(Debug, PartialEq, Summary)]
+#[summary(format = "文章：{title}，作者：{author}")]
 #[allow(dead_code)]
 struct Post {
     pub title: String,
@@ -61,12 +74,6 @@ impl Post {
 
 impl Say for Post {}
 
-impl Summary for Post {
-    fn summarize(&self) -> String {
-        format!("文章：{}，作者：{}", self.title, self.author)
-    }
-}
-
 /// 为 Post 实现 Display 特征
 impl Display for Post {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -78,6 +85,9 @@ impl Display for Post {
     }
 }
 
+/// 没有实现 Display，notify4 中的例子依然不能编译
+#[derive(Debug, PartialEq, Summary)]
+#[summary(format = "{username} 发表了微博=> {content}")]
 #[allow(dead_code)]
 struct Weibo {
     pub username: String,
@@ -90,18 +100,40 @@ impl Weibo {
     }
 }
 
-impl Summary for Weibo {
-    fn summarize(&self) -> String {
-        format!("{} 发表了微博=> {}", self.username, self.content)
-    }
-}
-
 impl Say for Weibo {
     fn something(&self) -> String {
         "微博".to_string()
     }
 }
 
+impl ToJson for Post {
+    fn to_json(&self) -> JsonValue {
+        object([
+            ("title", self.title.to_json()),
+            ("author", self.author.to_json()),
+            ("content", self.content.to_json()),
+        ])
+    }
+}
+
+impl FromJson for Post {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(Post::new(field(value, "title")?, field(value, "author")?, field(value, "content")?))
+    }
+}
+
+impl ToJson for Weibo {
+    fn to_json(&self) -> JsonValue {
+        object([("username", self.username.to_json()), ("content", self.content.to_json())])
+    }
+}
+
+impl FromJson for Weibo {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(Weibo::new(field(value, "username")?, field(value, "content")?))
+    }
+}
+
 fn trait_as_params() {
     let post = Post::new("Head first Rust".to_string(), "Zhong".to_string(), "一本介绍Rust的书籍".to_string());
     let weibo = Weibo::new("重".to_string(), "我发了一条微博".to_string());
@@ -261,6 +293,20 @@ fn returns_summarizable(switch: bool) -> impl Summary {
     }
 }
 
+/// # 为自己的类型实现 ToJson/FromJson
+/// 特征和类型有一个是本地的就可以实现，孤儿规则不会限制这里
+fn json_traits() {
+    let post = Post::new("Head first Rust".to_string(), "Zhong".to_string(), "一本介绍Rust的书籍".to_string());
+    let weibo = Weibo::new("重".to_string(), "我发了一条微博".to_string());
+    println!("{}", assert_round_trip(&post));
+    println!("{}", assert_round_trip(&weibo));
+
+    // 不同类型的 Summary 放在一起时，也可以用 ToJson 特征对象统一输出
+    let items: Vec<&dyn ToJson> = vec![&post, &weibo];
+    let array: Vec<JsonValue> = items.iter().map(|item| item.to_json()).collect();
+    println!("{}", JsonValue::Array(array).to_pretty());
+}
+
 /// # 为外部类型实现外部特征
 /// 绕过孤儿原则
 /// 
@@ -284,4 +330,27 @@ impl Display for Wrapper {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f,"[{}]",self.0.join(","))
     }
-}
\ No newline at end of file
+}
+
+/// # 用派生宏实现特征
+/// * `#[derive(Summary)]` 按 `#[summary(format = "...")]` 生成 `impl Summary`
+/// * `#[derive(FieldDisplay)]` 生成 `impl Display`，每个字段一行 `标签: 值`，
+///   和 Post 上手写的 Display 一样；`rename` 修改标签，`skip` 跳过字段
+#[derive(Summary, FieldDisplay)]
+#[summary(format = "《{title}》共 {pages} 页")]
+struct Book {
+    title: String,
+    #[display(rename = "页数")]
+    pages: u32,
+    #[display(skip)]
+    isbn: String,
+}
+
+fn derive_macros() {
+    let book = Book { title: "Head first Rust".to_string(), pages: 320, isbn: "978-7-0000-0000-0".to_string() };
+    println!("{}", book.summarize());
+    println!("{}", book);
+    assert_eq!(book.summarize(), "《Head first Rust》共 320 页");
+    assert_eq!(book.to_string(), "Title: Head first Rust\n页数: 320");
+    assert!(!book.to_string().contains(&book.isbn));
+}
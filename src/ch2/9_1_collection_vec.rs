@@ -1,3 +1,6 @@
+use crate::ch2::json::JsonValue;
+use crate::ch2::json_convert::{assert_round_trip, field, object, FromJson, FromJsonError, ToJson};
+
 pub fn main() {
     let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
         ("关联类型", Box::new(|| basic())),
@@ -229,7 +232,10 @@ fn struct_sort() {
         Person::new("AFoo".to_string(), 45),
     ];
     people.sort_unstable_by(|a, b| a.age.cmp(&b.age));
-    println!("{:?}", people)
+    println!("{:?}", people);
+
+    // 排序后的结果保存成 JSON 数组，读回来顺序不变
+    println!("{}", assert_round_trip(&people));
 }
 
 /// 结构体使用默认 Trait 排序
@@ -252,7 +258,7 @@ struct Item {
     original_index: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[allow(dead_code)]
 struct Person {
     name: String,
@@ -265,6 +271,18 @@ impl Person {
     }
 }
 
+impl ToJson for Person {
+    fn to_json(&self) -> JsonValue {
+        object([("name", self.name.to_json()), ("age", self.age.to_json())])
+    }
+}
+
+impl FromJson for Person {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(Person::new(field(value, "name")?, field(value, "age")?))
+    }
+}
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 struct Animal {
     age: i32,
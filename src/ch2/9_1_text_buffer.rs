@@ -0,0 +1,791 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use crate::ch2::char_index::{CharEditExt, CharIndexError, CharIndexExt};
+use crate::ch2::unicode_inspect::pad_to_width;
+use crate::rng::XorShift64;
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("间隙缓冲区", Box::new(|| gap_buffer_demo())),
+        ("绳索", Box::new(|| rope_demo())),
+        ("撤销与重做", Box::new(|| undo_redo())),
+        ("随机编辑对比", Box::new(|| cross_check())),
+        ("性能对比", Box::new(|| benchmark())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+/// # 文本缓冲区
+/// 下标都是字符下标，行号从 0 开始，`\n` 属于它所在的行
+pub trait TextBuffer: Display {
+    fn len_chars(&self) -> usize;
+
+    fn insert_at(&mut self, at: usize, text: &str) -> Result<(), CharIndexError>;
+
+    /// 删除并返回被删除的文本
+    fn remove_range(&mut self, range: Range<usize>) -> Result<String, CharIndexError>;
+
+    fn slice_chars(&self, range: Range<usize>) -> Result<String, CharIndexError>;
+
+    /// 换行符个数 + 1，空文本也有一行
+    fn line_count(&self) -> usize;
+
+    /// 第 line 行开头的字符下标，行号超出范围时返回 None
+    fn line_to_char(&self, line: usize) -> Option<usize>;
+
+    /// 字符所在的行号
+    fn char_to_line(&self, at: usize) -> Result<usize, CharIndexError>;
+
+    /// 第 line 行的内容，不含行尾的 `\n`
+    fn line(&self, line: usize) -> Option<String> {
+        let start = self.line_to_char(line)?;
+        let end = self.line_to_char(line + 1).map_or(self.len_chars(), |next| next - 1);
+        self.slice_chars(start..end).ok()
+    }
+}
+
+fn check_index(at: usize, len: usize) -> Result<(), CharIndexError> {
+    if at > len {
+        return Err(CharIndexError::OutOfBounds { index: at, len });
+    }
+    Ok(())
+}
+
+fn check_range(range: &Range<usize>, len: usize) -> Result<(), CharIndexError> {
+    if range.start > range.end {
+        return Err(CharIndexError::InvalidRange { start: range.start, end: range.end });
+    }
+    check_index(range.end, len)
+}
+
+/// 第 n 个（从 0 开始）换行符之后的字符下标
+fn after_nth_newline(chars: impl Iterator<Item = char>, n: usize) -> Option<usize> {
+    chars.enumerate().filter(|&(_, c)| c == '\n').nth(n).map(|(i, _)| i + 1)
+}
+
+/// # 直接修改 String
+/// 每次操作都要先从头数字符找到字节下标，插入、删除还要移动后面所有的字节，作为对比的基准
+impl TextBuffer for String {
+    fn len_chars(&self) -> usize {
+        self.chars().count()
+    }
+
+    fn insert_at(&mut self, at: usize, text: &str) -> Result<(), CharIndexError> {
+        self.insert_at_char(at, text)
+    }
+
+    fn remove_range(&mut self, range: Range<usize>) -> Result<String, CharIndexError> {
+        self.remove_char_range(range)
+    }
+
+    fn slice_chars(&self, range: Range<usize>) -> Result<String, CharIndexError> {
+        self.char_slice(range).map(str::to_string)
+    }
+
+    fn line_count(&self) -> usize {
+        self.matches('\n').count() + 1
+    }
+
+    fn line_to_char(&self, line: usize) -> Option<usize> {
+        match line {
+            0 => Some(0),
+            _ => after_nth_newline(self.chars(), line - 1),
+        }
+    }
+
+    fn char_to_line(&self, at: usize) -> Result<usize, CharIndexError> {
+        check_index(at, self.len_chars())?;
+        Ok(self.chars().take(at).filter(|&c| c == '\n').count())
+    }
+}
+
+/// # 间隙缓冲区
+/// 文本存成 `[前半段][空隙][后半段]`，空隙就是光标的位置：
+/// * 在光标处插入只是填充空隙，O(1)
+/// * 光标移动时把空隙一侧的字符搬到另一侧，移动距离越短越快
+/// * 连续输入、删除都集中在光标附近，所以大多数编辑器的单行编辑都很快
+///
+/// 按 `char` 存储，字符下标就是数组下标；行号需要从头扫描
+#[derive(Debug, Clone)]
+pub struct GapBuffer {
+    buf: Vec<char>,
+    gap_start: usize,
+    gap_end: usize,
+}
+
+const MIN_GAP: usize = 64;
+
+impl GapBuffer {
+    pub fn new() -> Self {
+        GapBuffer { buf: vec!['\0'; MIN_GAP], gap_start: 0, gap_end: MIN_GAP }
+    }
+
+    fn gap_len(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    /// 光标（空隙）的位置
+    pub fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    fn move_gap(&mut self, at: usize) {
+        if at < self.gap_start {
+            let n = self.gap_start - at;
+            self.buf.copy_within(at..self.gap_start, self.gap_end - n);
+            self.gap_start = at;
+            self.gap_end -= n;
+        } else if at > self.gap_start {
+            let n = at - self.gap_start;
+            self.buf.copy_within(self.gap_end..self.gap_end + n, self.gap_start);
+            self.gap_start += n;
+            self.gap_end += n;
+        }
+    }
+
+    /// 空隙不够时扩容，容量至少翻倍，保证插入的均摊复杂度
+    fn ensure_gap(&mut self, needed: usize) {
+        if self.gap_len() >= needed {
+            return;
+        }
+        let len = self.len_chars();
+        let capacity = (self.buf.len() * 2).max(len + needed + MIN_GAP);
+        let mut buf = Vec::with_capacity(capacity);
+        buf.extend_from_slice(&self.buf[..self.gap_start]);
+        buf.resize(capacity - (self.buf.len() - self.gap_end), '\0');
+        let gap_end = buf.len();
+        buf.extend_from_slice(&self.buf[self.gap_end..]);
+        self.buf = buf;
+        self.gap_end = gap_end;
+    }
+
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.buf[..self.gap_start].iter().chain(&self.buf[self.gap_end..]).copied()
+    }
+
+    pub fn char_at(&self, at: usize) -> Option<char> {
+        if at < self.gap_start {
+            Some(self.buf[at])
+        } else {
+            self.buf.get(at + self.gap_len()).copied()
+        }
+    }
+}
+
+impl Default for GapBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for GapBuffer {
+    fn from(text: &str) -> Self {
+        let mut buffer = GapBuffer::new();
+        buffer.insert_at(0, text).unwrap();
+        buffer
+    }
+}
+
+impl TextBuffer for GapBuffer {
+    fn len_chars(&self) -> usize {
+        self.buf.len() - self.gap_len()
+    }
+
+    fn insert_at(&mut self, at: usize, text: &str) -> Result<(), CharIndexError> {
+        check_index(at, self.len_chars())?;
+        let chars: Vec<char> = text.chars().collect();
+        self.move_gap(at);
+        self.ensure_gap(chars.len());
+        self.buf[self.gap_start..self.gap_start + chars.len()].copy_from_slice(&chars);
+        self.gap_start += chars.len();
+        Ok(())
+    }
+
+    fn remove_range(&mut self, range: Range<usize>) -> Result<String, CharIndexError> {
+        check_range(&range, self.len_chars())?;
+        self.move_gap(range.start);
+        let count = range.len();
+        let removed = self.buf[self.gap_end..self.gap_end + count].iter().collect();
+        self.gap_end += count;
+        Ok(removed)
+    }
+
+    fn slice_chars(&self, range: Range<usize>) -> Result<String, CharIndexError> {
+        check_range(&range, self.len_chars())?;
+        Ok(range.map(|i| self.char_at(i).unwrap()).collect())
+    }
+
+    fn line_count(&self) -> usize {
+        self.chars().filter(|&c| c == '\n').count() + 1
+    }
+
+    fn line_to_char(&self, line: usize) -> Option<usize> {
+        match line {
+            0 => Some(0),
+            _ => after_nth_newline(self.chars(), line - 1),
+        }
+    }
+
+    fn char_to_line(&self, at: usize) -> Result<usize, CharIndexError> {
+        check_index(at, self.len_chars())?;
+        Ok(self.chars().take(at).filter(|&c| c == '\n').count())
+    }
+}
+
+impl Display for GapBuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let before: String = self.buf[..self.gap_start].iter().collect();
+        let after: String = self.buf[self.gap_end..].iter().collect();
+        write!(f, "{}{}", before, after)
+    }
+}
+
+/// 叶子节点最多保存的字节数
+const MAX_LEAF: usize = 1024;
+
+/// 每个节点都记录子树的字符数和换行符数，按字符下标、行号查找时可以直接跳过整棵子树
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf { text: String, chars: usize, newlines: usize },
+    Branch { left: Box<Node>, right: Box<Node>, chars: usize, newlines: usize, depth: usize },
+}
+
+impl Node {
+    fn leaf(text: &str) -> Node {
+        Node::Leaf {
+            text: text.to_string(),
+            chars: text.chars().count(),
+            newlines: text.bytes().filter(|&b| b == b'\n').count(),
+        }
+    }
+
+    fn chars(&self) -> usize {
+        match self {
+            Node::Leaf { chars, .. } | Node::Branch { chars, .. } => *chars,
+        }
+    }
+
+    fn newlines(&self) -> usize {
+        match self {
+            Node::Leaf { newlines, .. } | Node::Branch { newlines, .. } => *newlines,
+        }
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            Node::Leaf { .. } => 0,
+            Node::Branch { depth, .. } => *depth,
+        }
+    }
+
+    fn branch(left: Node, right: Node) -> Node {
+        Node::Branch {
+            chars: left.chars() + right.chars(),
+            newlines: left.newlines() + right.newlines(),
+            depth: left.depth().max(right.depth()) + 1,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// 把文本切成不超过 MAX_LEAF 字节的叶子，再两两合并成平衡的树
+    fn build(text: &str) -> Node {
+        let mut leaves = Vec::new();
+        let mut start = 0;
+        while start < text.len() {
+            let mut end = (start + MAX_LEAF).min(text.len());
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            leaves.push(Node::leaf(&text[start..end]));
+            start = end;
+        }
+        Self::balanced(leaves)
+    }
+
+    fn balanced(mut leaves: Vec<Node>) -> Node {
+        match leaves.len() {
+            0 => Node::leaf(""),
+            1 => leaves.pop().unwrap(),
+            n => {
+                let right = leaves.split_off(n / 2);
+                Node::branch(Self::balanced(leaves), Self::balanced(right))
+            }
+        }
+    }
+
+    /// 拼接两棵树，两个小叶子直接合并成一个
+    fn concat(left: Node, right: Node) -> Node {
+        if left.chars() == 0 {
+            return right;
+        }
+        if right.chars() == 0 {
+            return left;
+        }
+        match (&left, &right) {
+            (Node::Leaf { text: a, .. }, Node::Leaf { text: b, .. }) if a.len() + b.len() <= MAX_LEAF => {
+                Node::leaf(&format!("{}{}", a, b))
+            }
+            _ => Node::branch(left, right),
+        }
+    }
+
+    /// 在字符下标 at 处分成两棵树
+    fn split(self, at: usize) -> (Node, Node) {
+        match self {
+            Node::Leaf { text, .. } => {
+                let byte = text.char_to_byte(at).unwrap();
+                (Node::leaf(&text[..byte]), Node::leaf(&text[byte..]))
+            }
+            Node::Branch { left, right, .. } => {
+                let left_chars = left.chars();
+                if at <= left_chars {
+                    let (a, b) = left.split(at);
+                    (a, Node::concat(b, *right))
+                } else {
+                    let (a, b) = right.split(at - left_chars);
+                    (Node::concat(*left, a), b)
+                }
+            }
+        }
+    }
+
+    fn collect(&self, range: Range<usize>, out: &mut String) {
+        if range.is_empty() {
+            return;
+        }
+        match self {
+            Node::Leaf { text, .. } => out.push_str(text.char_slice(range).unwrap()),
+            Node::Branch { left, right, .. } => {
+                let left_chars = left.chars();
+                if range.start < left_chars {
+                    left.collect(range.start..range.end.min(left_chars), out);
+                }
+                if range.end > left_chars {
+                    right.collect(range.start.max(left_chars) - left_chars..range.end - left_chars, out);
+                }
+            }
+        }
+    }
+
+    fn newlines_before(&self, at: usize) -> usize {
+        match self {
+            Node::Leaf { text, .. } => text.chars().take(at).filter(|&c| c == '\n').count(),
+            Node::Branch { left, right, .. } => {
+                let left_chars = left.chars();
+                if at <= left_chars {
+                    left.newlines_before(at)
+                } else {
+                    left.newlines() + right.newlines_before(at - left_chars)
+                }
+            }
+        }
+    }
+
+    /// 第 n 个换行符之后的字符下标，调用方保证 n 小于换行符总数
+    fn after_nth_newline(&self, n: usize) -> usize {
+        match self {
+            Node::Leaf { text, .. } => after_nth_newline(text.chars(), n).unwrap(),
+            Node::Branch { left, right, .. } => {
+                if n < left.newlines() {
+                    left.after_nth_newline(n)
+                } else {
+                    left.chars() + right.after_nth_newline(n - left.newlines())
+                }
+            }
+        }
+    }
+
+    fn write_to(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Node::Leaf { text, .. } => write!(f, "{}", text),
+            Node::Branch { left, right, .. } => {
+                left.write_to(f)?;
+                right.write_to(f)
+            }
+        }
+    }
+}
+
+/// # 绳索（rope）
+/// 文本切成小段存在二叉树的叶子上：
+/// * 插入、删除都是先按字符下标把树分开，再拼接起来，只复制被切开的那个叶子
+/// * 任意位置编辑、按行号查找都是 O(log n)
+/// * 树太深时整体重建一次保持平衡
+#[derive(Debug, Clone)]
+pub struct Rope {
+    root: Node,
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Rope { root: Node::leaf("") }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.root.depth()
+    }
+
+    fn take_root(&mut self) -> Node {
+        std::mem::replace(&mut self.root, Node::leaf(""))
+    }
+
+    /// 深度超过 2·log2(n) + 8 时重建
+    fn rebalance(&mut self) {
+        let limit = 2 * (usize::BITS - self.len_chars().leading_zeros()) as usize + 8;
+        if self.root.depth() > limit {
+            self.root = Node::build(&self.to_string());
+        }
+    }
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        Rope { root: Node::build(text) }
+    }
+}
+
+impl TextBuffer for Rope {
+    fn len_chars(&self) -> usize {
+        self.root.chars()
+    }
+
+    fn insert_at(&mut self, at: usize, text: &str) -> Result<(), CharIndexError> {
+        check_index(at, self.len_chars())?;
+        let (left, right) = self.take_root().split(at);
+        self.root = Node::concat(Node::concat(left, Node::build(text)), right);
+        self.rebalance();
+        Ok(())
+    }
+
+    fn remove_range(&mut self, range: Range<usize>) -> Result<String, CharIndexError> {
+        check_range(&range, self.len_chars())?;
+        let (left, rest) = self.take_root().split(range.start);
+        let (middle, right) = rest.split(range.len());
+        self.root = Node::concat(left, right);
+        self.rebalance();
+        let mut removed = String::new();
+        middle.collect(0..middle.chars(), &mut removed);
+        Ok(removed)
+    }
+
+    fn slice_chars(&self, range: Range<usize>) -> Result<String, CharIndexError> {
+        check_range(&range, self.len_chars())?;
+        let mut out = String::new();
+        self.root.collect(range, &mut out);
+        Ok(out)
+    }
+
+    fn line_count(&self) -> usize {
+        self.root.newlines() + 1
+    }
+
+    fn line_to_char(&self, line: usize) -> Option<usize> {
+        match line {
+            0 => Some(0),
+            n if n <= self.root.newlines() => Some(self.root.after_nth_newline(n - 1)),
+            _ => None,
+        }
+    }
+
+    fn char_to_line(&self, at: usize) -> Result<usize, CharIndexError> {
+        check_index(at, self.len_chars())?;
+        Ok(self.root.newlines_before(at))
+    }
+}
+
+impl Display for Rope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.root.write_to(f)
+    }
+}
+
+/// 一次编辑，撤销时执行它的逆操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Edit {
+    Insert { at: usize, text: String },
+    Remove { at: usize, text: String },
+}
+
+impl Edit {
+    fn inverse(&self) -> Edit {
+        match self {
+            Edit::Insert { at, text } => Edit::Remove { at: *at, text: text.clone() },
+            Edit::Remove { at, text } => Edit::Insert { at: *at, text: text.clone() },
+        }
+    }
+
+    fn apply(&self, buffer: &mut impl TextBuffer) {
+        // 历史记录中的编辑在当时的文本上执行成功过，按顺序重放一定合法
+        match self {
+            Edit::Insert { at, text } => buffer.insert_at(*at, text).expect("recorded edit is valid"),
+            Edit::Remove { at, text } => {
+                buffer.remove_range(*at..*at + text.chars().count()).expect("recorded edit is valid");
+            }
+        }
+    }
+}
+
+/// # 带撤销、重做的编辑器
+/// 对任意 [`TextBuffer`] 记录编辑历史，新的编辑会清空重做记录
+#[derive(Debug)]
+pub struct Editor<B: TextBuffer> {
+    buffer: B,
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl<B: TextBuffer> Editor<B> {
+    pub fn new(buffer: B) -> Self {
+        Editor { buffer, undo: vec![], redo: vec![] }
+    }
+
+    pub fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    pub fn insert(&mut self, at: usize, text: &str) -> Result<(), CharIndexError> {
+        self.buffer.insert_at(at, text)?;
+        self.undo.push(Edit::Insert { at, text: text.to_string() });
+        self.redo.clear();
+        Ok(())
+    }
+
+    pub fn remove(&mut self, range: Range<usize>) -> Result<String, CharIndexError> {
+        let at = range.start;
+        let removed = self.buffer.remove_range(range)?;
+        self.undo.push(Edit::Remove { at, text: removed.clone() });
+        self.redo.clear();
+        Ok(removed)
+    }
+
+    /// 没有可以撤销的编辑时返回 false
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(edit) => {
+                edit.inverse().apply(&mut self.buffer);
+                self.redo.push(edit);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(edit) => {
+                edit.apply(&mut self.buffer);
+                self.undo.push(edit);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn show_lines(buffer: &impl TextBuffer) {
+    for n in 0..buffer.line_count() {
+        println!("{:>3}| {}", n, buffer.line(n).unwrap());
+    }
+}
+
+fn gap_buffer_demo() {
+    let mut buffer = GapBuffer::from("fn main() {\n}\n");
+    buffer.insert_at(12, "    println!(\"你好\");\n").unwrap();
+    println!("光标在 {}", buffer.cursor());
+    buffer.insert_at(28, "，世界").unwrap();
+    show_lines(&buffer);
+    assert_eq!(buffer.line(1).as_deref(), Some("    println!(\"你好，世界\");"));
+
+    let removed = buffer.remove_range(25..32).unwrap();
+    println!("删除了 {:?}", removed);
+    assert_eq!(removed, "\"你好，世界\"");
+    assert_eq!(buffer.to_string(), "fn main() {\n    println!();\n}\n");
+    assert_eq!((buffer.line_count(), buffer.char_to_line(20), buffer.line_to_char(2)), (4, Ok(1), Some(28)));
+    assert_eq!(buffer.insert_at(100, "x"), Err(CharIndexError::OutOfBounds { index: 100, len: 30 }));
+}
+
+fn rope_demo() {
+    // 多个叶子的文本，行号查找跨越叶子
+    let text: String = (0..300).map(|i| format!("第 {} 行 line {}\n", i, i)).collect();
+    let mut rope = Rope::from(text.as_str());
+    println!("{} 个字符，{} 行，深度 {}", rope.len_chars(), rope.line_count(), rope.depth());
+    println!("第 150 行：{:?}", rope.line(150));
+    assert_eq!(rope.line(150).as_deref(), Some("第 150 行 line 150"));
+
+    let at = rope.line_to_char(100).unwrap();
+    rope.insert_at(at, "插入的一行\n").unwrap();
+    assert_eq!(rope.line(100).as_deref(), Some("插入的一行"));
+    assert_eq!(rope.char_to_line(at + 3), Ok(100));
+
+    let start = rope.line_to_char(10).unwrap();
+    let end = rope.line_to_char(20).unwrap();
+    rope.remove_range(start..end).unwrap();
+    assert_eq!(rope.line(10).as_deref(), Some("第 20 行 line 20"));
+    assert_eq!(rope.line_count(), 292);
+    println!("编辑后 {} 行，深度 {}", rope.line_count(), rope.depth());
+}
+
+fn undo_redo() {
+    let mut editor = Editor::new(Rope::from("hello"));
+    editor.insert(5, " world").unwrap();
+    editor.insert(0, ">> ").unwrap();
+    editor.remove(3..8).unwrap();
+    println!("{}", editor.buffer());
+    assert_eq!(editor.buffer().to_string(), ">>  world");
+
+    assert!(editor.undo());
+    println!("撤销：{}", editor.buffer());
+    assert_eq!(editor.buffer().to_string(), ">> hello world");
+    assert!(editor.undo());
+    assert!(editor.undo());
+    assert!(!editor.undo()); // 已经回到最初的状态
+    assert_eq!(editor.buffer().to_string(), "hello");
+
+    assert!(editor.redo());
+    println!("重做：{}", editor.buffer());
+    assert_eq!(editor.buffer().to_string(), "hello world");
+
+    // 新的编辑清空重做记录
+    editor.insert(0, "👋 ").unwrap();
+    assert!(!editor.redo());
+    assert_eq!(editor.buffer().to_string(), "👋 hello world");
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(usize, String),
+    Remove(Range<usize>),
+}
+
+/// 在长度为 len 的文本上生成随机编辑，偶尔插入换行和多字节字符
+fn random_ops(rng: &mut XorShift64, mut len: usize, count: usize) -> Vec<Op> {
+    const PIECES: &[&str] = &["a", "rust", "\n", "中文", "🦀", " ", "line\nbreak", "字"];
+    (0..count)
+        .map(|_| {
+            if len > 0 && rng.below(3) == 0 {
+                let start = rng.below(len as u64) as usize;
+                let end = (start + 1 + rng.below(20) as usize).min(len);
+                len -= end - start;
+                Op::Remove(start..end)
+            } else {
+                let text: String = (0..1 + rng.below(4)).map(|_| *rng.choose(PIECES).unwrap()).collect();
+                let at = rng.below(len as u64 + 1) as usize;
+                len += text.chars().count();
+                Op::Insert(at, text)
+            }
+        })
+        .collect()
+}
+
+fn apply_ops(buffer: &mut impl TextBuffer, ops: &[Op]) {
+    for op in ops {
+        match op {
+            Op::Insert(at, text) => buffer.insert_at(*at, text).unwrap(),
+            Op::Remove(range) => {
+                buffer.remove_range(range.clone()).unwrap();
+            }
+        }
+    }
+}
+
+/// 三种实现执行同样的随机编辑，每一步的结果都要一致
+fn cross_check() {
+    let mut rng = XorShift64::new(42);
+    for round in 0..20 {
+        let initial: String = (0..rng.below(3000)).map(|i| if i % 37 == 0 { '\n' } else { '文' }).collect();
+        let ops = random_ops(&mut rng, initial.chars().count(), 300);
+        let mut string = initial.clone();
+        let mut gap = GapBuffer::from(initial.as_str());
+        let mut rope = Rope::from(initial.as_str());
+        for op in ops.chunks(1) {
+            apply_ops(&mut string, op);
+            apply_ops(&mut gap, op);
+            apply_ops(&mut rope, op);
+            assert_eq!(gap.len_chars(), string.len_chars());
+            assert_eq!(rope.len_chars(), string.len_chars());
+        }
+        assert_eq!(gap.to_string(), string);
+        assert_eq!(rope.to_string(), string);
+        for line in 0..=string.line_count() {
+            assert_eq!(gap.line(line), string.line(line));
+            assert_eq!(rope.line(line), string.line(line));
+        }
+        let len = string.len_chars();
+        for at in [0, len / 3, len / 2, len] {
+            assert_eq!(rope.char_to_line(at), string.char_to_line(at));
+            assert_eq!(gap.char_to_line(at), string.char_to_line(at));
+        }
+        if round == 0 {
+            println!("第一轮：{} 个字符，{} 行，rope 深度 {}", len, string.line_count(), rope.depth());
+        }
+    }
+    println!("20 轮 × 300 次随机编辑，三种实现结果一致");
+}
+
+/// 各项耗时：随机位置编辑、光标处连续输入、随机行查询
+fn measure(buffer: &mut impl TextBuffer, random: &[Op], cursor: usize, lines: &[usize]) -> [Duration; 3] {
+    let start = Instant::now();
+    apply_ops(buffer, random);
+    let random_time = start.elapsed();
+
+    // 模拟连续输入：每输入 10 个字符退格一次
+    let start = Instant::now();
+    let mut at = cursor.min(buffer.len_chars());
+    for i in 0..1000 {
+        if i % 10 == 9 {
+            buffer.remove_range(at - 1..at).unwrap();
+            at -= 1;
+        } else {
+            buffer.insert_at(at, "字").unwrap();
+            at += 1;
+        }
+    }
+    let typing_time = start.elapsed();
+
+    let start = Instant::now();
+    for &line in lines {
+        assert!(buffer.line(line % buffer.line_count()).is_some());
+    }
+    [random_time, typing_time, start.elapsed()]
+}
+
+fn benchmark() {
+    let text: String = (0..1000).map(|i| format!("第 {} 行：the quick brown fox jumps over the lazy dog\n", i)).collect();
+    let mut rng = XorShift64::new(7);
+    let random = random_ops(&mut rng, text.chars().count(), 300);
+    let lines: Vec<usize> = (0..200).map(|_| rng.below(1000) as usize).collect();
+    let cursor = text.chars().count() / 2;
+    println!("初始文本 {} 个字符，{} 行（debug 模式下耗时更明显，可以用 --release 对比）", text.chars().count(), 1000);
+
+    let mut string = text.clone();
+    let mut gap = GapBuffer::from(text.as_str());
+    let mut rope = Rope::from(text.as_str());
+    let results = [
+        ("String", measure(&mut string, &random, cursor, &lines)),
+        ("GapBuffer", measure(&mut gap, &random, cursor, &lines)),
+        ("Rope", measure(&mut rope, &random, cursor, &lines)),
+    ];
+    let row = |cells: [&str; 4]| cells.map(|cell| pad_to_width(cell, 16)).join("");
+    println!("{}", row(["", "300 次随机编辑", "1000 次输入", "200 次行查询"]));
+    for (name, times) in results {
+        let [random_time, typing_time, line_time] = times.map(|time| format!("{:.2?}", time));
+        println!("{}", row([name, &random_time, &typing_time, &line_time]));
+    }
+    assert_eq!(gap.to_string(), string);
+    assert_eq!(rope.to_string(), string);
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::ch2::tokenizer::Tokenizer;
+
 pub fn main() {
     let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
         ("基本示例", Box::new(|| basic())),
@@ -159,4 +161,14 @@ fn update_when_exist() {
         *count += 1;
     }
     println!("{:?}", words_map);
+
+    // split_whitespace 只按空白切分，"你好，rust" 会被当成一个词
+    let text = "你好，Rust！hello rust";
+    let mut words_map = HashMap::new();
+    for token in Tokenizer::default().tokenize(text) {
+        let count = words_map.entry(token.normalized()).or_insert(0);
+        *count += 1;
+    }
+    println!("{:?}", words_map);
+    assert_eq!(words_map["rust"], 2);
 }
\ No newline at end of file
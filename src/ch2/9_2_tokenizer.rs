@@ -0,0 +1,253 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::ch2::unicode_inspect::{category, CharCategory};
+
+pub fn main() {
+    let functions: Vec<(&str, Box<dyn Fn()>)> = vec![
+        ("中英混排分词", Box::new(|| mixed())),
+        ("词典最长匹配", Box::new(|| dictionary())),
+        ("统计词频", Box::new(|| word_count())),
+    ];
+
+    for (name, function) in functions.into_iter() {
+        println!();
+        println!(">>>>>>>>>>开始执行：{}", name);
+        function();
+        println!("{}: 执行结束<<<<<<<<<<", name);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// 拉丁字母等按空格分词的文字，可以带数字、连字符号
+    Word,
+    Number,
+    /// 一个汉字或词典中的一个词
+    Cjk,
+}
+
+/// # 分词结果
+/// `text` 借用自输入字符串，`span` 是它在输入中的字节范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub text: &'a str,
+    pub span: Range<usize>,
+    pub kind: TokenKind,
+}
+
+impl<'a> Token<'a> {
+    /// 小写形式，本来就是小写时不分配内存
+    pub fn normalized(&self) -> Cow<'a, str> {
+        if self.text.chars().any(char::is_uppercase) {
+            Cow::Owned(self.text.to_lowercase())
+        } else {
+            Cow::Borrowed(self.text)
+        }
+    }
+}
+
+/// # 中文词典
+/// 只用来做正向最长匹配，记录最长词的字符数以限制匹配窗口
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    words: HashSet<String>,
+    max_chars: usize,
+}
+
+/// 内置的小词表，够演示用
+const BUNDLED_WORDS: &[&str] = &[
+    "你好", "世界", "中国", "中国人", "我们", "喜欢", "学习", "编程", "语言", "程序", "程序员", "所有权", "借用", "引用",
+    "生命周期", "字符串", "哈希表", "集合", "向量", "切片", "结构体", "枚举", "模式匹配", "错误处理", "泛型", "特征",
+    "闭包", "迭代器", "智能指针", "并发", "线程", "安全", "内存", "内存安全", "速度", "非常", "一门",
+];
+
+impl Dictionary {
+    pub fn new<'w>(words: impl IntoIterator<Item = &'w str>) -> Self {
+        let mut dict = Dictionary::default();
+        for word in words {
+            dict.insert(word);
+        }
+        dict
+    }
+
+    pub fn bundled() -> Self {
+        Self::new(BUNDLED_WORDS.iter().copied())
+    }
+
+    pub fn insert(&mut self, word: &str) {
+        self.max_chars = self.max_chars.max(word.chars().count());
+        self.words.insert(word.to_string());
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    /// `text` 开头能匹配到的最长词的字节长度，没有匹配时返回 None
+    fn longest_prefix(&self, text: &str) -> Option<usize> {
+        let ends: Vec<usize> = text.char_indices().skip(1).map(|(i, _)| i).chain([text.len()]).take(self.max_chars).collect();
+        ends.into_iter().rev().find(|&end| self.words.contains(&text[..end]))
+    }
+}
+
+/// # 汉字的切分方式
+#[derive(Debug, Clone, Copy)]
+pub enum CjkMode<'d> {
+    /// 每个汉字单独成词
+    PerChar,
+    /// 词典正向最长匹配，匹配不到的字单独成词
+    Dictionary(&'d Dictionary),
+}
+
+/// # 中英混排分词器
+/// * 空白、标点（包括 `，` `！` 这样的全角标点）、符号都是分隔符，不出现在结果中
+/// * 连续的字母、数字成为一个词，词内部的 `'` `-` 保留，例如 `don't`、`utf-8`
+/// * 汉字按 [`CjkMode`] 切分
+pub struct Tokenizer<'d> {
+    mode: CjkMode<'d>,
+}
+
+impl<'d> Tokenizer<'d> {
+    pub fn new(mode: CjkMode<'d>) -> Self {
+        Tokenizer { mode }
+    }
+
+    pub fn tokenize<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+        let mut chars = text.char_indices().peekable();
+        while let Some(&(start, c)) = chars.peek() {
+            match category(c) {
+                CharCategory::Cjk => {
+                    // 取出整段连续的汉字再切分
+                    let mut end = start;
+                    while let Some(&(i, c)) = chars.peek() {
+                        if category(c) != CharCategory::Cjk {
+                            break;
+                        }
+                        end = i + c.len_utf8();
+                        chars.next();
+                    }
+                    self.split_cjk(text, start..end, &mut tokens);
+                }
+                CharCategory::Letter | CharCategory::Digit => {
+                    let mut end = start;
+                    let mut has_letter = false;
+                    while let Some(&(i, c)) = chars.peek() {
+                        match category(c) {
+                            CharCategory::Letter => has_letter = true,
+                            CharCategory::Digit | CharCategory::Mark => {}
+                            // 连接符只在两侧都是字母或数字时才算词的一部分
+                            _ if is_joiner(c) && starts_word(&text[i + c.len_utf8()..]) => {}
+                            _ => break,
+                        }
+                        end = i + c.len_utf8();
+                        chars.next();
+                    }
+                    let kind = if has_letter { TokenKind::Word } else { TokenKind::Number };
+                    tokens.push(Token { text: &text[start..end], span: start..end, kind });
+                }
+                _ => {
+                    chars.next();
+                }
+            }
+        }
+        tokens
+    }
+
+    fn split_cjk<'a>(&self, text: &'a str, run: Range<usize>, tokens: &mut Vec<Token<'a>>) {
+        let mut pos = run.start;
+        while pos < run.end {
+            let rest = &text[pos..run.end];
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let len = match self.mode {
+                CjkMode::PerChar => first,
+                CjkMode::Dictionary(dict) => dict.longest_prefix(rest).unwrap_or(first),
+            };
+            tokens.push(Token { text: &text[pos..pos + len], span: pos..pos + len, kind: TokenKind::Cjk });
+            pos += len;
+        }
+    }
+}
+
+impl Default for Tokenizer<'_> {
+    fn default() -> Self {
+        Tokenizer::new(CjkMode::PerChar)
+    }
+}
+
+fn is_joiner(c: char) -> bool {
+    matches!(c, '\'' | '’' | '-' | '_')
+}
+
+fn starts_word(s: &str) -> bool {
+    s.chars().next().is_some_and(|c| matches!(category(c), CharCategory::Letter | CharCategory::Digit))
+}
+
+/// 按小写形式统计词频
+pub fn word_frequencies<'a>(tokenizer: &Tokenizer, text: &'a str) -> HashMap<Cow<'a, str>, usize> {
+    let mut counts = HashMap::new();
+    for token in tokenizer.tokenize(text) {
+        *counts.entry(token.normalized()).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn texts<'a>(tokens: &[Token<'a>]) -> Vec<&'a str> {
+    tokens.iter().map(|t| t.text).collect()
+}
+
+fn mixed() {
+    let text = "你好，Rust！Don't panic: UTF-8 编码 2024 年";
+    println!("split_whitespace：{:?}", text.split_whitespace().collect::<Vec<_>>());
+
+    let tokens = Tokenizer::default().tokenize(text);
+    for token in &tokens {
+        println!("{:>2}..{:<2} {:?} {:?} -> {}", token.span.start, token.span.end, token.kind, token.text, token.normalized());
+    }
+    assert_eq!(
+        texts(&tokens),
+        vec!["你", "好", "Rust", "Don't", "panic", "UTF-8", "编", "码", "2024", "年"]
+    );
+    assert_eq!(tokens[8].kind, TokenKind::Number);
+    // span 总能切回原文
+    for token in &tokens {
+        assert_eq!(&text[token.span.clone()], token.text);
+    }
+    assert_eq!(tokens[2].normalized(), "rust");
+    assert!(matches!(tokens[0].normalized(), Cow::Borrowed(_)));
+
+    // 词尾的连接符不算词的一部分
+    assert_eq!(texts(&Tokenizer::default().tokenize("rust- 'hi' snake_case")), vec!["rust", "hi", "snake_case"]);
+}
+
+fn dictionary() {
+    let dict = Dictionary::bundled();
+    let tokenizer = Tokenizer::new(CjkMode::Dictionary(&dict));
+    let text = "我们喜欢Rust的所有权和生命周期，中国人学编程！";
+    let tokens = tokenizer.tokenize(text);
+    println!("{}", texts(&tokens).join(" / "));
+    assert_eq!(
+        texts(&tokens),
+        vec!["我们", "喜欢", "Rust", "的", "所有权", "和", "生命周期", "中国人", "学", "编程"]
+    );
+
+    // 自定义词典：最长匹配优先
+    let custom = Dictionary::new(["南京", "南京市", "长江", "长江大桥", "市长"]);
+    let tokens = Tokenizer::new(CjkMode::Dictionary(&custom)).tokenize("南京市长江大桥");
+    println!("{}", texts(&tokens).join(" / "));
+    assert_eq!(texts(&tokens), vec!["南京市", "长江大桥"]);
+}
+
+fn word_count() {
+    let text = "Hello world，hello WORLD！你好世界";
+    let dict = Dictionary::bundled();
+    let counts = word_frequencies(&Tokenizer::new(CjkMode::Dictionary(&dict)), text);
+    let mut sorted: Vec<_> = counts.iter().collect();
+    sorted.sort();
+    println!("{:?}", sorted);
+    assert_eq!(counts["hello"], 2);
+    assert_eq!(counts["world"], 2);
+    assert_eq!(counts["世界"], 1);
+}
@@ -7,6 +7,21 @@ pub mod basic_type;
 #[path = "2_1_number.rs"]
 pub mod number;
 
+#[path = "2_1_float_bits.rs"]
+pub mod float_bits;
+
+#[path = "2_1_bitset.rs"]
+pub mod bitset;
+
+#[path = "2_1_range.rs"]
+pub mod ranges;
+
+#[path = "2_1_fractal.rs"]
+pub mod fractal;
+
+#[path = "2_1_fft.rs"]
+pub mod fft;
+
 #[path = "2_2_char_bool_unit.rs"]
 pub mod char_bool_unit;
 
@@ -25,15 +40,48 @@ pub mod ref_or_borrow;
 #[path = "4_1_string_slice.rs"]
 pub mod string_slice;
 
+#[path = "4_1_unicode_inspect.rs"]
+pub mod unicode_inspect;
+
+#[path = "4_1_utf8_codec.rs"]
+pub mod utf8_codec;
+
+#[path = "4_1_char_index.rs"]
+pub mod char_index;
+
+#[path = "4_1_string_literal.rs"]
+pub mod string_literal;
+
+#[path = "4_1_vfs.rs"]
+pub mod vfs;
+
+#[path = "4_1_ident_case.rs"]
+pub mod ident_case;
+
+#[path = "4_1_similarity.rs"]
+pub mod similarity;
+
+#[path = "4_1_string_search.rs"]
+pub mod string_search;
+
 #[path = "4_2_tuple.rs"]
 pub mod tuple;
 
 #[path = "4_3_struct.rs"]
 pub mod structure;
 
+#[path = "4_3_user_store.rs"]
+pub mod user_store;
+
 #[path = "4_4_enum.rs"]
 pub mod enumeration;
 
+#[path = "4_4_poker.rs"]
+pub mod poker;
+
+#[path = "4_4_message_script.rs"]
+pub mod message_script;
+
 #[path = "4_5_array.rs"]
 pub mod array;
 
@@ -55,9 +103,21 @@ pub mod method;
 #[path = "8_1_generics.rs"]
 pub mod generics;
 
+#[path = "8_1_mod_int.rs"]
+pub mod mod_int;
+
+#[path = "8_1_polynomial.rs"]
+pub mod polynomial;
+
+#[path = "8_1_json.rs"]
+pub mod json;
+
 #[path = "8_2_trait.rs"]
 pub mod trait_mod;
 
+#[path = "8_2_json_convert.rs"]
+pub mod json_convert;
+
 #[path = "8_3_trait_obj.rs"]
 pub mod trait_obj;
 
@@ -67,7 +127,13 @@ pub mod trait_enhance;
 #[path = "9_1_collection_vec.rs"]
 pub mod collection_vec;
 
+#[path = "9_1_text_buffer.rs"]
+pub mod text_buffer;
+
 #[path = "9_2_collection_hashmap.rs"]
 pub mod collection_hashmap;
+
+#[path = "9_2_tokenizer.rs"]
+pub mod tokenizer;
 #[path = "10_0_lifetime.rs"]
 pub mod lifetime;
@@ -1,4 +1,5 @@
 pub mod ch2;
+pub mod rng;
 
 #[macro_export]
 macro_rules! print_size_of_val {